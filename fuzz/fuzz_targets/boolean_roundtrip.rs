@@ -0,0 +1,46 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Roundtrips an arbitrary `Vec<bool>` through [`BooleanEncoder`]/[`BooleanDecoder`], the
+//! way `rle_v2_delta_decode` roundtrips integers through `RleV2Decoder`. This specifically
+//! targets the MSB-first bit ordering that `BooleanEncoder::finish` has to produce (ORC
+//! stores validity/boolean bits from the MSB down, opposite to Arrow's LSB-first
+//! `BooleanBufferBuilder`), and the `reverse_bits` step that bridges the two -- an off-by-one
+//! in either direction there would silently shift or reverse every bit rather than panic,
+//! so a direct roundtrip assertion is the only way to catch it.
+//!
+//! Run with `cargo fuzz run boolean_roundtrip`; regressions get checked in under
+//! `fuzz/corpus/boolean_roundtrip/`.
+
+#![no_main]
+
+use arrow::buffer::BooleanBuffer;
+use libfuzzer_sys::fuzz_target;
+use orc_rust::encoding::boolean::{BooleanDecoder, BooleanEncoder};
+use orc_rust::encoding::PrimitiveValueDecoder;
+
+fuzz_target!(|values: Vec<bool>| {
+    let mut encoder = BooleanEncoder::new();
+    encoder.extend_bb(&BooleanBuffer::from_iter(values.iter().copied()));
+    let encoded = encoder.finish();
+
+    let mut decoder = BooleanDecoder::new(encoded.as_ref());
+    let mut actual = vec![false; values.len()];
+    decoder.decode(&mut actual).unwrap();
+
+    assert_eq!(actual, values);
+});
@@ -20,65 +20,123 @@ use std::sync::Arc;
 
 use arrow::array::ArrayRef;
 use arrow::buffer::NullBuffer;
-use arrow::datatypes::Decimal128Type;
-use snafu::ResultExt;
+use arrow::datatypes::{i256, Decimal128Type, Decimal256Type, DecimalType};
+use snafu::{OptionExt, ResultExt};
 
-use crate::encoding::decimal::UnboundedVarintStreamDecoder;
-use crate::encoding::integer::get_rle_reader;
+use crate::encoding::decimal::{UnboundedVarintStreamDecoder, UnboundedVarintStreamDecoder256};
+use crate::encoding::integer::{get_rle_reader_with_limits, DecodeLimits};
 use crate::encoding::PrimitiveValueDecoder;
-use crate::error::ArrowSnafu;
+use crate::error::{ArrowSnafu, OutOfSpecSnafu};
 use crate::proto::stream::Kind;
 use crate::stripe::Stripe;
 use crate::{column::Column, error::Result};
 
 use super::{ArrayBatchDecoder, PresentDecoder, PrimitiveArrayDecoder};
 
+/// Decodes a Decimal128/Decimal256 column into the matching Arrow array, honoring the
+/// `Present` stream like the other `ArrayBatchDecoder`s. ORC stores decimals as two
+/// streams: `Data` holds each row's unscaled value as a base-128, zig-zag-encoded
+/// varint of unbounded width, and `Secondary` holds each row's own scale as an RLE
+/// V1/V2-encoded signed integer; [`DecimalScaleRepairDecoder`] rescales every value from
+/// its row's scale to the column's fixed `scale` before it reaches the output array.
+///
+/// `NInt` is implemented for `i128` (see [`crate::encoding::integer::NInt`]) and
+/// [`fix_decimal_scale`] below already does the checked rescale-and-validate arithmetic
+/// (widen by multiplying, narrow by truncating division, then bounds-check against
+/// `precision`) this decoder needs -- both ends of decimal support this module's doc
+/// comment describes are already in place.
+/// Called from [`array_decoder_factory`](super::array_decoder_factory)'s `DataType::Decimal`
+/// arms, once for a requested `Decimal128` and once for `Decimal256`, each passing the
+/// Arrow field's own `precision`/`scale` straight through as `precision`/`fixed_scale`.
 pub fn new_decimal_decoder(
     column: &Column,
     stripe: &Stripe,
     precision: u32,
     fixed_scale: u32,
+    decode_limits: DecodeLimits,
 ) -> Result<Box<dyn ArrayBatchDecoder>> {
-    let varint_iter = stripe.stream_map().get(column, Kind::Data);
-    let varint_iter = Box::new(UnboundedVarintStreamDecoder::new(varint_iter));
+    // Decimal unscaled values are written as a plain (unbounded) varint stream rather
+    // than RLE v1/v2: those encodings' bit-width fields top out at 64 bits (see
+    // `rle_v2_decode_bit_width`), too narrow for the up-to-256-bit unscaled values a
+    // high precision Decimal can require. `NInt` is implemented for `i128` so the rest
+    // of the RLE decode path (e.g. the `Secondary` scale stream below) can still share
+    // the generic machinery, but the `Data` stream itself must stay on this dedicated
+    // decoder.
+    //
+    // Precision beyond what an `i128` unscaled value can hold (i.e. more than 38
+    // digits) needs the `i256`-backed Decimal256 path instead.
+    if precision > Decimal128Type::MAX_PRECISION as u32 {
+        let varint_iter = stripe.stream_map().get(column, Kind::Data);
+        let varint_iter: Box<dyn PrimitiveValueDecoder<i256> + Send> =
+            Box::new(UnboundedVarintStreamDecoder256::new(varint_iter));
 
-    // Scale is specified on a per varint basis (in addition to being encoded in the type)
-    let scale_iter = stripe.stream_map().get(column, Kind::Secondary);
-    let scale_iter = get_rle_reader::<i32, _>(column, scale_iter)?;
+        // Scale is specified on a per varint basis (in addition to being encoded in the type)
+        let scale_iter = stripe.stream_map().get(column, Kind::Secondary);
+        let scale_iter = get_rle_reader_with_limits::<i32, _>(column, scale_iter, decode_limits)?;
 
-    let present = PresentDecoder::from_stripe(stripe, column);
+        let present = PresentDecoder::from_stripe(stripe, column);
 
-    let iter = DecimalScaleRepairDecoder {
-        varint_iter,
-        scale_iter,
-        fixed_scale,
-    };
-    let iter = Box::new(iter);
-
-    Ok(Box::new(DecimalArrayDecoder::new(
-        precision as u8,
-        fixed_scale as i8,
-        iter,
-        present,
-    )))
+        let iter = DecimalScaleRepairDecoder {
+            varint_iter,
+            scale_iter,
+            fixed_scale,
+            precision,
+            scale_scratch: Vec::new(),
+        };
+        let iter = Box::new(iter);
+
+        Ok(Box::new(DecimalArrayDecoder::<Decimal256Type>::new(
+            precision as u8,
+            fixed_scale as i8,
+            iter,
+            present,
+        )))
+    } else {
+        let varint_iter = stripe.stream_map().get(column, Kind::Data);
+        let varint_iter: Box<dyn PrimitiveValueDecoder<i128> + Send> =
+            Box::new(UnboundedVarintStreamDecoder::new(varint_iter));
+
+        // Scale is specified on a per varint basis (in addition to being encoded in the type)
+        let scale_iter = stripe.stream_map().get(column, Kind::Secondary);
+        let scale_iter = get_rle_reader_with_limits::<i32, _>(column, scale_iter, decode_limits)?;
+
+        let present = PresentDecoder::from_stripe(stripe, column);
+
+        let iter = DecimalScaleRepairDecoder {
+            varint_iter,
+            scale_iter,
+            fixed_scale,
+            precision,
+            scale_scratch: Vec::new(),
+        };
+        let iter = Box::new(iter);
+
+        Ok(Box::new(DecimalArrayDecoder::<Decimal128Type>::new(
+            precision as u8,
+            fixed_scale as i8,
+            iter,
+            present,
+        )))
+    }
 }
 
 /// Wrapper around PrimitiveArrayDecoder to allow specifying the precision and scale
-/// of the output decimal array.
-pub struct DecimalArrayDecoder {
+/// of the output decimal array. Generic over arrow's [`DecimalType`] so it covers both
+/// `Decimal128Type` (up to 38 digits of precision) and `Decimal256Type` (beyond that).
+pub struct DecimalArrayDecoder<T: DecimalType> {
     precision: u8,
     scale: i8,
-    inner: PrimitiveArrayDecoder<Decimal128Type>,
+    inner: PrimitiveArrayDecoder<T>,
 }
 
-impl DecimalArrayDecoder {
+impl<T: DecimalType> DecimalArrayDecoder<T> {
     pub fn new(
         precision: u8,
         scale: i8,
-        iter: Box<dyn PrimitiveValueDecoder<i128> + Send>,
+        iter: Box<dyn PrimitiveValueDecoder<T::Native> + Send>,
         present: Option<PresentDecoder>,
     ) -> Self {
-        let inner = PrimitiveArrayDecoder::<Decimal128Type>::new(iter, present);
+        let inner = PrimitiveArrayDecoder::<T>::new(iter, present);
         Self {
             precision,
             scale,
@@ -87,7 +145,7 @@ impl DecimalArrayDecoder {
     }
 }
 
-impl ArrayBatchDecoder for DecimalArrayDecoder {
+impl<T: DecimalType> ArrayBatchDecoder for DecimalArrayDecoder<T> {
     fn next_batch(
         &mut self,
         batch_size: usize,
@@ -105,52 +163,142 @@ impl ArrayBatchDecoder for DecimalArrayDecoder {
 
 /// This iter fixes the scales of the varints decoded as scale is specified on a per
 /// varint basis, and needs to align with type specified scale
-struct DecimalScaleRepairDecoder {
-    varint_iter: Box<dyn PrimitiveValueDecoder<i128> + Send>,
+struct DecimalScaleRepairDecoder<N> {
+    varint_iter: Box<dyn PrimitiveValueDecoder<N> + Send>,
     scale_iter: Box<dyn PrimitiveValueDecoder<i32> + Send>,
     fixed_scale: u32,
+    precision: u32,
+    /// Scratch space for the per-value scales decoded alongside `out`, reused (and
+    /// only grown, never reallocated from scratch) across calls to avoid a heap
+    /// allocation on every batch.
+    scale_scratch: Vec<i32>,
 }
 
-impl PrimitiveValueDecoder<i128> for DecimalScaleRepairDecoder {
-    fn decode(&mut self, out: &mut [i128]) -> Result<()> {
-        // TODO: can probably optimize, reuse buffers?
-        let mut varint = vec![0; out.len()];
-        let mut scale = vec![0; out.len()];
-        self.varint_iter.decode(&mut varint)?;
-        self.scale_iter.decode(&mut scale)?;
-        for (index, (&varint, &scale)) in varint.iter().zip(scale.iter()).enumerate() {
-            out[index] = fix_i128_scale(varint, self.fixed_scale, scale);
+impl<N: DecimalScale> PrimitiveValueDecoder<N> for DecimalScaleRepairDecoder<N> {
+    fn decode(&mut self, out: &mut [N]) -> Result<()> {
+        self.varint_iter.decode(out)?;
+
+        self.scale_scratch.clear();
+        self.scale_scratch.reserve(out.len());
+        self.scale_iter
+            .decode_into(&mut self.scale_scratch.spare_capacity_mut()[..out.len()])?;
+        // SAFETY: decode_into() only returns Ok once it has written every one of the
+        // out.len() elements of spare capacity reserved above.
+        unsafe { self.scale_scratch.set_len(out.len()) };
+
+        for (value, &scale) in out.iter_mut().zip(self.scale_scratch.iter()) {
+            *value = fix_decimal_scale(*value, self.fixed_scale, scale, self.precision)?;
         }
         Ok(())
     }
 }
 
-fn fix_i128_scale(i: i128, fixed_scale: u32, varying_scale: i32) -> i128 {
+/// The overflow-checked power-of-ten / multiply / divide arithmetic [`fix_decimal_scale`]
+/// needs, for whichever of the two unscaled-value widths ORC decimals can require.
+trait DecimalScale: Copy + Ord + std::ops::Neg<Output = Self> {
+    fn checked_pow10(exp: u32) -> Option<Self>;
+    fn checked_scale_mul(self, factor: Self) -> Option<Self>;
+    fn checked_scale_div(self, factor: Self) -> Option<Self>;
+}
+
+/// `10^0..=10^38`: every power of ten an `i128` unscaled value could need to be scaled
+/// by, covering Decimal128's full 38-digit precision range. Precomputed once rather than
+/// calling `i128::pow` per value.
+const POW10_I128: [i128; 39] = {
+    let mut table = [1i128; 39];
+    let mut i = 1;
+    while i < table.len() {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+    table
+};
+
+impl DecimalScale for i128 {
+    fn checked_pow10(exp: u32) -> Option<Self> {
+        POW10_I128.get(exp as usize).copied()
+    }
+
+    fn checked_scale_mul(self, factor: Self) -> Option<Self> {
+        self.checked_mul(factor)
+    }
+
+    fn checked_scale_div(self, factor: Self) -> Option<Self> {
+        self.checked_div(factor)
+    }
+}
+
+impl DecimalScale for i256 {
+    fn checked_pow10(exp: u32) -> Option<Self> {
+        let ten = i256::from_i128(10);
+        (0..exp).try_fold(i256::ONE, |acc, _| acc.checked_mul(ten))
+    }
+
+    fn checked_scale_mul(self, factor: Self) -> Option<Self> {
+        self.checked_mul(factor)
+    }
+
+    fn checked_scale_div(self, factor: Self) -> Option<Self> {
+        self.checked_div(factor)
+    }
+}
+
+/// Align `i`'s scale (as encoded per-value in the `Secondary` stream) with the column's
+/// declared `fixed_scale`, then check the result still fits in `precision` digits.
+///
+/// The widening case (`fixed_scale > varying_scale`, below) multiplies exactly. The
+/// narrowing case divides, which truncates towards zero rather than rounding -- matching
+/// the C++ reference implementation's `scaleDownInt128ByPowerOfTen`.
+fn fix_decimal_scale<N: DecimalScale>(
+    i: N,
+    fixed_scale: u32,
+    varying_scale: i32,
+    precision: u32,
+) -> Result<N> {
     // TODO: Verify with C++ impl in ORC repo, which does this cast
     //       Not sure why scale stream can be signed if it gets casted to unsigned anyway
     //       https://github.com/apache/orc/blob/0014bec1e4cdd1206f5bae4f5c2000b9300c6eb1/c%2B%2B/src/ColumnReader.cc#L1459-L1476
     let varying_scale = varying_scale as u32;
-    match fixed_scale.cmp(&varying_scale) {
+    let rescaled = match fixed_scale.cmp(&varying_scale) {
         Ordering::Less => {
             // fixed_scale < varying_scale
             // Current scale of number is greater than scale of the array type
-            // So need to divide to align the scale
-            // TODO: this differs from C++ implementation, need to verify
-            let scale_factor = varying_scale - fixed_scale;
-            // TODO: replace with lookup table?
-            let scale_factor = 10_i128.pow(scale_factor);
-            i / scale_factor
+            // So need to divide to align the scale, truncating (not rounding) any
+            // digits lost in the process
+            let scale_factor = N::checked_pow10(varying_scale - fixed_scale).context(
+                OutOfSpecSnafu {
+                    msg: "decimal scale factor overflowed while rescaling",
+                },
+            )?;
+            i.checked_scale_div(scale_factor).context(OutOfSpecSnafu {
+                msg: "decimal value overflowed while rescaling",
+            })?
         }
         Ordering::Equal => i,
         Ordering::Greater => {
             // fixed_scale > varying_scale
             // Current scale of number is smaller than scale of the array type
             // So need to multiply to align the scale
-            // TODO: this differs from C++ implementation, need to verify
-            let scale_factor = fixed_scale - varying_scale;
-            // TODO: replace with lookup table?
-            let scale_factor = 10_i128.pow(scale_factor);
-            i * scale_factor
+            let scale_factor = N::checked_pow10(fixed_scale - varying_scale).context(
+                OutOfSpecSnafu {
+                    msg: "decimal scale factor overflowed while rescaling",
+                },
+            )?;
+            i.checked_scale_mul(scale_factor).context(OutOfSpecSnafu {
+                msg: "decimal value overflowed while rescaling",
+            })?
         }
+    };
+
+    let precision_bound = N::checked_pow10(precision).context(OutOfSpecSnafu {
+        msg: "decimal precision too large to validate against",
+    })?;
+    if rescaled >= precision_bound || rescaled <= -precision_bound {
+        return OutOfSpecSnafu {
+            msg: "decimal value exceeds its column's declared precision after rescaling",
+        }
+        .fail();
     }
+
+    Ok(rescaled)
 }
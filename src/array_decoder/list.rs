@@ -17,18 +17,20 @@
 
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, ListArray};
+use arrow::array::{ArrayRef, LargeListArray, ListArray};
 use arrow::buffer::{NullBuffer, OffsetBuffer};
 use arrow::datatypes::{Field, FieldRef};
 use snafu::ResultExt;
 
 use crate::array_decoder::derive_present_vec;
 use crate::column::Column;
-use crate::encoding::integer::get_unsigned_rle_reader;
+use crate::encoding::integer::{get_unsigned_rle_reader_with_limits, DecodeLimits};
+use crate::encoding::timestamp::TimestampOverflowMode;
 use crate::encoding::PrimitiveValueDecoder;
 use crate::proto::stream::Kind;
+use crate::schema::MapKeyMode;
 
-use crate::error::{ArrowSnafu, Result};
+use crate::error::{ArrowSnafu, Result, UnexpectedSnafu};
 use crate::stripe::Stripe;
 
 use super::{array_decoder_factory, ArrayBatchDecoder, PresentDecoder};
@@ -38,23 +40,44 @@ pub struct ListArrayDecoder {
     present: Option<PresentDecoder>,
     lengths: Box<dyn PrimitiveValueDecoder<i64> + Send>,
     field: FieldRef,
+    /// Whether to produce `LargeList` (64-bit offsets) instead of `List` (32-bit),
+    /// chosen to match the caller's requested Arrow schema.
+    large: bool,
 }
 
 impl ListArrayDecoder {
-    pub fn new(column: &Column, field: Arc<Field>, stripe: &Stripe) -> Result<Self> {
+    pub fn new(
+        column: &Column,
+        field: Arc<Field>,
+        stripe: &Stripe,
+        timestamp_overflow: TimestampOverflowMode,
+        decode_limits: DecodeLimits,
+        map_key_mode: MapKeyMode,
+        decode_pool: Option<Arc<rayon::ThreadPool>>,
+        large: bool,
+    ) -> Result<Self> {
         let present = PresentDecoder::from_stripe(stripe, column);
 
         let child = &column.children()[0];
-        let inner = array_decoder_factory(child, field.clone(), stripe)?;
+        let inner = array_decoder_factory(
+            child,
+            field.clone(),
+            stripe,
+            timestamp_overflow,
+            decode_limits,
+            map_key_mode,
+            decode_pool,
+        )?;
 
         let reader = stripe.stream_map().get(column, Kind::Length);
-        let lengths = get_unsigned_rle_reader(column, reader);
+        let lengths = get_unsigned_rle_reader_with_limits(column, reader, decode_limits);
 
         Ok(Self {
             inner,
             present,
             lengths,
             field,
+            large,
         })
     }
 }
@@ -75,14 +98,36 @@ impl ArrayBatchDecoder for ListArrayDecoder {
             self.lengths.decode(&mut lengths)?;
         }
         let total_length: i64 = lengths.iter().sum();
+        if !self.large && total_length > i32::MAX as i64 {
+            return UnexpectedSnafu {
+                msg: format!(
+                    "list column '{}' has {total_length} child elements in this batch, which \
+                     overflows the 32-bit offsets of a List array -- request this column as \
+                     LargeList in the target Arrow schema instead",
+                    self.field.name(),
+                ),
+            }
+            .fail();
+        }
         // Fetch child array as one Array with total_length elements
         let child_array = self.inner.next_batch(total_length as usize, None)?;
-        let offsets = OffsetBuffer::from_lengths(lengths.into_iter().map(|l| l as usize));
         let null_buffer = present.map(NullBuffer::from);
 
-        let array = ListArray::try_new(self.field.clone(), offsets, child_array, null_buffer)
-            .context(ArrowSnafu)?;
-        let array = Arc::new(array);
+        let array: ArrayRef = if self.large {
+            let offsets =
+                OffsetBuffer::<i64>::from_lengths(lengths.into_iter().map(|l| l as usize));
+            Arc::new(
+                LargeListArray::try_new(self.field.clone(), offsets, child_array, null_buffer)
+                    .context(ArrowSnafu)?,
+            )
+        } else {
+            let offsets =
+                OffsetBuffer::<i32>::from_lengths(lengths.into_iter().map(|l| l as usize));
+            Arc::new(
+                ListArray::try_new(self.field.clone(), offsets, child_array, null_buffer)
+                    .context(ArrowSnafu)?,
+            )
+        };
         Ok(array)
     }
 }
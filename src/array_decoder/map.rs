@@ -17,17 +17,21 @@
 
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, MapArray, StructArray};
+use arrow::array::{ArrayRef, MapArray, StructArray, UInt32Array};
 use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::compute::kernels::cmp::eq;
+use arrow::compute::{sort_to_indices, take};
 use arrow::datatypes::{Field, Fields};
-use snafu::ResultExt;
+use snafu::{ensure, ResultExt};
 
 use crate::array_decoder::derive_present_vec;
 use crate::column::Column;
-use crate::encoding::integer::get_unsigned_rle_reader;
+use crate::encoding::integer::{get_unsigned_rle_reader_with_limits, DecodeLimits};
+use crate::encoding::timestamp::TimestampOverflowMode;
 use crate::encoding::PrimitiveValueDecoder;
-use crate::error::{ArrowSnafu, Result};
+use crate::error::{ArrowSnafu, DuplicateMapKeySnafu, Result};
 use crate::proto::stream::Kind;
+use crate::schema::MapKeyMode;
 use crate::stripe::Stripe;
 
 use super::{array_decoder_factory, ArrayBatchDecoder, PresentDecoder};
@@ -37,26 +41,59 @@ pub struct MapArrayDecoder {
     values: Box<dyn ArrayBatchDecoder>,
     present: Option<PresentDecoder>,
     lengths: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+    /// Scratch buffer for the decoded lengths of the current batch, reused across
+    /// `next_batch` calls (resized, not reallocated) to avoid a `vec![0; batch_size]`
+    /// allocation per call over the stripe's lifetime.
+    lengths_buf: Vec<i64>,
+    /// Name of the intermediate `entries` struct field Arrow's `Map` type wraps the key
+    /// and value columns in, as requested by the schema (see [`MapFieldNames`](crate::schema::MapFieldNames)).
+    entries_name: String,
     fields: Fields,
+    /// Set via [`ArrowReaderBuilder::with_map_key_mode`](crate::ArrowReaderBuilder::with_map_key_mode);
+    /// ORC makes no promise that a map's entries are written in any particular order, let
+    /// alone deduplicated, so this is off (preserving file order as-is) by default.
+    key_mode: MapKeyMode,
 }
 
 impl MapArrayDecoder {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         column: &Column,
+        entries_name: String,
         keys_field: Arc<Field>,
         values_field: Arc<Field>,
         stripe: &Stripe,
+        timestamp_overflow: TimestampOverflowMode,
+        decode_limits: DecodeLimits,
+        key_mode: MapKeyMode,
+        decode_pool: Option<Arc<rayon::ThreadPool>>,
     ) -> Result<Self> {
         let present = PresentDecoder::from_stripe(stripe, column);
 
         let keys_column = &column.children()[0];
-        let keys = array_decoder_factory(keys_column, keys_field.clone(), stripe)?;
+        let keys = array_decoder_factory(
+            keys_column,
+            keys_field.clone(),
+            stripe,
+            timestamp_overflow,
+            decode_limits,
+            key_mode,
+            decode_pool.clone(),
+        )?;
 
         let values_column = &column.children()[1];
-        let values = array_decoder_factory(values_column, values_field.clone(), stripe)?;
+        let values = array_decoder_factory(
+            values_column,
+            values_field.clone(),
+            stripe,
+            timestamp_overflow,
+            decode_limits,
+            key_mode,
+            decode_pool,
+        )?;
 
         let reader = stripe.stream_map().get(column, Kind::Length);
-        let lengths = get_unsigned_rle_reader(column, reader);
+        let lengths = get_unsigned_rle_reader_with_limits(column, reader, decode_limits);
 
         let fields = Fields::from(vec![keys_field, values_field]);
 
@@ -65,11 +102,57 @@ impl MapArrayDecoder {
             values,
             present,
             lengths,
+            lengths_buf: Vec::new(),
+            entries_name,
             fields,
+            key_mode,
         })
     }
 }
 
+/// Computes, for [`MapKeyMode::ValidateUnique`] and [`MapKeyMode::SortKeys`], a permutation
+/// of `keys`' indices that sorts each map entry's own keys independently -- entries don't
+/// share any ordering relationship with each other, only within their own key/value pairs --
+/// erroring out for `ValidateUnique` as soon as two equal keys turn up adjacent in that sorted
+/// order. Returns `None` for [`MapKeyMode::Unordered`], where `keys` is left exactly as the
+/// file wrote it.
+fn resolve_map_entry_order(
+    keys: &ArrayRef,
+    offsets: &OffsetBuffer<i32>,
+    key_mode: MapKeyMode,
+    name: &str,
+) -> Result<Option<UInt32Array>> {
+    if key_mode == MapKeyMode::Unordered {
+        return Ok(None);
+    }
+
+    let mut indices = Vec::with_capacity(keys.len());
+    for window in offsets.windows(2) {
+        let start = window[0] as usize;
+        let entry_len = (window[1] - window[0]) as usize;
+        if entry_len < 2 {
+            indices.extend(start as u32..start as u32 + entry_len as u32);
+            continue;
+        }
+
+        let entry_keys = keys.slice(start, entry_len);
+        let local_order = sort_to_indices(&entry_keys, None, None).context(ArrowSnafu)?;
+
+        if key_mode == MapKeyMode::ValidateUnique {
+            for pair in local_order.values().windows(2) {
+                let a = entry_keys.slice(pair[0] as usize, 1);
+                let b = entry_keys.slice(pair[1] as usize, 1);
+                let equal = eq(&a, &b).context(ArrowSnafu)?;
+                ensure!(!equal.value(0), DuplicateMapKeySnafu { name });
+            }
+        }
+
+        indices.extend(local_order.values().iter().map(|&i| start as u32 + i));
+    }
+
+    Ok(Some(UInt32Array::from(indices)))
+}
+
 impl ArrayBatchDecoder for MapArrayDecoder {
     fn next_batch(
         &mut self,
@@ -79,27 +162,133 @@ impl ArrayBatchDecoder for MapArrayDecoder {
         let present =
             derive_present_vec(&mut self.present, parent_present, batch_size).transpose()?;
 
-        let mut lengths = vec![0; batch_size];
+        self.lengths_buf.clear();
+        self.lengths_buf.resize(batch_size, 0);
         if let Some(present) = &present {
-            self.lengths.decode_spaced(&mut lengths, present)?;
+            self.lengths.decode_spaced(&mut self.lengths_buf, present)?;
         } else {
-            self.lengths.decode(&mut lengths)?;
+            self.lengths.decode(&mut self.lengths_buf)?;
         }
-        let total_length: i64 = lengths.iter().sum();
+        let total_length: i64 = self.lengths_buf.iter().sum();
         // Fetch key and value arrays, each with total_length elements
         // Fetch child array as one Array with total_length elements
         let keys_array = self.keys.next_batch(total_length as usize, None)?;
         let values_array = self.values.next_batch(total_length as usize, None)?;
+        // Iterate (rather than consume) the scratch buffer so it's kept around for reuse
+        // on the next call instead of being moved into the offsets buffer.
+        let offsets = OffsetBuffer::from_lengths(self.lengths_buf.iter().map(|&l| l as usize));
+
+        let order =
+            resolve_map_entry_order(&keys_array, &offsets, self.key_mode, &self.entries_name)?;
+        let sorted = self.key_mode == MapKeyMode::SortKeys;
+        let (keys_array, values_array) = match &order {
+            Some(indices) if sorted => (
+                take(&keys_array, indices, None).context(ArrowSnafu)?,
+                take(&values_array, indices, None).context(ArrowSnafu)?,
+            ),
+            _ => (keys_array, values_array),
+        };
+
         // Compose the keys + values array into a StructArray with two entries
         let entries =
             StructArray::try_new(self.fields.clone(), vec![keys_array, values_array], None)
                 .context(ArrowSnafu)?;
-        let offsets = OffsetBuffer::from_lengths(lengths.into_iter().map(|l| l as usize));
 
-        let field = Arc::new(Field::new_struct("entries", self.fields.clone(), false));
+        let field = Arc::new(Field::new_struct(
+            self.entries_name.clone(),
+            self.fields.clone(),
+            false,
+        ));
         let array =
-            MapArray::try_new(field, offsets, entries, present, false).context(ArrowSnafu)?;
+            MapArray::try_new(field, offsets, entries, present, sorted).context(ArrowSnafu)?;
         let array = Arc::new(array);
         Ok(array)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int32Array;
+
+    use super::*;
+
+    /// Builds an `ArrayRef`/offsets pair for the given per-map key lists, mirroring how
+    /// `next_batch` derives `keys_array`/`offsets` from the decoded lengths.
+    fn keys_and_offsets(maps: &[&[i32]]) -> (ArrayRef, OffsetBuffer<i32>) {
+        let flat: Vec<i32> = maps.iter().flat_map(|m| m.iter().copied()).collect();
+        let keys: ArrayRef = Arc::new(Int32Array::from(flat));
+        let offsets = OffsetBuffer::from_lengths(maps.iter().map(|m| m.len()));
+        (keys, offsets)
+    }
+
+    #[test]
+    fn unordered_leaves_empty_and_single_entry_maps_as_is() {
+        let (keys, offsets) = keys_and_offsets(&[&[], &[1]]);
+        let order =
+            resolve_map_entry_order(&keys, &offsets, MapKeyMode::Unordered, "m").unwrap();
+        assert!(order.is_none());
+    }
+
+    #[test]
+    fn validate_unique_allows_empty_and_single_entry_maps() {
+        let (keys, offsets) = keys_and_offsets(&[&[], &[1]]);
+        let order =
+            resolve_map_entry_order(&keys, &offsets, MapKeyMode::ValidateUnique, "m").unwrap();
+        let order = order.unwrap();
+        assert_eq!(order.values(), &[0]);
+    }
+
+    #[test]
+    fn validate_unique_allows_already_sorted_map() {
+        let (keys, offsets) = keys_and_offsets(&[&[1, 2, 3]]);
+        let order =
+            resolve_map_entry_order(&keys, &offsets, MapKeyMode::ValidateUnique, "m").unwrap();
+        assert_eq!(order.unwrap().values(), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn validate_unique_rejects_duplicate_keys() {
+        let (keys, offsets) = keys_and_offsets(&[&[1, 2, 1]]);
+        let err = resolve_map_entry_order(&keys, &offsets, MapKeyMode::ValidateUnique, "m")
+            .unwrap_err();
+        assert!(err.to_string().contains("Duplicate key"));
+    }
+
+    #[test]
+    fn validate_unique_rejects_duplicate_keys_already_adjacent() {
+        // The duplicate keys are already next to each other before sorting, so this also
+        // exercises the case where sorting doesn't need to move anything to bring them
+        // together.
+        let (keys, offsets) = keys_and_offsets(&[&[1, 1, 2]]);
+        assert!(resolve_map_entry_order(&keys, &offsets, MapKeyMode::ValidateUnique, "m").is_err());
+    }
+
+    #[test]
+    fn sort_keys_reorders_unsorted_map_independently_per_entry() {
+        let (keys, offsets) = keys_and_offsets(&[&[3, 1, 2], &[5, 4]]);
+        let order = resolve_map_entry_order(&keys, &offsets, MapKeyMode::SortKeys, "m")
+            .unwrap()
+            .unwrap();
+        // First entry is [3, 1, 2] at indices 0..3, ascending-sorting to indices [1, 2, 0];
+        // second entry is [5, 4] at indices 3..5, local to itself and sorting to [4, 3].
+        assert_eq!(order.values(), &[1, 2, 0, 4, 3]);
+    }
+
+    #[test]
+    fn sort_keys_leaves_empty_and_single_entry_maps_as_is() {
+        let (keys, offsets) = keys_and_offsets(&[&[], &[7]]);
+        let order = resolve_map_entry_order(&keys, &offsets, MapKeyMode::SortKeys, "m")
+            .unwrap()
+            .unwrap();
+        assert_eq!(order.values(), &[0]);
+    }
+
+    #[test]
+    fn sort_keys_allows_duplicate_keys() {
+        // Unlike ValidateUnique, SortKeys doesn't treat a duplicate as an error -- it just
+        // reorders, same as any other pair of equal-sorting elements would.
+        let (keys, offsets) = keys_and_offsets(&[&[2, 1, 2]]);
+        let order = resolve_map_entry_order(&keys, &offsets, MapKeyMode::SortKeys, "m").unwrap();
+        assert!(order.is_some());
+    }
+}
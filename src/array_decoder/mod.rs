@@ -17,28 +17,33 @@
 
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, BooleanArray, BooleanBufferBuilder, PrimitiveArray};
+use arrow::array::{new_null_array, ArrayRef, BooleanArray, BooleanBufferBuilder, PrimitiveArray};
 use arrow::buffer::NullBuffer;
+use arrow::compute::{can_cast_types, cast, filter_record_batch};
 use arrow::datatypes::ArrowNativeTypeOp;
 use arrow::datatypes::ArrowPrimitiveType;
 use arrow::datatypes::{DataType as ArrowDataType, Field};
 use arrow::datatypes::{
-    Date32Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, SchemaRef,
+    Date32Type, Date64Type, Decimal128Type, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, SchemaRef,
 };
 use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use rayon::prelude::*;
 use snafu::{ensure, ResultExt};
 
 use crate::column::Column;
 use crate::encoding::boolean::BooleanDecoder;
 use crate::encoding::byte::ByteRleDecoder;
 use crate::encoding::float::FloatDecoder;
-use crate::encoding::integer::get_rle_reader;
-use crate::encoding::PrimitiveValueDecoder;
+use crate::encoding::integer::{get_rle_reader_with_limits, DecodeLimits};
+use crate::encoding::timestamp::TimestampOverflowMode;
+use crate::encoding::{decode_into_vec, PrimitiveValueDecoder};
 use crate::error::{
     self, MismatchedSchemaSnafu, Result, UnexpectedSnafu, UnsupportedTypeVariantSnafu,
 };
 use crate::proto::stream::Kind;
-use crate::schema::DataType;
+use crate::row_selection::RowSelection;
+use crate::schema::{DataType, MapKeyMode};
 use crate::stripe::Stripe;
 
 use self::decimal::new_decimal_decoder;
@@ -74,8 +79,29 @@ pub trait ArrayBatchDecoder: Send {
         batch_size: usize,
         parent_present: Option<&NullBuffer>,
     ) -> Result<ArrayRef>;
+
+    /// Advances past `n` rows without returning them, for cheaply honoring an `OFFSET` or
+    /// skipping a pruned row group without paying for the discarded values' Arrow arrays.
+    ///
+    /// The default just decodes and drops a batch of `n` rows, which is no cheaper than
+    /// [`Self::next_batch`] itself -- it exists so every decoder gets a correct `skip` for
+    /// free. [`StructArrayDecoder`] overrides this to the real optimization: it skips only
+    /// its own `Present` stream, then forwards just the resulting non-null count to each
+    /// child's `skip`, so null child slots (which consume no entry in the child's value
+    /// stream at all) are never decoded into either.
+    fn skip(&mut self, n: usize) -> Result<()> {
+        if n > 0 {
+            self.next_batch(n, None)?;
+        }
+        Ok(())
+    }
 }
 
+/// [`Self::next_primitive_batch`] builds each batch in one pass: the values go straight
+/// into a contiguous `Vec<T::Native>` via [`decode_into_vec`]/[`PrimitiveValueDecoder::decode_spaced`]
+/// rather than an `append_option` call per row, and the null bitmap (when there is one)
+/// comes from [`PresentDecoder::next_buffer`]'s own bulk `BooleanBufferBuilder` packing --
+/// there's no per-row branch-and-append on this path.
 struct PrimitiveArrayDecoder<T: ArrowPrimitiveType> {
     iter: Box<dyn PrimitiveValueDecoder<T::Native> + Send>,
     present: Option<PresentDecoder>,
@@ -96,17 +122,36 @@ impl<T: ArrowPrimitiveType> PrimitiveArrayDecoder<T> {
     ) -> Result<PrimitiveArray<T>> {
         let present =
             derive_present_vec(&mut self.present, parent_present, batch_size).transpose()?;
-        let mut data = vec![T::Native::ZERO; batch_size];
         match present {
+            // Every value is null: nothing to decode, the stream wasn't even touched for
+            // these rows (see the corresponding fast path in `derive_present_vec`).
+            Some(present) if present.null_count() == present.len() => {
+                let data = vec![T::Native::ZERO; batch_size];
+                Ok(PrimitiveArray::<T>::new(data.into(), Some(present)))
+            }
+            // No nulls from the `Present` stream: decode straight into uninitialized memory,
+            // same as the genuinely-no-`Present`-stream case below. A timestamp decoder under
+            // `TimestampOverflowMode::Null` can still introduce nulls of its own here, so the
+            // null buffer isn't necessarily skipped entirely.
+            Some(present) if present.null_count() == 0 => {
+                debug_assert_eq!(present.len(), batch_size);
+                let data = decode_into_vec(self.iter.as_mut(), batch_size)?;
+                let overflow_mask = self.iter.take_overflow_mask();
+                let present = merge_overflow_mask(Some(present), overflow_mask, batch_size);
+                Ok(PrimitiveArray::<T>::new(data.into(), present))
+            }
             Some(present) => {
+                let mut data = vec![T::Native::ZERO; batch_size];
                 self.iter.decode_spaced(data.as_mut_slice(), &present)?;
-                let array = PrimitiveArray::<T>::new(data.into(), Some(present));
-                Ok(array)
+                let overflow_mask = self.iter.take_overflow_mask();
+                let present = merge_overflow_mask(Some(present), overflow_mask, batch_size);
+                Ok(PrimitiveArray::<T>::new(data.into(), present))
             }
             None => {
-                self.iter.decode(data.as_mut_slice())?;
-                let array = PrimitiveArray::<T>::from_iter_values(data);
-                Ok(array)
+                let data = decode_into_vec(self.iter.as_mut(), batch_size)?;
+                let overflow_mask = self.iter.take_overflow_mask();
+                let present = merge_overflow_mask(None, overflow_mask, batch_size);
+                Ok(PrimitiveArray::<T>::new(data.into(), present))
             }
         }
     }
@@ -124,6 +169,58 @@ impl<T: ArrowPrimitiveType> ArrayBatchDecoder for PrimitiveArrayDecoder<T> {
     }
 }
 
+/// Wraps another decoder's output with an `arrow-rs` [`cast`] to `to_type`, for a column
+/// whose natural Arrow type (what [`array_decoder_factory`] would otherwise decode it as)
+/// doesn't match what [`ArrowReaderBuilder::with_schema`](crate::arrow_reader::ArrowReaderBuilder::with_schema)
+/// asked for -- e.g. ORC `Int` requested as Arrow `Int64`, or `String` as `LargeUtf8`.
+/// [`NaiveStripeDecoder::new`] only builds one of these once [`can_cast_types`] has already
+/// confirmed the cast is supported, so the [`cast`] call here isn't expected to fail.
+struct CastArrayDecoder {
+    inner: Box<dyn ArrayBatchDecoder>,
+    to_type: ArrowDataType,
+}
+
+impl CastArrayDecoder {
+    fn new(inner: Box<dyn ArrayBatchDecoder>, to_type: ArrowDataType) -> Self {
+        Self { inner, to_type }
+    }
+}
+
+impl ArrayBatchDecoder for CastArrayDecoder {
+    fn next_batch(
+        &mut self,
+        batch_size: usize,
+        parent_present: Option<&NullBuffer>,
+    ) -> Result<ArrayRef> {
+        let array = self.inner.next_batch(batch_size, parent_present)?;
+        cast(&array, &self.to_type).context(error::ArrowSnafu)
+    }
+}
+
+/// Fills a column that [`ArrowReaderBuilder::with_schema`](crate::arrow_reader::ArrowReaderBuilder::with_schema)
+/// asked for by name but that this file doesn't have with an all-null array of the
+/// requested type, the same way a query engine's schema merge treats a column absent from
+/// one file among many sharing a logical table schema.
+struct NullArrayDecoder {
+    data_type: ArrowDataType,
+}
+
+impl NullArrayDecoder {
+    fn new(data_type: ArrowDataType) -> Self {
+        Self { data_type }
+    }
+}
+
+impl ArrayBatchDecoder for NullArrayDecoder {
+    fn next_batch(
+        &mut self,
+        batch_size: usize,
+        _parent_present: Option<&NullBuffer>,
+    ) -> Result<ArrayRef> {
+        Ok(new_null_array(&self.data_type, batch_size))
+    }
+}
+
 type Int64ArrayDecoder = PrimitiveArrayDecoder<Int64Type>;
 type Int32ArrayDecoder = PrimitiveArrayDecoder<Int32Type>;
 type Int16ArrayDecoder = PrimitiveArrayDecoder<Int16Type>;
@@ -132,6 +229,47 @@ type Float32ArrayDecoder = PrimitiveArrayDecoder<Float32Type>;
 type Float64ArrayDecoder = PrimitiveArrayDecoder<Float64Type>;
 type DateArrayDecoder = PrimitiveArrayDecoder<Date32Type>; // TODO: does ORC encode as i64 or i32?
 
+/// Decodes an ORC `Date` column (day counts) into Arrow `Date64` (millisecond counts),
+/// for callers whose target schema asks for the wider representation instead of the
+/// natural `Date32` one.
+struct Date64ArrayDecoder {
+    iter: Box<dyn PrimitiveValueDecoder<i32> + Send>,
+    present: Option<PresentDecoder>,
+}
+
+impl Date64ArrayDecoder {
+    fn new(
+        iter: Box<dyn PrimitiveValueDecoder<i32> + Send>,
+        present: Option<PresentDecoder>,
+    ) -> Self {
+        Self { iter, present }
+    }
+}
+
+impl ArrayBatchDecoder for Date64ArrayDecoder {
+    fn next_batch(
+        &mut self,
+        batch_size: usize,
+        parent_present: Option<&NullBuffer>,
+    ) -> Result<ArrayRef> {
+        let present =
+            derive_present_vec(&mut self.present, parent_present, batch_size).transpose()?;
+        let mut days = vec![0i32; batch_size];
+        match &present {
+            Some(present) if present.null_count() == present.len() => {}
+            Some(present) => self.iter.decode_spaced(&mut days, present)?,
+            None => self.iter.decode(&mut days)?,
+        }
+        // Days -> milliseconds, matching Date64's epoch-millisecond representation.
+        let millis = days
+            .into_iter()
+            .map(|d| d as i64 * 86_400_000)
+            .collect::<Vec<_>>();
+        let array = PrimitiveArray::<Date64Type>::new(millis.into(), present);
+        Ok(Arc::new(array))
+    }
+}
+
 struct BooleanArrayDecoder {
     iter: Box<dyn PrimitiveValueDecoder<bool> + Send>,
     present: Option<PresentDecoder>,
@@ -156,6 +294,16 @@ impl ArrayBatchDecoder for BooleanArrayDecoder {
             derive_present_vec(&mut self.present, parent_present, batch_size).transpose()?;
         let mut data = vec![false; batch_size];
         let array = match present {
+            // Every value is null: nothing to decode.
+            Some(present) if present.null_count() == present.len() => {
+                BooleanArray::new(data.into(), Some(present))
+            }
+            // No nulls in this batch: skip `decode_spaced` and the null buffer entirely.
+            Some(present) if present.null_count() == 0 => {
+                debug_assert_eq!(present.len(), batch_size);
+                self.iter.decode(data.as_mut_slice())?;
+                BooleanArray::from(data)
+            }
             Some(present) => {
                 self.iter.decode_spaced(data.as_mut_slice(), &present)?;
                 BooleanArray::new(data.into(), Some(present))
@@ -172,7 +320,15 @@ impl ArrayBatchDecoder for BooleanArrayDecoder {
 struct PresentDecoder {
     // TODO: ideally directly reference BooleanDecoder, doing this way to avoid
     //       the generic propagation that would be required (BooleanDecoder<R: Read>)
+    //
+    // `from_stripe` below returns `Option<Self>`, so a column with no `Present` stream
+    // (i.e. no nulls) is unambiguously `None` rather than a dummy always-true decoder --
+    // every call site matches on that directly instead of driving a fake iterator.
     inner: Box<dyn PrimitiveValueDecoder<bool> + Send>,
+    /// Scratch space `next_buffer` decodes each batch's bools into before bulk-packing
+    /// them into the returned [`NullBuffer`], reused (resized, never reallocated from
+    /// scratch) across calls rather than `vec![false; size]`-ing fresh on every batch.
+    scratch: Vec<bool>,
 }
 
 impl PresentDecoder {
@@ -182,14 +338,34 @@ impl PresentDecoder {
             .get_opt(column, Kind::Present)
             .map(|stream| {
                 let inner = Box::new(BooleanDecoder::new(stream));
-                PresentDecoder { inner }
+                PresentDecoder {
+                    inner,
+                    scratch: Vec::new(),
+                }
             })
     }
 
     fn next_buffer(&mut self, size: usize) -> Result<NullBuffer> {
-        let mut data = vec![false; size];
-        self.inner.decode(&mut data)?;
-        Ok(NullBuffer::from(data))
+        self.scratch.clear();
+        self.scratch.resize(size, false);
+        self.inner.decode(&mut self.scratch)?;
+        // Bulk-packs 8 bools per byte rather than setting each bit one at a time, unlike
+        // the bit-by-bit `BooleanBufferBuilder::append` this used to go through via
+        // `NullBuffer::from(Vec<bool>)` (which also has to allocate and fill the now-reused
+        // `scratch` buffer itself on every call).
+        let mut builder = BooleanBufferBuilder::new(size);
+        builder.append_slice(&self.scratch);
+        Ok(NullBuffer::from(builder.finish()))
+    }
+
+    /// Advances past `n` rows' worth of the `Present` stream, returning how many of them
+    /// were non-null -- i.e. how many entries the caller's child value stream(s) need to
+    /// skip in turn, since a null row consumes a `Present` bit but no value-stream entry.
+    fn skip(&mut self, n: usize) -> Result<usize> {
+        self.scratch.clear();
+        self.scratch.resize(n, false);
+        self.inner.decode(&mut self.scratch)?;
+        Ok(self.scratch.iter().filter(|&&present| present).count())
     }
 }
 
@@ -197,6 +373,11 @@ fn merge_parent_present(
     parent_present: &NullBuffer,
     present: Result<NullBuffer>,
 ) -> Result<NullBuffer> {
+    // Parent has no nulls: every child value maps 1:1 onto the parent's slots in order, so
+    // the child's own present vec is already the merged result.
+    if parent_present.null_count() == 0 {
+        return present;
+    }
     let present = present?;
     let non_null_count = parent_present.len() - parent_present.null_count();
     debug_assert!(present.len() == non_null_count);
@@ -208,12 +389,42 @@ fn merge_parent_present(
     Ok(builder.finish().into())
 }
 
+/// Merges the mask [`PrimitiveValueDecoder::take_overflow_mask`] returns, if any, into
+/// `present`: the result is null wherever either source says null. Both, if given, must be
+/// exactly `batch_size` long.
+fn merge_overflow_mask(
+    present: Option<NullBuffer>,
+    overflow_mask: Option<Vec<bool>>,
+    batch_size: usize,
+) -> Option<NullBuffer> {
+    match (present, overflow_mask) {
+        (present, None) => present,
+        (None, Some(mask)) => Some(NullBuffer::from(mask)),
+        (Some(present), Some(mask)) => {
+            debug_assert_eq!(present.len(), batch_size);
+            debug_assert_eq!(mask.len(), batch_size);
+            let merged = (0..batch_size)
+                .map(|i| present.is_valid(i) && mask[i])
+                .collect::<Vec<_>>();
+            Some(NullBuffer::from(merged))
+        }
+    }
+}
+
 fn derive_present_vec(
     present: &mut Option<PresentDecoder>,
     parent_present: Option<&NullBuffer>,
     batch_size: usize,
 ) -> Option<Result<NullBuffer>> {
     match (present, parent_present) {
+        // Parent is entirely null: the child can't hold a value in any of these slots
+        // either, so produce an all-null buffer without decoding anything from the child
+        // stream at all.
+        (_, Some(parent_present))
+            if parent_present.null_count() == parent_present.len() && parent_present.len() > 0 =>
+        {
+            Some(Ok(NullBuffer::new_null(parent_present.len())))
+        }
         (Some(present), Some(parent_present)) => {
             let element_count = parent_present.len() - parent_present.null_count();
             let present = present.next_buffer(element_count);
@@ -232,28 +443,63 @@ pub struct NaiveStripeDecoder {
     index: usize,
     batch_size: usize,
     number_of_rows: usize,
+    /// Set when the caller requested decoding with more than one thread via
+    /// [`ArrowReaderBuilder::with_decode_parallelism`](crate::ArrowReaderBuilder::with_decode_parallelism).
+    /// Column decoders read disjoint streams, so each projected column of a batch can be
+    /// decoded on its own thread in this pool and joined back in column order. Wrapped in
+    /// an `Arc` so the same pool can also be handed down to any [`StructArrayDecoder`]
+    /// among the column decoders, which fans its own children out across it too.
+    decode_pool: Option<Arc<rayon::ThreadPool>>,
+    /// Caps how many values any single RLE-decoded stream will decode over the life of
+    /// the read; see [`ArrowReaderBuilder::with_decode_value_limit`](crate::ArrowReaderBuilder::with_decode_value_limit).
+    decode_limits: DecodeLimits,
+    /// Rows to keep out of this stripe, derived from bloom-filter and/or row index
+    /// statistics pruning of its `rowIndexStride`-sized row groups (see
+    /// [`crate::row_selection`]). `None` keeps every row, same as a selection covering
+    /// the whole stripe would, just without the redundant filter pass.
+    row_selection: Option<RowSelection>,
 }
 
 impl Iterator for NaiveStripeDecoder {
     type Item = Result<RecordBatch>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.number_of_rows {
-            let record = self
-                .decode_next_batch(self.number_of_rows - self.index)
-                .transpose()?;
+        while self.index < self.number_of_rows {
+            let chunk = self.batch_size.min(self.number_of_rows - self.index);
+            if self.chunk_fully_excluded(chunk) {
+                if let Err(err) = self.skip_chunk(chunk) {
+                    return Some(Err(err));
+                }
+                self.index += chunk;
+                continue;
+            }
+
+            let record = match self.decode_next_batch(self.number_of_rows - self.index) {
+                Ok(record) => record,
+                Err(err) => return Some(Err(err)),
+            };
             self.index += self.batch_size;
-            Some(record)
-        } else {
-            None
+            return record.map(Ok);
         }
+        None
     }
 }
 
+/// Builds the decoder for one column, recursing into this same function (via
+/// `StructArrayDecoder`/`ListArrayDecoder`/`MapArrayDecoder`/`UnionArrayDecoder`'s own
+/// constructors) for each child of a compound type. Since the recursion bottoms out on
+/// whatever `field`'s Arrow type actually is rather than assuming a fixed nesting depth,
+/// a struct-of-struct, struct-of-list, struct-of-map, or any other combination of nested
+/// compound types is handled the same way a top-level one is, with no special-casing
+/// needed for deeper nesting.
 pub fn array_decoder_factory(
     column: &Column,
     field: Arc<Field>,
     stripe: &Stripe,
+    timestamp_overflow: TimestampOverflowMode,
+    decode_limits: DecodeLimits,
+    map_key_mode: MapKeyMode,
+    decode_pool: Option<Arc<rayon::ThreadPool>>,
 ) -> Result<Box<dyn ArrayBatchDecoder>> {
     let decoder: Box<dyn ArrayBatchDecoder> = match (column.data_type(), field.data_type()) {
         // TODO: try make branches more generic, reduce duplication
@@ -263,6 +509,8 @@ pub fn array_decoder_factory(
             let present = PresentDecoder::from_stripe(stripe, column);
             Box::new(BooleanArrayDecoder::new(iter, present))
         }
+        // TinyInt columns are byte-RLE encoded on their own `Data` stream, same as the
+        // Union tag stream `UnionArrayDecoder` reads with the same decoder.
         (DataType::Byte { .. }, ArrowDataType::Int8) => {
             let iter = stripe.stream_map().get(column, Kind::Data);
             let iter = Box::new(ByteRleDecoder::new(iter));
@@ -271,19 +519,19 @@ pub fn array_decoder_factory(
         }
         (DataType::Short { .. }, ArrowDataType::Int16) => {
             let iter = stripe.stream_map().get(column, Kind::Data);
-            let iter = get_rle_reader(column, iter)?;
+            let iter = get_rle_reader_with_limits(column, iter, decode_limits)?;
             let present = PresentDecoder::from_stripe(stripe, column);
             Box::new(Int16ArrayDecoder::new(iter, present))
         }
         (DataType::Int { .. }, ArrowDataType::Int32) => {
             let iter = stripe.stream_map().get(column, Kind::Data);
-            let iter = get_rle_reader(column, iter)?;
+            let iter = get_rle_reader_with_limits(column, iter, decode_limits)?;
             let present = PresentDecoder::from_stripe(stripe, column);
             Box::new(Int32ArrayDecoder::new(iter, present))
         }
         (DataType::Long { .. }, ArrowDataType::Int64) => {
             let iter = stripe.stream_map().get(column, Kind::Data);
-            let iter = get_rle_reader(column, iter)?;
+            let iter = get_rle_reader_with_limits(column, iter, decode_limits)?;
             let present = PresentDecoder::from_stripe(stripe, column);
             Box::new(Int64ArrayDecoder::new(iter, present))
         }
@@ -299,41 +547,141 @@ pub fn array_decoder_factory(
             let present = PresentDecoder::from_stripe(stripe, column);
             Box::new(Float64ArrayDecoder::new(iter, present))
         }
-        (DataType::String { .. }, ArrowDataType::Utf8)
-        | (DataType::Varchar { .. }, ArrowDataType::Utf8)
-        | (DataType::Char { .. }, ArrowDataType::Utf8) => new_string_decoder(column, stripe)?,
-        (DataType::Binary { .. }, ArrowDataType::Binary) => new_binary_decoder(column, stripe)?,
+        (
+            DataType::String { .. },
+            field_type @ (ArrowDataType::Utf8
+            | ArrowDataType::LargeUtf8
+            | ArrowDataType::Utf8View
+            | ArrowDataType::Dictionary(..)),
+        )
+        | (
+            DataType::Varchar { .. },
+            field_type @ (ArrowDataType::Utf8
+            | ArrowDataType::LargeUtf8
+            | ArrowDataType::Utf8View
+            | ArrowDataType::Dictionary(..)),
+        )
+        | (
+            DataType::Char { .. },
+            field_type @ (ArrowDataType::Utf8
+            | ArrowDataType::LargeUtf8
+            | ArrowDataType::Utf8View
+            | ArrowDataType::Dictionary(..)),
+        ) => new_string_decoder(column, field_type, stripe, decode_limits)?,
+        (
+            DataType::Binary { .. },
+            field_type @ (ArrowDataType::Binary
+            | ArrowDataType::LargeBinary
+            | ArrowDataType::BinaryView),
+        ) => new_binary_decoder(column, field_type, stripe, decode_limits)?,
+        // `new_decimal_decoder` reads the unscaled i128 off the `Data` stream and the
+        // per-row scale off `Secondary`, rescaling each row to the schema's fixed
+        // precision/scale (see its doc comment for the two streams' exact encoding).
         (
             DataType::Decimal {
                 precision, scale, ..
             },
             ArrowDataType::Decimal128(a_precision, a_scale),
-        ) if *precision as u8 == *a_precision && *scale as i8 == *a_scale => {
-            new_decimal_decoder(column, stripe, *precision, *scale)?
-        }
-        (DataType::Timestamp { .. }, field_type) => {
-            new_timestamp_decoder(column, field_type.clone(), stripe)?
-        }
-        (DataType::TimestampWithLocalTimezone { .. }, field_type) => {
-            new_timestamp_instant_decoder(column, field_type.clone(), stripe)?
+        ) if *precision == *a_precision && *scale as i8 == *a_scale => new_decimal_decoder(
+            column,
+            stripe,
+            *precision as u32,
+            *scale as u32,
+            decode_limits,
+        )?,
+        // Beyond 38 digits of precision, the unscaled value no longer fits in `i128`,
+        // so the target schema must ask for the `i256`-backed `Decimal256` instead.
+        (
+            DataType::Decimal {
+                precision, scale, ..
+            },
+            ArrowDataType::Decimal256(a_precision, a_scale),
+        ) if *precision > Decimal128Type::MAX_PRECISION
+            && *precision == *a_precision
+            && *scale as i8 == *a_scale =>
+        {
+            new_decimal_decoder(
+                column,
+                stripe,
+                *precision as u32,
+                *scale as u32,
+                decode_limits,
+            )?
         }
+        (DataType::Timestamp { .. }, field_type) => new_timestamp_decoder(
+            column,
+            field_type.clone(),
+            stripe,
+            timestamp_overflow,
+            decode_limits,
+        )?,
+        (DataType::TimestampWithLocalTimezone { .. }, field_type) => new_timestamp_instant_decoder(
+            column,
+            field_type.clone(),
+            stripe,
+            timestamp_overflow,
+            decode_limits,
+        )?,
         (DataType::Date { .. }, ArrowDataType::Date32) => {
-            // TODO: allow Date64
             let iter = stripe.stream_map().get(column, Kind::Data);
-            let iter = get_rle_reader(column, iter)?;
+            let iter = get_rle_reader_with_limits(column, iter, decode_limits)?;
             let present = PresentDecoder::from_stripe(stripe, column);
             Box::new(DateArrayDecoder::new(iter, present))
         }
+        (DataType::Date { .. }, ArrowDataType::Date64) => {
+            let iter = stripe.stream_map().get(column, Kind::Data);
+            let iter = get_rle_reader_with_limits(column, iter, decode_limits)?;
+            let present = PresentDecoder::from_stripe(stripe, column);
+            Box::new(Date64ArrayDecoder::new(iter, present))
+        }
         (DataType::Struct { .. }, ArrowDataType::Struct(fields)) => {
-            Box::new(StructArrayDecoder::new(column, fields.clone(), stripe)?)
+            Box::new(StructArrayDecoder::new(
+                column,
+                fields.clone(),
+                stripe,
+                timestamp_overflow,
+                decode_limits,
+                map_key_mode,
+                decode_pool.clone(),
+            )?)
         }
-        (DataType::List { .. }, ArrowDataType::List(field)) => {
-            // TODO: add support for ArrowDataType::LargeList
-            Box::new(ListArrayDecoder::new(column, field.clone(), stripe)?)
+        (DataType::List { .. }, ArrowDataType::List(field)) => Box::new(ListArrayDecoder::new(
+            column,
+            field.clone(),
+            stripe,
+            timestamp_overflow,
+            decode_limits,
+            map_key_mode,
+            decode_pool.clone(),
+            false,
+        )?),
+        (DataType::List { .. }, ArrowDataType::LargeList(field)) => {
+            Box::new(ListArrayDecoder::new(
+                column,
+                field.clone(),
+                stripe,
+                timestamp_overflow,
+                decode_limits,
+                map_key_mode,
+                decode_pool.clone(),
+                true,
+            )?)
         }
-        (DataType::Map { .. }, ArrowDataType::Map(entries, sorted)) => {
-            ensure!(!sorted, UnsupportedTypeVariantSnafu { msg: "Sorted map" });
-            let ArrowDataType::Struct(entries) = entries.data_type() else {
+        (DataType::Map { .. }, ArrowDataType::Map(entries_field, sorted)) => {
+            // Any two-field struct is accepted regardless of field names (the
+            // requested schema's `entries`/`keys`/`values` naming need not match ORC's
+            // own `entries`/`key`/`value` convention): ORC's key column maps onto the
+            // first field and value column onto the second by position, and
+            // `MapArrayDecoder` builds its output `StructArray` using the requested
+            // field names, not ORC's.
+            ensure!(
+                !sorted || map_key_mode == MapKeyMode::SortKeys,
+                UnsupportedTypeVariantSnafu {
+                    msg: "Sorted map requested without MapKeyMode::SortKeys"
+                }
+            );
+            let entries_name = entries_field.name().clone();
+            let ArrowDataType::Struct(entries) = entries_field.data_type() else {
                 UnexpectedSnafu {
                     msg: "arrow Map with non-Struct entry type".to_owned(),
                 }
@@ -353,13 +701,27 @@ pub fn array_decoder_factory(
 
             Box::new(MapArrayDecoder::new(
                 column,
+                entries_name,
                 keys_field,
                 values_field,
                 stripe,
+                timestamp_overflow,
+                decode_limits,
+                map_key_mode,
+                decode_pool.clone(),
             )?)
         }
-        (DataType::Union { .. }, ArrowDataType::Union(fields, _)) => {
-            Box::new(UnionArrayDecoder::new(column, fields.clone(), stripe)?)
+        (DataType::Union { .. }, ArrowDataType::Union(fields, mode)) => {
+            Box::new(UnionArrayDecoder::new(
+                column,
+                fields.clone(),
+                *mode,
+                stripe,
+                timestamp_overflow,
+                decode_limits,
+                map_key_mode,
+                decode_pool.clone(),
+            )?)
         }
         (data_type, field_type) => {
             return MismatchedSchemaSnafu {
@@ -377,6 +739,24 @@ impl NaiveStripeDecoder {
     fn inner_decode_next_batch(&mut self, remaining: usize) -> Result<Vec<ArrayRef>> {
         let chunk = self.batch_size.min(remaining);
 
+        // Column decoders each own disjoint streams, so when a decode pool was requested,
+        // they can run across its threads without any coordination beyond joining their
+        // outputs back in column order.
+        if let Some(pool) = &self.decode_pool {
+            let decoders = &mut self.decoders;
+            let arrays = pool.install(|| {
+                decoders
+                    .par_iter_mut()
+                    .map(|decoder| decoder.next_batch(chunk, None))
+                    .collect::<Result<Vec<_>>>()
+            })?;
+            return Ok(if arrays.iter().any(ArrayRef::is_empty) {
+                Vec::new()
+            } else {
+                arrays
+            });
+        }
+
         let mut fields = Vec::with_capacity(self.stripe.columns().len());
 
         for decoder in &mut self.decoders {
@@ -392,24 +772,21 @@ impl NaiveStripeDecoder {
     }
 
     fn decode_next_batch(&mut self, remaining: usize) -> Result<Option<RecordBatch>> {
+        let start = self.index;
         let fields = self.inner_decode_next_batch(remaining)?;
 
-        if fields.is_empty() {
+        let batch = if fields.is_empty() {
             if remaining == 0 {
-                Ok(None)
-            } else {
-                // In case of empty projection, we need to create a RecordBatch with `row_count` only
-                // to reflect the row number
-                Ok(Some(
-                    RecordBatch::try_new_with_options(
-                        Arc::clone(&self.schema_ref),
-                        fields,
-                        &RecordBatchOptions::new()
-                            .with_row_count(Some(self.batch_size.min(remaining))),
-                    )
-                    .context(error::ConvertRecordBatchSnafu)?,
-                ))
+                return Ok(None);
             }
+            // In case of empty projection, we need to create a RecordBatch with `row_count` only
+            // to reflect the row number
+            RecordBatch::try_new_with_options(
+                Arc::clone(&self.schema_ref),
+                fields,
+                &RecordBatchOptions::new().with_row_count(Some(self.batch_size.min(remaining))),
+            )
+            .context(error::ConvertRecordBatchSnafu)?
         } else {
             //TODO(weny): any better way?
             let fields = self
@@ -420,22 +797,176 @@ impl NaiveStripeDecoder {
                 .zip(fields)
                 .collect::<Vec<_>>();
 
-            Ok(Some(
-                RecordBatch::try_from_iter(fields).context(error::ConvertRecordBatchSnafu)?,
-            ))
+            RecordBatch::try_from_iter(fields).context(error::ConvertRecordBatchSnafu)?
+        };
+
+        Ok(Some(self.apply_row_selection(batch, start)?))
+    }
+
+    /// Whether every row in `[self.index, self.index + chunk)` was excluded by
+    /// `row_selection`, i.e. this whole upcoming batch can be [`Self::skip_chunk`]ped
+    /// instead of decoded and immediately dropped by [`Self::apply_row_selection`].
+    fn chunk_fully_excluded(&self, chunk: usize) -> bool {
+        match &self.row_selection {
+            Some(selection) => selection
+                .mask_for_range(self.index..self.index + chunk)
+                .iter()
+                .all(|&selected| !selected),
+            None => false,
         }
     }
 
-    pub fn new(stripe: Stripe, schema_ref: SchemaRef, batch_size: usize) -> Result<Self> {
-        let mut decoders = Vec::with_capacity(stripe.columns().len());
+    /// Advances every column decoder past `chunk` rows without materializing them, via
+    /// [`ArrayBatchDecoder::skip`]. Only ever called on a chunk [`Self::chunk_fully_excluded`]
+    /// has already proven contains no selected row, so unlike [`Self::decode_next_batch`]
+    /// this never needs to build or filter a `RecordBatch` at all.
+    ///
+    /// This still decodes (and discards) every skipped decoder's own streams rather than
+    /// seeking past them: only [`StructArrayDecoder`] overrides [`ArrayBatchDecoder::skip`]
+    /// with a real shortcut today (see its doc comment), so most column types still pay for
+    /// decoding a skipped row group, just not for building an output array for it. Actually
+    /// seeking a skipped row group's `Data`/`Secondary`/`Length` streams to the byte offset
+    /// `RowIndexEntry::positions` recorded for it would additionally require parsing those
+    /// positions (see [`crate::row_selection`]'s module doc) and plumbing a `seek` through
+    /// every [`PrimitiveValueDecoder`], which doesn't exist yet.
+    fn skip_chunk(&mut self, chunk: usize) -> Result<()> {
+        if let Some(pool) = &self.decode_pool {
+            let decoders = &mut self.decoders;
+            pool.install(|| decoders.par_iter_mut().try_for_each(|d| d.skip(chunk)))?;
+        } else {
+            for decoder in &mut self.decoders {
+                decoder.skip(chunk)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops rows `batch` holds that `row_selection` didn't select, i.e. rows from
+    /// a `rowIndexStride` bloom-filter pruning proved couldn't match. `batch` is assumed to
+    /// start at absolute row `start` within the stripe.
+    fn apply_row_selection(&self, batch: RecordBatch, start: usize) -> Result<RecordBatch> {
+        let Some(selection) = &self.row_selection else {
+            return Ok(batch);
+        };
+        let mask = selection.mask_for_range(start..start + batch.num_rows());
+        if mask.iter().all(|&selected| selected) {
+            return Ok(batch);
+        }
+        filter_record_batch(&batch, &BooleanArray::from(mask))
+            .context(error::ConvertRecordBatchSnafu)
+    }
+
+    /// `stripe` is already projection-pruned by the time it gets here: [`Stripe::new`]
+    /// only builds a [`Column`] (and so only reads its streams) for entries in `schema_ref`,
+    /// so a query selecting 2 of 50 columns never constructs a decoder -- let alone an
+    /// `RleV2Decoder` -- for the other 48. What this doesn't do is defer decoding those 2
+    /// selected columns further still: every column here is decoded for every row before
+    /// [`Self::apply_row_selection`] or the caller's own filter evaluation drops any of
+    /// them, rather than decoding filter columns first and only materializing the rest for
+    /// rows that survive. Late materialization in that sense would need `decoders` split
+    /// into filter and payload groups and `inner_decode_next_batch` sequenced across them,
+    /// which doesn't exist yet.
+    /// `native_schema` is the schema this stripe's columns would be decoded at had
+    /// [`ArrowReaderBuilder::with_schema`](crate::arrow_reader::ArrowReaderBuilder::with_schema)
+    /// never been called (see
+    /// [`ArrowReaderBuilder::native_schema`](crate::arrow_reader::ArrowReaderBuilder::native_schema)),
+    /// always in the same column order as `stripe.columns()`. Each field of `schema_ref` (the
+    /// schema actually requested, identical to `native_schema` unless `with_schema` was used)
+    /// is resolved against it by name rather than position: a same-named column decodes
+    /// natively and then through [`CastArrayDecoder`] if its type differs, while a name
+    /// `schema_ref` asks for that isn't in `native_schema` at all is filled in with
+    /// [`NullArrayDecoder`] instead of failing the read.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        stripe: Stripe,
+        schema_ref: SchemaRef,
+        native_schema: SchemaRef,
+        batch_size: usize,
+        decode_parallelism: usize,
+        timestamp_overflow: TimestampOverflowMode,
+        decode_limits: DecodeLimits,
+        map_key_mode: MapKeyMode,
+        row_selection: Option<RowSelection>,
+    ) -> Result<Self> {
         let number_of_rows = stripe.number_of_rows();
 
-        for (col, field) in stripe
-            .columns()
-            .iter()
-            .zip(schema_ref.fields.iter().cloned())
-        {
-            let decoder = array_decoder_factory(col, field, &stripe)?;
+        // Built up front (rather than after decoders, as previously) and handed down
+        // through `array_decoder_factory` so any `StructArrayDecoder` among the column
+        // decoders can also fan its own children out across the very same pool, instead
+        // of this top-level column fan-out being the only thing that benefits from
+        // `with_decode_parallelism`.
+        let decode_pool = if decode_parallelism > 1 {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(decode_parallelism)
+                .build()
+                .map_err(|e| {
+                    UnexpectedSnafu {
+                        msg: format!("failed to build decode thread pool: {e}"),
+                    }
+                    .build()
+                })?;
+            Some(Arc::new(pool))
+        } else {
+            None
+        };
+
+        let mut decoders = Vec::with_capacity(schema_ref.fields().len());
+
+        for target_field in schema_ref.fields() {
+            let decoder: Box<dyn ArrayBatchDecoder> = match native_schema
+                .fields()
+                .iter()
+                .position(|native_field| native_field.name() == target_field.name())
+            {
+                // Try decoding straight at `target_field`'s type first: `array_decoder_factory`
+                // already natively supports several requested types per ORC source type (e.g.
+                // any `TimeUnit` for a `Timestamp`, `Decimal128` or `Decimal256` for a
+                // `Decimal`, a `Dictionary` or `Utf8View` for a `String`), so this is also the
+                // path taken when `schema_ref` is just `native_schema` (the common case, when
+                // `with_schema` wasn't called). Only a type combination it truly doesn't
+                // support falls through to a generic `arrow-rs` cast of the natively-decoded
+                // array below.
+                Some(index) => {
+                    let col = &stripe.columns()[index];
+                    match array_decoder_factory(
+                        col,
+                        target_field.clone(),
+                        &stripe,
+                        timestamp_overflow,
+                        decode_limits,
+                        map_key_mode,
+                        decode_pool.clone(),
+                    ) {
+                        Ok(decoder) => decoder,
+                        Err(error::OrcError::MismatchedSchema { .. }) => {
+                            let native_field = native_schema.fields()[index].clone();
+                            let decoder = array_decoder_factory(
+                                col,
+                                native_field.clone(),
+                                &stripe,
+                                timestamp_overflow,
+                                decode_limits,
+                                map_key_mode,
+                                decode_pool.clone(),
+                            )?;
+                            ensure!(
+                                can_cast_types(native_field.data_type(), target_field.data_type()),
+                                error::UnsupportedSchemaCastSnafu {
+                                    name: target_field.name().clone(),
+                                    from_type: native_field.data_type().clone(),
+                                    to_type: target_field.data_type().clone(),
+                                }
+                            );
+                            Box::new(CastArrayDecoder::new(
+                                decoder,
+                                target_field.data_type().clone(),
+                            ))
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                None => Box::new(NullArrayDecoder::new(target_field.data_type().clone())),
+            };
             decoders.push(decoder);
         }
 
@@ -446,6 +977,9 @@ impl NaiveStripeDecoder {
             index: 0,
             batch_size,
             number_of_rows,
+            decode_pool,
+            decode_limits,
+            row_selection,
         })
     }
 }
@@ -19,48 +19,107 @@ use std::io::Read;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
+use arrow::array::builder::{BinaryViewBuilder, StringViewBuilder};
 use arrow::array::{ArrayRef, DictionaryArray, GenericByteArray, StringArray};
 use arrow::buffer::{Buffer, NullBuffer, OffsetBuffer};
 use arrow::compute::kernels::cast;
-use arrow::datatypes::{ByteArrayType, DataType, GenericBinaryType, GenericStringType};
-use snafu::ResultExt;
+use arrow::datatypes::{
+    ByteArrayType, DataType, GenericBinaryType, GenericStringType, OffsetSizeTrait,
+};
+use arrow::error::ArrowError;
+use snafu::{ensure, ResultExt};
 
 use crate::array_decoder::derive_present_vec;
 use crate::column::Column;
 use crate::compression::Decompressor;
-use crate::encoding::integer::get_unsigned_rle_reader;
+use crate::encoding::integer::{
+    get_narrow_unsigned_rle_reader_with_limits, get_unsigned_rle_reader_with_limits, DecodeLimits,
+};
 use crate::encoding::PrimitiveValueDecoder;
-use crate::error::{ArrowSnafu, IoSnafu, Result};
+use crate::error::{ArrowSnafu, IoSnafu, OutOfSpecSnafu, Result, UnsupportedTypeVariantSnafu};
 use crate::proto::column_encoding::Kind as ColumnEncodingKind;
 use crate::proto::stream::Kind;
 use crate::stripe::Stripe;
 
-use super::{ArrayBatchDecoder, Int64ArrayDecoder, PresentDecoder};
+use super::{
+    ArrayBatchDecoder, Int16ArrayDecoder, Int32ArrayDecoder, Int64ArrayDecoder, Int8ArrayDecoder,
+    PresentDecoder,
+};
 
 // TODO: reduce duplication with string below
-pub fn new_binary_decoder(column: &Column, stripe: &Stripe) -> Result<Box<dyn ArrayBatchDecoder>> {
+/// `field_type` is driven by [`ArrowReaderBuilder::with_utf8_view`](crate::arrow_reader::ArrowReaderBuilder::with_utf8_view)
+/// (and the `Large*` variants by the requested Arrow schema) the same way every other
+/// natively-supported target type is chosen in [`array_decoder_factory`] -- there's no
+/// separate reader-level flag beyond the schema/builder options already threaded down to
+/// here.
+pub fn new_binary_decoder(
+    column: &Column,
+    field_type: &DataType,
+    stripe: &Stripe,
+    decode_limits: DecodeLimits,
+) -> Result<Box<dyn ArrayBatchDecoder>> {
     let present = PresentDecoder::from_stripe(stripe, column);
 
     let lengths = stripe.stream_map().get(column, Kind::Length);
-    let lengths = get_unsigned_rle_reader(column, lengths);
+    let lengths = get_unsigned_rle_reader_with_limits(column, lengths, decode_limits);
 
     let bytes = Box::new(stripe.stream_map().get(column, Kind::Data));
-    Ok(Box::new(BinaryArrayDecoder::new(bytes, lengths, present)))
+    if matches!(field_type, DataType::BinaryView) {
+        Ok(Box::new(BinaryViewArrayDecoder::new(
+            bytes, lengths, present,
+        )))
+    } else if matches!(field_type, DataType::LargeBinary) {
+        Ok(Box::new(LargeBinaryArrayDecoder::new(
+            bytes, lengths, present,
+        )))
+    } else {
+        Ok(Box::new(BinaryArrayDecoder::new(bytes, lengths, present)))
+    }
 }
 
-pub fn new_string_decoder(column: &Column, stripe: &Stripe) -> Result<Box<dyn ArrayBatchDecoder>> {
+pub fn new_string_decoder(
+    column: &Column,
+    field_type: &DataType,
+    stripe: &Stripe,
+    decode_limits: DecodeLimits,
+) -> Result<Box<dyn ArrayBatchDecoder>> {
     let kind = column.encoding().kind();
     let present = PresentDecoder::from_stripe(stripe, column);
 
     let lengths = stripe.stream_map().get(column, Kind::Length);
-    let lengths = get_unsigned_rle_reader(column, lengths);
+    let lengths = get_unsigned_rle_reader_with_limits(column, lengths, decode_limits);
 
     match kind {
         ColumnEncodingKind::Direct | ColumnEncodingKind::DirectV2 => {
             let bytes = Box::new(stripe.stream_map().get(column, Kind::Data));
-            Ok(Box::new(DirectStringArrayDecoder::new(
-                bytes, lengths, present,
-            )))
+            if matches!(field_type, DataType::Utf8View) {
+                Ok(Box::new(StringViewArrayDecoder::new(
+                    bytes, lengths, present,
+                )))
+            } else if matches!(field_type, DataType::LargeUtf8) {
+                Ok(Box::new(LargeStringArrayDecoder::new(
+                    bytes, lengths, present,
+                )))
+            } else if let DataType::Dictionary(key_type, value_type) = field_type {
+                ensure!(
+                    value_type.as_ref() == &DataType::Utf8,
+                    UnsupportedTypeVariantSnafu {
+                        msg: "Dictionary value type other than Utf8 for a string column"
+                    }
+                );
+                // This stripe's column wasn't ORC dictionary-encoded, but the schema still
+                // promises a `Dictionary` array (requested once for the whole file via
+                // `ArrowReaderBuilder::with_dictionary_key_type`/`with_schema`), so re-derive
+                // one here rather than returning a plain `StringArray` that wouldn't match.
+                Ok(Box::new(RedictionarizedStringArrayDecoder::new(
+                    DirectStringArrayDecoder::new(bytes, lengths, present),
+                    key_type.as_ref().clone(),
+                )))
+            } else {
+                Ok(Box::new(DirectStringArrayDecoder::new(
+                    bytes, lengths, present,
+                )))
+            }
         }
         ColumnEncodingKind::Dictionary | ColumnEncodingKind::DictionaryV2 => {
             let bytes = Box::new(stripe.stream_map().get(column, Kind::DictionaryData));
@@ -72,21 +131,65 @@ pub fn new_string_decoder(column: &Column, stripe: &Stripe) -> Result<Box<dyn Ar
             let dictionary_strings = Arc::new(dictionary_strings);
 
             let indexes = stripe.stream_map().get(column, Kind::Data);
-            let indexes = get_unsigned_rle_reader(column, indexes);
-            let indexes = Int64ArrayDecoder::new(indexes, present);
+            let indexes = DictionaryIndexDecoder::new(
+                column,
+                indexes,
+                present,
+                dictionary_size,
+                decode_limits,
+            );
+
+            // `preserve_dictionary` is requested by asking for a `Dictionary` Arrow type
+            // (via `ArrowReaderBuilder::with_schema` or `with_dictionary_key_type`), the same
+            // way `new_timestamp_decoder` reads the target timezone off the requested Arrow
+            // type rather than a separate builder flag.
+            let dictionary_key_type = match field_type {
+                DataType::Dictionary(key_type, value_type) if value_type.as_ref() == &DataType::Utf8 => {
+                    match key_type.as_ref() {
+                        // `UInt64` is the width callers reach for when they just want the
+                        // dictionary preserved without hand-picking an exact key width;
+                        // narrow it down to what this stripe's dictionary actually needs
+                        // instead of always paying for 8-byte keys.
+                        DataType::UInt64 => {
+                            let DataType::Dictionary(narrowed, _) =
+                                column.arrow_data_type(DataType::Utf8)
+                            else {
+                                unreachable!("Column::arrow_data_type always returns a Dictionary")
+                            };
+                            Some(*narrowed)
+                        }
+                        // Any other width was chosen deliberately; honor it exactly so the
+                        // schema stays fixed across stripes.
+                        key_type => Some(key_type.clone()),
+                    }
+                }
+                _ => None,
+            };
 
             Ok(Box::new(DictionaryStringArrayDecoder::new(
                 indexes,
                 dictionary_strings,
+                dictionary_key_type,
             )?))
         }
     }
 }
 
-// TODO: check this offset size type
 pub type DirectStringArrayDecoder = GenericByteArrayDecoder<GenericStringType<i32>>;
 pub type BinaryArrayDecoder = GenericByteArrayDecoder<GenericBinaryType<i32>>;
+/// Used instead of [`DirectStringArrayDecoder`] when the caller requests `LargeUtf8`, or when
+/// [`GenericByteArrayDecoder::next_byte_batch`] determines the 32-bit offset variant would
+/// overflow (see there).
+pub type LargeStringArrayDecoder = GenericByteArrayDecoder<GenericStringType<i64>>;
+/// Used instead of [`BinaryArrayDecoder`] when the caller requests `LargeBinary`.
+pub type LargeBinaryArrayDecoder = GenericByteArrayDecoder<GenericBinaryType<i64>>;
 
+/// Backs both the direct string and binary decoders (see the `DirectStringArrayDecoder`/
+/// `DirectBinaryArrayDecoder` aliases below). [`Self::next_byte_batch`] decodes a whole
+/// batch's values into one contiguous [`Buffer`] -- sliced straight out of the decompressed
+/// block via [`Decompressor::next_contiguous`] when the batch doesn't straddle a block
+/// boundary, copied once via `read_to_end` when it does -- rather than allocating and
+/// copying each value's bytes individually.
 pub struct GenericByteArrayDecoder<T: ByteArrayType> {
     bytes: Box<Decompressor>,
     lengths: Box<dyn PrimitiveValueDecoder<i64> + Send>,
@@ -123,14 +226,37 @@ impl<T: ByteArrayType> GenericByteArrayDecoder<T> {
             self.lengths.decode(&mut lengths)?;
         }
         let total_length: i64 = lengths.iter().sum();
-        // Fetch all data bytes at once
-        let mut bytes = Vec::with_capacity(total_length as usize);
-        self.bytes
-            .by_ref()
-            .take(total_length as u64)
-            .read_to_end(&mut bytes)
-            .context(IoSnafu)?;
-        let bytes = Buffer::from(bytes);
+        if !T::Offset::IS_LARGE && total_length > i32::MAX as i64 {
+            // The 32-bit offset variant can't represent a batch this large; the caller needs
+            // to request the `Large` Arrow type for this column instead. We can't silently
+            // promote mid-stream: the Arrow type for a column is fixed once for the whole
+            // read (driven by the schema passed to `array_decoder_factory`), so switching
+            // datatypes partway through would produce batches with inconsistent schemas.
+            return OutOfSpecSnafu {
+                msg: format!(
+                    "{:?} column data batch is {total_length} bytes, exceeding what i32 offsets \
+                     can represent; request the corresponding Large variant for this column",
+                    T::DATA_TYPE
+                ),
+            }
+            .fail();
+        }
+        // When the whole batch's worth of bytes already sits contiguously in the current
+        // decompressed (or originally uncompressed) block, slice straight into it instead of
+        // `read_to_end`-ing a copy of every value's bytes into a fresh `Vec`. Falls back to
+        // the copying path when the batch spans more than one compression block.
+        let bytes = match self.bytes.next_contiguous(total_length as usize) {
+            Some(bytes) => Buffer::from(bytes),
+            None => {
+                let mut bytes = Vec::with_capacity(total_length as usize);
+                self.bytes
+                    .by_ref()
+                    .take(total_length as u64)
+                    .read_to_end(&mut bytes)
+                    .context(IoSnafu)?;
+                Buffer::from(bytes)
+            }
+        };
         let offsets =
             OffsetBuffer::<T::Offset>::from_lengths(lengths.into_iter().map(|l| l as usize));
 
@@ -139,6 +265,9 @@ impl<T: ByteArrayType> GenericByteArrayDecoder<T> {
             Some(present) if present.null_count() == 0 => None,
             _ => present,
         };
+        // For a `T` whose `DATA_TYPE` is a UTF-8 string type, `try_new` validates the whole
+        // contiguous `bytes` buffer against `offsets` in one pass internally; there's no
+        // per-value `std::str::from_utf8` call to avoid on this path to begin with.
         let array =
             GenericByteArray::<T>::try_new(offsets, bytes, null_buffer).context(ArrowSnafu)?;
         Ok(array)
@@ -157,16 +286,232 @@ impl<T: ByteArrayType> ArrayBatchDecoder for GenericByteArrayDecoder<T> {
     }
 }
 
+// TODO: reduce duplication with string below
+pub struct BinaryViewArrayDecoder {
+    bytes: Box<Decompressor>,
+    lengths: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+    present: Option<PresentDecoder>,
+}
+
+impl BinaryViewArrayDecoder {
+    fn new(
+        bytes: Box<Decompressor>,
+        lengths: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+        present: Option<PresentDecoder>,
+    ) -> Self {
+        Self {
+            bytes,
+            lengths,
+            present,
+        }
+    }
+}
+
+impl ArrayBatchDecoder for BinaryViewArrayDecoder {
+    fn next_batch(
+        &mut self,
+        batch_size: usize,
+        parent_present: Option<&NullBuffer>,
+    ) -> Result<ArrayRef> {
+        let present =
+            derive_present_vec(&mut self.present, parent_present, batch_size).transpose()?;
+
+        let mut lengths = vec![0; batch_size];
+        if let Some(present) = &present {
+            self.lengths.decode_spaced(&mut lengths, present)?;
+        } else {
+            self.lengths.decode(&mut lengths)?;
+        }
+
+        let mut builder = BinaryViewBuilder::with_capacity(batch_size);
+        for (index, length) in lengths.into_iter().enumerate() {
+            if present.as_ref().is_some_and(|p| !p.is_valid(index)) {
+                builder.append_null();
+                continue;
+            }
+            let value = match self.bytes.next_contiguous(length as usize) {
+                Some(bytes) => bytes.to_vec(),
+                None => {
+                    let mut bytes = Vec::with_capacity(length as usize);
+                    self.bytes
+                        .by_ref()
+                        .take(length as u64)
+                        .read_to_end(&mut bytes)
+                        .context(IoSnafu)?;
+                    bytes
+                }
+            };
+            builder.append_value(value);
+        }
+
+        Ok(Arc::new(builder.finish()))
+    }
+}
+
+// TODO: reduce duplication with binary above
+pub struct StringViewArrayDecoder {
+    bytes: Box<Decompressor>,
+    lengths: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+    present: Option<PresentDecoder>,
+}
+
+impl StringViewArrayDecoder {
+    fn new(
+        bytes: Box<Decompressor>,
+        lengths: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+        present: Option<PresentDecoder>,
+    ) -> Self {
+        Self {
+            bytes,
+            lengths,
+            present,
+        }
+    }
+}
+
+impl ArrayBatchDecoder for StringViewArrayDecoder {
+    fn next_batch(
+        &mut self,
+        batch_size: usize,
+        parent_present: Option<&NullBuffer>,
+    ) -> Result<ArrayRef> {
+        let present =
+            derive_present_vec(&mut self.present, parent_present, batch_size).transpose()?;
+
+        let mut lengths = vec![0; batch_size];
+        if let Some(present) = &present {
+            self.lengths.decode_spaced(&mut lengths, present)?;
+        } else {
+            self.lengths.decode(&mut lengths)?;
+        }
+
+        let mut builder = StringViewBuilder::with_capacity(batch_size);
+        for (index, length) in lengths.into_iter().enumerate() {
+            if present.as_ref().is_some_and(|p| !p.is_valid(index)) {
+                builder.append_null();
+                continue;
+            }
+            let value = match self.bytes.next_contiguous(length as usize) {
+                Some(bytes) => bytes.to_vec(),
+                None => {
+                    let mut bytes = Vec::with_capacity(length as usize);
+                    self.bytes
+                        .by_ref()
+                        .take(length as u64)
+                        .read_to_end(&mut bytes)
+                        .context(IoSnafu)?;
+                    bytes
+                }
+            };
+            let value = std::str::from_utf8(&value)
+                .map_err(|e| ArrowError::CastError(format!("invalid utf-8 in ORC string column: {e}")))
+                .context(ArrowSnafu)?
+                .to_string();
+            builder.append_value(value);
+        }
+
+        Ok(Arc::new(builder.finish()))
+    }
+}
+
+/// Decodes the dictionary index stream at the narrowest signed width that can hold every
+/// index `0..dictionary_size` -- e.g. a 200-entry dictionary decodes straight into `i16`
+/// keys instead of always paying for `i64` ones and narrowing afterward. The chosen width
+/// only has to cover the index range, independent of whatever Arrow key type the caller
+/// eventually wants (see [`DictionaryStringArrayDecoder::dictionary_key_type`]), since
+/// [`DictionaryStringArrayDecoder::next_batch`] still casts the result to that type.
+pub(crate) enum DictionaryIndexDecoder {
+    Int8(Int8ArrayDecoder),
+    Int16(Int16ArrayDecoder),
+    Int32(Int32ArrayDecoder),
+    Int64(Int64ArrayDecoder),
+}
+
+impl DictionaryIndexDecoder {
+    fn new(
+        column: &Column,
+        reader: Decompressor,
+        present: Option<PresentDecoder>,
+        dictionary_size: usize,
+        decode_limits: DecodeLimits,
+    ) -> Self {
+        // An index is always `< dictionary_size`, so `dictionary_size - 1` is the largest
+        // value the stream can contain.
+        let max_index = dictionary_size.saturating_sub(1);
+        if max_index <= i8::MAX as usize {
+            Self::Int8(Int8ArrayDecoder::new(
+                get_narrow_unsigned_rle_reader_with_limits::<i8, _>(column, reader, decode_limits),
+                present,
+            ))
+        } else if max_index <= i16::MAX as usize {
+            Self::Int16(Int16ArrayDecoder::new(
+                get_narrow_unsigned_rle_reader_with_limits::<i16, _>(column, reader, decode_limits),
+                present,
+            ))
+        } else if max_index <= i32::MAX as usize {
+            Self::Int32(Int32ArrayDecoder::new(
+                get_narrow_unsigned_rle_reader_with_limits::<i32, _>(column, reader, decode_limits),
+                present,
+            ))
+        } else {
+            Self::Int64(Int64ArrayDecoder::new(
+                get_narrow_unsigned_rle_reader_with_limits::<i64, _>(column, reader, decode_limits),
+                present,
+            ))
+        }
+    }
+
+    fn next_batch(
+        &mut self,
+        batch_size: usize,
+        parent_present: Option<&NullBuffer>,
+        dictionary: &Arc<StringArray>,
+    ) -> Result<ArrayRef> {
+        let array: ArrayRef = match self {
+            Self::Int8(decoder) => {
+                let keys = decoder.next_primitive_batch(batch_size, parent_present)?;
+                Arc::new(DictionaryArray::try_new(keys, dictionary.clone()).context(ArrowSnafu)?)
+            }
+            Self::Int16(decoder) => {
+                let keys = decoder.next_primitive_batch(batch_size, parent_present)?;
+                Arc::new(DictionaryArray::try_new(keys, dictionary.clone()).context(ArrowSnafu)?)
+            }
+            Self::Int32(decoder) => {
+                let keys = decoder.next_primitive_batch(batch_size, parent_present)?;
+                Arc::new(DictionaryArray::try_new(keys, dictionary.clone()).context(ArrowSnafu)?)
+            }
+            Self::Int64(decoder) => {
+                let keys = decoder.next_primitive_batch(batch_size, parent_present)?;
+                Arc::new(DictionaryArray::try_new(keys, dictionary.clone()).context(ArrowSnafu)?)
+            }
+        };
+        Ok(array)
+    }
+}
+
 pub struct DictionaryStringArrayDecoder {
-    indexes: Int64ArrayDecoder,
+    indexes: DictionaryIndexDecoder,
     dictionary: Arc<StringArray>,
+    /// The Arrow dictionary key type to preserve the ORC dictionary encoding as, requested by
+    /// the caller via a target `Dictionary` field type. `None` reproduces the historical
+    /// behavior of casting every batch back to a plain `StringArray`.
+    ///
+    /// Each stripe carries its own independent dictionary, so consecutive batches spanning a
+    /// stripe boundary may carry different dictionary values arrays for the same column;
+    /// unifying them into one merged dictionary across stripes is left as a follow-up.
+    dictionary_key_type: Option<DataType>,
 }
 
 impl DictionaryStringArrayDecoder {
-    fn new(indexes: Int64ArrayDecoder, dictionary: Arc<StringArray>) -> Result<Self> {
+    fn new(
+        indexes: DictionaryIndexDecoder,
+        dictionary: Arc<StringArray>,
+        dictionary_key_type: Option<DataType>,
+    ) -> Result<Self> {
         Ok(Self {
             indexes,
             dictionary,
+            dictionary_key_type,
         })
     }
 }
@@ -177,19 +522,66 @@ impl ArrayBatchDecoder for DictionaryStringArrayDecoder {
         batch_size: usize,
         parent_present: Option<&NullBuffer>,
     ) -> Result<ArrayRef> {
-        let keys = self
+        let array = self
             .indexes
-            .next_primitive_batch(batch_size, parent_present)?;
-        // TODO: ORC spec states: For dictionary encodings the dictionary is sorted
-        //       (in lexicographical order of bytes in the UTF-8 encodings).
-        //       So we can set the is_ordered property here?
-        let array = DictionaryArray::try_new(keys, self.dictionary.clone()).context(ArrowSnafu)?;
-        // Cast back to StringArray to ensure all stripes have consistent datatype
-        // TODO: Is there anyway to preserve the dictionary encoding?
-        //       This costs performance.
-        let array = cast(&array, &DataType::Utf8).context(ArrowSnafu)?;
-
-        let array = Arc::new(array);
+            .next_batch(batch_size, parent_present, &self.dictionary)?;
+        // ORC spec: for dictionary encodings the dictionary is sorted in lexicographical
+        // order of bytes in the UTF-8 encodings.
+        // TODO: that makes every preserved dictionary array ordered, but there's currently no
+        //       way to flow that through to the `dict_is_ordered` flag on the Arrow `Field`
+        //       produced by `schema::RootDataType`'s arrow schema derivation.
+        let array: ArrayRef = match &self.dictionary_key_type {
+            Some(key_type) => {
+                let dictionary_type =
+                    DataType::Dictionary(Box::new(key_type.clone()), Box::new(DataType::Utf8));
+                if array.data_type() == &dictionary_type {
+                    // Already the key type `DictionaryIndexDecoder` decoded as; nothing left
+                    // to do.
+                    array
+                } else {
+                    // Preserve the dictionary encoding, just re-keying to the width requested.
+                    Arc::new(cast(&array, &dictionary_type).context(ArrowSnafu)?)
+                }
+            }
+            // Cast back to StringArray to ensure all stripes have a consistent datatype.
+            None => Arc::new(cast(&array, &DataType::Utf8).context(ArrowSnafu)?),
+        };
+
         Ok(array)
     }
 }
+
+/// Wraps a [`DirectStringArrayDecoder`] so a column that wasn't ORC dictionary-encoded can
+/// still produce `Dictionary(key_type, Utf8)` batches when the caller requested dictionary
+/// output for the whole file (see [`new_string_decoder`]'s `Direct` arm) -- the Arrow type for
+/// a column is fixed for the whole read, so a direct-encoded stripe can't just fall back to a
+/// plain `StringArray` if a dictionary-encoded stripe elsewhere in the same file would produce
+/// `Dictionary`.
+///
+/// Unlike [`DictionaryStringArrayDecoder`], there's no ORC-native dictionary to key into, so
+/// this pays the cost of building one fresh per batch via [`cast`] rather than reusing an
+/// existing dictionary values array.
+struct RedictionarizedStringArrayDecoder {
+    inner: DirectStringArrayDecoder,
+    key_type: DataType,
+}
+
+impl RedictionarizedStringArrayDecoder {
+    fn new(inner: DirectStringArrayDecoder, key_type: DataType) -> Self {
+        Self { inner, key_type }
+    }
+}
+
+impl ArrayBatchDecoder for RedictionarizedStringArrayDecoder {
+    fn next_batch(
+        &mut self,
+        batch_size: usize,
+        parent_present: Option<&NullBuffer>,
+    ) -> Result<ArrayRef> {
+        let array = self.inner.next_batch(batch_size, parent_present)?;
+        let dictionary_type =
+            DataType::Dictionary(Box::new(self.key_type.clone()), Box::new(DataType::Utf8));
+        let array = cast(&array, &dictionary_type).context(ArrowSnafu)?;
+        Ok(Arc::new(array))
+    }
+}
@@ -22,9 +22,13 @@ use arrow::{
     buffer::NullBuffer,
     datatypes::Fields,
 };
+use rayon::prelude::*;
 use snafu::ResultExt;
 
+use crate::encoding::integer::DecodeLimits;
+use crate::encoding::timestamp::TimestampOverflowMode;
 use crate::error::Result;
+use crate::schema::MapKeyMode;
 use crate::stripe::Stripe;
 use crate::{column::Column, error::ArrowSnafu};
 
@@ -34,23 +38,47 @@ pub struct StructArrayDecoder {
     fields: Fields,
     decoders: Vec<Box<dyn ArrayBatchDecoder>>,
     present: Option<PresentDecoder>,
+    /// Set when the reader was built with [`ArrowReaderBuilder::with_decode_parallelism`](crate::ArrowReaderBuilder::with_decode_parallelism);
+    /// the same pool the top-level column decoders fan out across in
+    /// [`NaiveStripeDecoder`](super::NaiveStripeDecoder), reused here since each child
+    /// column decoder also reads its own disjoint streams.
+    decode_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl StructArrayDecoder {
-    pub fn new(column: &Column, fields: Fields, stripe: &Stripe) -> Result<Self> {
+    pub fn new(
+        column: &Column,
+        fields: Fields,
+        stripe: &Stripe,
+        timestamp_overflow: TimestampOverflowMode,
+        decode_limits: DecodeLimits,
+        map_key_mode: MapKeyMode,
+        decode_pool: Option<Arc<rayon::ThreadPool>>,
+    ) -> Result<Self> {
         let present = PresentDecoder::from_stripe(stripe, column);
 
         let decoders = column
             .children()
             .iter()
             .zip(fields.iter().cloned())
-            .map(|(child, field)| array_decoder_factory(child, field, stripe))
+            .map(|(child, field)| {
+                array_decoder_factory(
+                    child,
+                    field,
+                    stripe,
+                    timestamp_overflow,
+                    decode_limits,
+                    map_key_mode,
+                    decode_pool.clone(),
+                )
+            })
             .collect::<Result<Vec<_>>>()?;
 
         Ok(Self {
             decoders,
             present,
             fields,
+            decode_pool,
         })
     }
 }
@@ -64,11 +92,20 @@ impl ArrayBatchDecoder for StructArrayDecoder {
         let present =
             derive_present_vec(&mut self.present, parent_present, batch_size).transpose()?;
 
-        let child_arrays = self
-            .decoders
-            .iter_mut()
-            .map(|child| child.next_batch(batch_size, present.as_ref()))
-            .collect::<Result<Vec<_>>>()?;
+        let child_arrays = if let Some(pool) = &self.decode_pool {
+            let decoders = &mut self.decoders;
+            pool.install(|| {
+                decoders
+                    .par_iter_mut()
+                    .map(|child| child.next_batch(batch_size, present.as_ref()))
+                    .collect::<Result<Vec<_>>>()
+            })?
+        } else {
+            self.decoders
+                .iter_mut()
+                .map(|child| child.next_batch(batch_size, present.as_ref()))
+                .collect::<Result<Vec<_>>>()?
+        };
 
         let null_buffer = present.map(NullBuffer::from);
         let array = StructArray::try_new(self.fields.clone(), child_arrays, null_buffer)
@@ -76,4 +113,20 @@ impl ArrayBatchDecoder for StructArrayDecoder {
         let array = Arc::new(array);
         Ok(array)
     }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        if n == 0 {
+            return Ok(());
+        }
+        // A null row consumes a `Present` bit here but no entry at all in any child's
+        // value stream, so only the non-null count -- not `n` itself -- gets forwarded.
+        let non_null = match &mut self.present {
+            Some(present) => present.skip(n)?,
+            None => n,
+        };
+        for child in &mut self.decoders {
+            child.skip(non_null)?;
+        }
+        Ok(())
+    }
 }
@@ -15,14 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::{
     array_decoder::ArrowDataType,
     column::Column,
     encoding::{
-        integer::{get_rle_reader, get_unsigned_rle_reader},
-        timestamp::{TimestampDecoder, TimestampNanosecondAsDecimalDecoder},
+        integer::{get_rle_reader_with_limits, get_unsigned_rle_reader_with_limits, DecodeLimits},
+        timestamp::{TimestampDecoder, TimestampNanosecondAsDecimalDecoder, TimestampOverflowMode},
         PrimitiveValueDecoder,
     },
     error::{MismatchedSchemaSnafu, Result},
@@ -41,7 +42,19 @@ use chrono_tz::{Tz, UTC};
 use super::{
     decimal::DecimalArrayDecoder, ArrayBatchDecoder, PresentDecoder, PrimitiveArrayDecoder,
 };
-use crate::error::UnsupportedTypeVariantSnafu;
+use crate::error::OutOfSpecSnafu;
+
+/// Parses the timezone requested for a `Timestamp(_, Some(tz))` field so it can be
+/// used both to validate the batch's output type up front and to convert values into
+/// that zone's wall clock.
+fn parse_reader_tz(tz: &str) -> Result<Tz> {
+    Tz::from_str(tz).map_err(|_| {
+        OutOfSpecSnafu {
+            msg: format!("unknown timezone for reading timestamps: '{tz}'"),
+        }
+        .build()
+    })
+}
 
 const NANOSECONDS_IN_SECOND: i128 = 1_000_000_000;
 const NANOSECOND_DIGITS: i8 = 9;
@@ -54,12 +67,14 @@ fn get_inner_timestamp_decoder<T: ArrowTimestampType + Send>(
     column: &Column,
     stripe: &Stripe,
     seconds_since_unix_epoch: i64,
+    overflow: TimestampOverflowMode,
+    decode_limits: DecodeLimits,
 ) -> Result<PrimitiveArrayDecoder<T>> {
     let data = stripe.stream_map().get(column, Kind::Data);
-    let data = get_rle_reader(column, data)?;
+    let data = get_rle_reader_with_limits(column, data, decode_limits)?;
 
     let secondary = stripe.stream_map().get(column, Kind::Secondary);
-    let secondary = get_unsigned_rle_reader(column, secondary);
+    let secondary = get_unsigned_rle_reader_with_limits(column, secondary, decode_limits);
 
     let present = PresentDecoder::from_stripe(stripe, column);
 
@@ -67,6 +82,7 @@ fn get_inner_timestamp_decoder<T: ArrowTimestampType + Send>(
         seconds_since_unix_epoch,
         data,
         secondary,
+        overflow,
     ));
     Ok(PrimitiveArrayDecoder::<T>::new(iter, present))
 }
@@ -75,22 +91,54 @@ fn get_timestamp_decoder<T: ArrowTimestampType + Send>(
     column: &Column,
     stripe: &Stripe,
     seconds_since_unix_epoch: i64,
+    target_tz: Option<Arc<str>>,
+    overflow: TimestampOverflowMode,
+    decode_limits: DecodeLimits,
 ) -> Result<Box<dyn ArrayBatchDecoder>> {
-    let inner = get_inner_timestamp_decoder::<T>(column, stripe, seconds_since_unix_epoch)?;
+    let inner = get_inner_timestamp_decoder::<T>(
+        column,
+        stripe,
+        seconds_since_unix_epoch,
+        overflow,
+        decode_limits,
+    )?;
     match stripe.writer_tz() {
-        Some(writer_tz) => Ok(Box::new(TimestampOffsetArrayDecoder { inner, writer_tz })),
-        None => Ok(Box::new(inner)),
+        Some(writer_tz) => Ok(Box::new(TimestampOffsetArrayDecoder {
+            inner,
+            writer_tz,
+            target_tz: target_tz
+                .map(|tz| parse_reader_tz(tz.as_ref()).map(|parsed| (tz, parsed)))
+                .transpose()?,
+        })),
+        None => match target_tz {
+            Some(tz) => {
+                parse_reader_tz(tz.as_ref())?;
+                Ok(Box::new(TimestampInstantArrayDecoder(inner, tz)))
+            }
+            None => Ok(Box::new(inner)),
+        },
     }
 }
 
 fn get_timestamp_instant_decoder<T: ArrowTimestampType + Send>(
     column: &Column,
     stripe: &Stripe,
+    target_tz: Arc<str>,
+    overflow: TimestampOverflowMode,
+    decode_limits: DecodeLimits,
 ) -> Result<Box<dyn ArrayBatchDecoder>> {
-    // TIMESTAMP_INSTANT is encoded as UTC so we don't check writer timezone in stripe
-    let inner =
-        get_inner_timestamp_decoder::<T>(column, stripe, ORC_EPOCH_UTC_SECONDS_SINCE_UNIX_EPOCH)?;
-    Ok(Box::new(TimestampInstantArrayDecoder(inner)))
+    // TIMESTAMP_INSTANT is encoded as UTC so we don't check writer timezone in stripe.
+    // The underlying instant doesn't change for a different target timezone, only the
+    // label attached to the output array does.
+    parse_reader_tz(target_tz.as_ref())?;
+    let inner = get_inner_timestamp_decoder::<T>(
+        column,
+        stripe,
+        ORC_EPOCH_UTC_SECONDS_SINCE_UNIX_EPOCH,
+        overflow,
+        decode_limits,
+    )?;
+    Ok(Box::new(TimestampInstantArrayDecoder(inner, target_tz)))
 }
 
 fn decimal128_decoder(
@@ -98,12 +146,13 @@ fn decimal128_decoder(
     stripe: &Stripe,
     seconds_since_unix_epoch: i64,
     writer_tz: Option<Tz>,
+    decode_limits: DecodeLimits,
 ) -> Result<DecimalArrayDecoder> {
     let data = stripe.stream_map().get(column, Kind::Data);
-    let data = get_rle_reader(column, data)?;
+    let data = get_rle_reader_with_limits(column, data, decode_limits)?;
 
     let secondary = stripe.stream_map().get(column, Kind::Secondary);
-    let secondary = get_rle_reader(column, secondary)?;
+    let secondary = get_rle_reader_with_limits(column, secondary, decode_limits)?;
 
     let present = PresentDecoder::from_stripe(stripe, column);
 
@@ -122,13 +171,22 @@ fn decimal128_decoder(
     ))
 }
 
-/// Decodes a TIMESTAMP column stripe into batches of Timestamp{Nano,Micro,Milli,}secondArrays
-/// with no timezone. Will convert timestamps from writer timezone to UTC if a writer timezone
-/// is specified for the stripe.
+/// Decodes a TIMESTAMP column stripe into batches of Timestamp{Nano,Micro,Milli,}secondArrays.
+/// If the requested field has no timezone, timestamps are converted from the writer timezone
+/// (if any is specified for the stripe) to UTC. If the requested field has a timezone, values
+/// are instead converted into that timezone's wall clock.
+///
+/// `field_type`'s [`TimeUnit`] is driven by
+/// [`ArrowReaderBuilder::with_timestamp_unit`](crate::arrow_reader::ArrowReaderBuilder::with_timestamp_unit),
+/// so a value that would overflow `Nanosecond`'s roughly-1678-to-2262 range can be read at
+/// `Second`/`Millisecond`/`Microsecond` instead; [`TimestampOverflowMode`] controls what
+/// happens to a value that's still out of the chosen unit's range.
 pub fn new_timestamp_decoder(
     column: &Column,
     field_type: ArrowDataType,
     stripe: &Stripe,
+    overflow: TimestampOverflowMode,
+    decode_limits: DecodeLimits,
 ) -> Result<Box<dyn ArrayBatchDecoder>> {
     let seconds_since_unix_epoch = match stripe.writer_tz() {
         Some(writer_tz) => {
@@ -147,28 +205,44 @@ pub fn new_timestamp_decoder(
     };
 
     match field_type {
-        ArrowDataType::Timestamp(TimeUnit::Second, None) => {
-            get_timestamp_decoder::<TimestampSecondType>(column, stripe, seconds_since_unix_epoch)
+        ArrowDataType::Timestamp(TimeUnit::Second, target_tz) => {
+            get_timestamp_decoder::<TimestampSecondType>(
+                column,
+                stripe,
+                seconds_since_unix_epoch,
+                target_tz,
+                overflow,
+                decode_limits,
+            )
         }
-        ArrowDataType::Timestamp(TimeUnit::Millisecond, None) => {
+        ArrowDataType::Timestamp(TimeUnit::Millisecond, target_tz) => {
             get_timestamp_decoder::<TimestampMillisecondType>(
                 column,
                 stripe,
                 seconds_since_unix_epoch,
+                target_tz,
+                overflow,
+                decode_limits,
             )
         }
-        ArrowDataType::Timestamp(TimeUnit::Microsecond, None) => {
+        ArrowDataType::Timestamp(TimeUnit::Microsecond, target_tz) => {
             get_timestamp_decoder::<TimestampMicrosecondType>(
                 column,
                 stripe,
                 seconds_since_unix_epoch,
+                target_tz,
+                overflow,
+                decode_limits,
             )
         }
-        ArrowDataType::Timestamp(TimeUnit::Nanosecond, None) => {
+        ArrowDataType::Timestamp(TimeUnit::Nanosecond, target_tz) => {
             get_timestamp_decoder::<TimestampNanosecondType>(
                 column,
                 stripe,
                 seconds_since_unix_epoch,
+                target_tz,
+                overflow,
+                decode_limits,
             )
         }
         ArrowDataType::Decimal128(Decimal128Type::MAX_PRECISION, NANOSECOND_DIGITS) => {
@@ -177,6 +251,7 @@ pub fn new_timestamp_decoder(
                 stripe,
                 seconds_since_unix_epoch,
                 stripe.writer_tz(),
+                decode_limits,
             )?))
         }
         _ => MismatchedSchemaSnafu {
@@ -188,35 +263,60 @@ pub fn new_timestamp_decoder(
 }
 
 /// Decodes a TIMESTAMP_INSTANT column stripe into batches of
-/// Timestamp{Nano,Micro,Milli,}secondArrays with UTC timezone.
+/// Timestamp{Nano,Micro,Milli,}secondArrays, labelled with whatever timezone the caller
+/// requested (defaulting callers should request UTC, since that's how the underlying
+/// instant is always encoded).
 pub fn new_timestamp_instant_decoder(
     column: &Column,
     field_type: ArrowDataType,
     stripe: &Stripe,
+    overflow: TimestampOverflowMode,
+    decode_limits: DecodeLimits,
 ) -> Result<Box<dyn ArrayBatchDecoder>> {
     match field_type {
-        ArrowDataType::Timestamp(TimeUnit::Second, Some(tz)) if tz.as_ref() == "UTC" => {
-            get_timestamp_instant_decoder::<TimestampSecondType>(column, stripe)
-        }
-        ArrowDataType::Timestamp(TimeUnit::Millisecond, Some(tz)) if tz.as_ref() == "UTC" => {
-            get_timestamp_instant_decoder::<TimestampMillisecondType>(column, stripe)
+        ArrowDataType::Timestamp(TimeUnit::Second, Some(tz)) => {
+            get_timestamp_instant_decoder::<TimestampSecondType>(
+                column,
+                stripe,
+                tz,
+                overflow,
+                decode_limits,
+            )
         }
-        ArrowDataType::Timestamp(TimeUnit::Microsecond, Some(tz)) if tz.as_ref() == "UTC" => {
-            get_timestamp_instant_decoder::<TimestampMicrosecondType>(column, stripe)
+        ArrowDataType::Timestamp(TimeUnit::Millisecond, Some(tz)) => {
+            get_timestamp_instant_decoder::<TimestampMillisecondType>(
+                column,
+                stripe,
+                tz,
+                overflow,
+                decode_limits,
+            )
         }
-        ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some(tz)) if tz.as_ref() == "UTC" => {
-            get_timestamp_instant_decoder::<TimestampNanosecondType>(column, stripe)
+        ArrowDataType::Timestamp(TimeUnit::Microsecond, Some(tz)) => {
+            get_timestamp_instant_decoder::<TimestampMicrosecondType>(
+                column,
+                stripe,
+                tz,
+                overflow,
+                decode_limits,
+            )
         }
-        ArrowDataType::Timestamp(_, Some(_)) => UnsupportedTypeVariantSnafu {
-            msg: "Non-UTC Arrow timestamps",
+        ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some(tz)) => {
+            get_timestamp_instant_decoder::<TimestampNanosecondType>(
+                column,
+                stripe,
+                tz,
+                overflow,
+                decode_limits,
+            )
         }
-        .fail(),
         ArrowDataType::Decimal128(Decimal128Type::MAX_PRECISION, NANOSECOND_DIGITS) => {
             Ok(Box::new(decimal128_decoder(
                 column,
                 stripe,
                 ORC_EPOCH_UTC_SECONDS_SINCE_UNIX_EPOCH,
                 None,
+                decode_limits,
             )?))
         }
         _ => MismatchedSchemaSnafu {
@@ -227,11 +327,15 @@ pub fn new_timestamp_instant_decoder(
     }
 }
 
-/// Wrapper around PrimitiveArrayDecoder to decode timestamps which are encoded in
-/// timezone of the writer to their UTC value.
+/// Wrapper around PrimitiveArrayDecoder to decode timestamps which are encoded in the
+/// timezone of the writer to either UTC (if no target timezone is requested) or the
+/// wall clock of a requested reader timezone.
 struct TimestampOffsetArrayDecoder<T: ArrowTimestampType> {
     inner: PrimitiveArrayDecoder<T>,
     writer_tz: chrono_tz::Tz,
+    /// The timezone to label the output array with, and the [`Tz`] it parses to, kept
+    /// together so we don't have to re-parse it on every batch.
+    target_tz: Option<(Arc<str>, Tz)>,
 }
 
 impl<T: ArrowTimestampType> ArrayBatchDecoder for TimestampOffsetArrayDecoder<T> {
@@ -245,13 +349,21 @@ impl<T: ArrowTimestampType> ArrayBatchDecoder for TimestampOffsetArrayDecoder<T>
             .next_primitive_batch(batch_size, parent_present)?;
 
         let convert_timezone = |ts| {
-            // Convert from writer timezone to reader timezone (which we default to UTC)
-            // TODO: more efficient way of doing this?
-            self.writer_tz
-                .timestamp_nanos(ts)
-                .naive_local()
-                .and_utc()
-                .timestamp_nanos_opt()
+            // First find the writer's wall clock reading for this instant.
+            let writer_local = self.writer_tz.timestamp_nanos(ts).naive_local();
+            match &self.target_tz {
+                // No target timezone requested: output is naive, so we reinterpret the
+                // writer's wall clock reading as if it were already UTC.
+                // TODO: more efficient way of doing this?
+                None => writer_local.and_utc().timestamp_nanos_opt(),
+                // A target timezone was requested: reinterpret the writer's wall clock
+                // reading as being in the target timezone instead, to get the instant
+                // that timezone's clock would agree shows the same wall clock time.
+                Some((_, target_tz)) => target_tz
+                    .from_local_datetime(&writer_local)
+                    .single()
+                    .and_then(|dt| dt.timestamp_nanos_opt()),
+            }
         };
         let array = array
             // first try to convert all non-nullable batches to non-nullable batches
@@ -260,14 +372,19 @@ impl<T: ArrowTimestampType> ArrayBatchDecoder for TimestampOffsetArrayDecoder<T>
             // <https://docs.rs/chrono/latest/chrono/struct.DateTime.html#method.timestamp_nanos_opt>),
             // for nanoseconds), try again by allowing a nullable batch as output
             .unwrap_or_else(|()| array.unary_opt::<_, T>(convert_timezone));
+        let array = match &self.target_tz {
+            Some((tz, _)) => array.with_timezone(tz.clone()),
+            None => array,
+        };
         let array = Arc::new(array) as ArrayRef;
         Ok(array)
     }
 }
 
 /// Wrapper around PrimitiveArrayDecoder to allow specifying the timezone of the output
-/// timestamp array as UTC.
-struct TimestampInstantArrayDecoder<T: ArrowTimestampType>(PrimitiveArrayDecoder<T>);
+/// timestamp array. The underlying values are already UTC instants (TIMESTAMP_INSTANT
+/// doesn't have a writer timezone to convert from), so this only relabels the array.
+struct TimestampInstantArrayDecoder<T: ArrowTimestampType>(PrimitiveArrayDecoder<T>, Arc<str>);
 
 impl<T: ArrowTimestampType> ArrayBatchDecoder for TimestampInstantArrayDecoder<T> {
     fn next_batch(
@@ -278,7 +395,7 @@ impl<T: ArrowTimestampType> ArrayBatchDecoder for TimestampInstantArrayDecoder<T
         let array = self
             .0
             .next_primitive_batch(batch_size, parent_present)?
-            .with_timezone("UTC");
+            .with_timezone(self.1.clone());
         let array = Arc::new(array) as ArrayRef;
         Ok(array)
     }
@@ -18,32 +18,53 @@
 use std::sync::Arc;
 
 use arrow::array::{ArrayRef, BooleanBufferBuilder, UnionArray};
-use arrow::buffer::{Buffer, NullBuffer};
-use arrow::datatypes::UnionFields;
+use arrow::buffer::{Buffer, NullBuffer, ScalarBuffer};
+use arrow::datatypes::{UnionFields, UnionMode};
 use snafu::ResultExt;
 
 use crate::column::Column;
 use crate::encoding::byte::ByteRleDecoder;
+use crate::encoding::integer::DecodeLimits;
+use crate::encoding::timestamp::TimestampOverflowMode;
 use crate::encoding::PrimitiveValueDecoder;
 use crate::error::ArrowSnafu;
 use crate::error::Result;
 use crate::proto::stream::Kind;
+use crate::schema::MapKeyMode;
 use crate::stripe::Stripe;
 
 use super::{array_decoder_factory, derive_present_vec, ArrayBatchDecoder, PresentDecoder};
 
-/// Decode ORC Union column into batches of Arrow Sparse UnionArrays.
+/// Decode ORC Union column into batches of Arrow `UnionArray`s, either Sparse (every
+/// variant padded out to the batch's full length) or Dense (each variant sized to just
+/// the rows tagged for it, indexed via a value-offsets buffer), matching whichever
+/// [`UnionMode`] the target schema's field asked for. ORC itself only ever writes a
+/// single byte-sized tag stream selecting the active variant per row, with each variant
+/// column storing just its own rows compactly -- [`Self::new`] below builds one child
+/// [`ArrayBatchDecoder`] per variant via [`array_decoder_factory`], and [`Self::next_batch`]
+/// reshapes that compact-per-variant layout into whichever dense/sparse `UnionArray` was
+/// requested.
 pub struct UnionArrayDecoder {
     // fields and variants should have same length
     // TODO: encode this assumption into types
     fields: UnionFields,
+    mode: UnionMode,
     variants: Vec<Box<dyn ArrayBatchDecoder>>,
     tags: Box<dyn PrimitiveValueDecoder<i8> + Send>,
     present: Option<PresentDecoder>,
 }
 
 impl UnionArrayDecoder {
-    pub fn new(column: &Column, fields: UnionFields, stripe: &Stripe) -> Result<Self> {
+    pub fn new(
+        column: &Column,
+        fields: UnionFields,
+        mode: UnionMode,
+        stripe: &Stripe,
+        timestamp_overflow: TimestampOverflowMode,
+        decode_limits: DecodeLimits,
+        map_key_mode: MapKeyMode,
+        decode_pool: Option<Arc<rayon::ThreadPool>>,
+    ) -> Result<Self> {
         let present = PresentDecoder::from_stripe(stripe, column);
 
         let tags = stripe.stream_map().get(column, Kind::Data);
@@ -53,11 +74,22 @@ impl UnionArrayDecoder {
             .children()
             .iter()
             .zip(fields.iter())
-            .map(|(child, (_id, field))| array_decoder_factory(child, field.clone(), stripe))
+            .map(|(child, (_id, field))| {
+                array_decoder_factory(
+                    child,
+                    field.clone(),
+                    stripe,
+                    timestamp_overflow,
+                    decode_limits,
+                    map_key_mode,
+                    decode_pool.clone(),
+                )
+            })
             .collect::<Result<Vec<_>>>()?;
 
         Ok(Self {
             fields,
+            mode,
             variants,
             tags,
             present,
@@ -85,6 +117,34 @@ impl ArrayBatchDecoder for UnionArrayDecoder {
             }
         }
 
+        let (child_arrays, value_offsets) = match self.mode {
+            UnionMode::Sparse => (
+                self.decode_sparse_children(&tags, &present, batch_size)?,
+                None,
+            ),
+            UnionMode::Dense => {
+                let (child_arrays, offsets) = self.decode_dense_children(&tags, &present)?;
+                (child_arrays, Some(ScalarBuffer::from(offsets)))
+            }
+        };
+
+        let type_ids = Buffer::from_vec(tags).into();
+        let array = UnionArray::try_new(self.fields.clone(), type_ids, value_offsets, child_arrays)
+            .context(ArrowSnafu)?;
+        let array = Arc::new(array);
+        Ok(array)
+    }
+}
+
+impl UnionArrayDecoder {
+    /// Decodes every variant padded out to `batch_size`, with only the rows tagged for
+    /// it marked non-null -- the layout [`UnionMode::Sparse`] expects.
+    fn decode_sparse_children(
+        &mut self,
+        tags: &[i8],
+        present: &Option<NullBuffer>,
+        batch_size: usize,
+    ) -> Result<Vec<ArrayRef>> {
         // Calculate nullability for children
         let mut children_nullability = (0..self.variants.len())
             .map(|index| {
@@ -95,7 +155,9 @@ impl ArrayBatchDecoder for UnionArrayDecoder {
                     .enumerate()
                     // Where the parent expects the value of the child, we set to non-null.
                     // Otherwise for the sparse spots, we leave as null in children.
-                    .filter_map(|(idx, &tag)| (tag as usize == index).then_some(idx))
+                    // `tag` is really a 0..=255 byte that's been through `as u8 as i8` (see
+                    // `to_arrow_data_type`), so it's un-wrapped the same way before comparing.
+                    .filter_map(|(idx, &tag)| ((tag as u8) as usize == index).then_some(idx))
                 {
                     child_present.set_bit(idx, true);
                 }
@@ -106,7 +168,7 @@ impl ArrayBatchDecoder for UnionArrayDecoder {
         // encodes this information, since as mentioned before, Arrow UnionArrays don't store
         // nullability and rely on their children. We default to first child to encode this
         // information so need to enforce that here.
-        if let Some(present) = &present {
+        if let Some(present) = present {
             let first_child = &mut children_nullability[0];
             for idx in present
                 .iter()
@@ -117,21 +179,68 @@ impl ArrayBatchDecoder for UnionArrayDecoder {
             }
         }
 
-        let child_arrays = self
-            .variants
+        self.variants
             .iter_mut()
             .zip(children_nullability)
             .map(|(decoder, mut present)| {
                 let present = NullBuffer::from(present.finish());
                 decoder.next_batch(batch_size, Some(&present))
             })
+            .collect::<Result<Vec<_>>>()
+    }
+
+    /// Decodes each variant sized to just the rows tagged for it (so a wide union's
+    /// unused variants never allocate a full `batch_size` array), alongside the
+    /// per-row offset into its variant's dense array that [`UnionMode::Dense`] needs.
+    ///
+    /// A row whose parent slot is null still occupies a slot in its (faked, see below)
+    /// tag's dense array -- `UnionArray`'s `value_offsets` must point somewhere valid for
+    /// every row regardless of nullness -- so each variant's present mask is built over
+    /// exactly the rows tagged for it, real or faked, rather than correcting the fake
+    /// tag-0 fallback away the way the Sparse path does.
+    fn decode_dense_children(
+        &mut self,
+        tags: &[i8],
+        present: &Option<NullBuffer>,
+    ) -> Result<(Vec<ArrayRef>, Vec<i32>)> {
+        let num_variants = self.variants.len();
+        let mut variant_lengths = vec![0usize; num_variants];
+        let mut value_offsets = Vec::with_capacity(tags.len());
+        for &tag in tags {
+            // `tag` is a 0..=255 byte that's been through `as u8 as i8` (see
+            // `to_arrow_data_type`), so un-wrap it the same way before using it as an index.
+            let length = &mut variant_lengths[tag as u8 as usize];
+            value_offsets.push(*length as i32);
+            *length += 1;
+        }
+
+        let mut variant_present_builders = variant_lengths
+            .iter()
+            .map(|&length| {
+                let mut builder = BooleanBufferBuilder::new(length);
+                builder.append_n(length, true);
+                builder
+            })
+            .collect::<Vec<_>>();
+        if let Some(present) = present {
+            for (idx, (&tag, &offset)) in tags.iter().zip(&value_offsets).enumerate() {
+                if !present.is_valid(idx) {
+                    variant_present_builders[tag as u8 as usize].set_bit(offset as usize, false);
+                }
+            }
+        }
+
+        let child_arrays = self
+            .variants
+            .iter_mut()
+            .zip(variant_lengths)
+            .zip(variant_present_builders)
+            .map(|((decoder, length), mut present)| {
+                let present = NullBuffer::from(present.finish());
+                decoder.next_batch(length, Some(&present))
+            })
             .collect::<Result<Vec<_>>>()?;
 
-        // Currently default to decoding as Sparse UnionArray so no value offsets
-        let type_ids = Buffer::from_vec(tags.clone()).into();
-        let array = UnionArray::try_new(self.fields.clone(), type_ids, None, child_arrays)
-            .context(ArrowSnafu)?;
-        let array = Arc::new(array);
-        Ok(array)
+        Ok((child_arrays, value_offsets))
     }
 }
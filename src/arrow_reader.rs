@@ -15,24 +15,40 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::ops::Range;
-use std::sync::Arc;
+use std::sync::{mpsc, Arc};
 
-use arrow::datatypes::SchemaRef;
+use arrow::datatypes::{DataType as ArrowDataType, SchemaRef, TimeUnit};
 use arrow::error::ArrowError;
 use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use snafu::ensure;
 
 use crate::array_decoder::NaiveStripeDecoder;
-use crate::error::Result;
+use crate::encoding::integer::DecodeLimits;
+use crate::encoding::timestamp::TimestampOverflowMode;
+use crate::error::{self, Result};
+use crate::predicate::{Predicate, ResolvedPredicate};
 use crate::projection::ProjectionMask;
 use crate::reader::metadata::{read_metadata, FileMetadata};
 use crate::reader::ChunkReader;
-use crate::schema::RootDataType;
+use crate::row_selection::RowSelection;
+use crate::schema::{MapFieldNames, MapKeyMode, RootDataType};
+use crate::statistics::{ColumnStatistics, TypeStatistics};
 use crate::stripe::{Stripe, StripeMetadata};
 
 const DEFAULT_BATCH_SIZE: usize = 8192;
 
+/// Default [`ArrowReaderBuilder::with_coalesce_gap_threshold`]: merge stream reads separated
+/// by less than this many bytes. Used by both [`build`](ArrowReaderBuilder::build) and
+/// [`build_async`](ArrowReaderBuilder::build_async) -- see [`Stripe::new`](crate::stripe::Stripe::new)/
+/// [`Stripe::new_async`](crate::stripe::Stripe::new_async).
+const DEFAULT_COALESCE_GAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// Default [`ArrowReaderBuilder::with_coalesce_max_merged_size`]: never merge stream reads
+/// into a single request larger than this, even if the gaps between them are small.
+const DEFAULT_COALESCE_MAX_MERGED_SIZE: u64 = 8 * 1024 * 1024;
+
 pub struct ArrowReaderBuilder<R> {
     pub(crate) reader: R,
     pub(crate) file_metadata: Arc<FileMetadata>,
@@ -40,6 +56,22 @@ pub struct ArrowReaderBuilder<R> {
     pub(crate) projection: ProjectionMask,
     pub(crate) schema_ref: Option<SchemaRef>,
     pub(crate) file_byte_range: Option<Range<usize>>,
+    pub(crate) decode_parallelism: usize,
+    pub(crate) timestamp_unit: TimeUnit,
+    pub(crate) prefetch: usize,
+    pub(crate) dictionary_key_type: Option<ArrowDataType>,
+    pub(crate) map_field_names: MapFieldNames,
+    pub(crate) map_key_mode: MapKeyMode,
+    pub(crate) output_timestamp_tz: Option<Arc<str>>,
+    pub(crate) use_utf8_view: bool,
+    pub(crate) predicates: Vec<Predicate>,
+    pub(crate) timestamp_overflow: TimestampOverflowMode,
+    pub(crate) stripe_concurrency: usize,
+    pub(crate) coalesce_gap_threshold: u64,
+    pub(crate) coalesce_max_merged_size: u64,
+    pub(crate) ignore_writer_tz: bool,
+    pub(crate) row_range: Option<(u64, Option<u64>)>,
+    pub(crate) decode_value_limit: DecodeLimits,
 }
 
 impl<R> ArrowReaderBuilder<R> {
@@ -51,6 +83,22 @@ impl<R> ArrowReaderBuilder<R> {
             projection: ProjectionMask::all(),
             schema_ref: None,
             file_byte_range: None,
+            decode_parallelism: 1,
+            timestamp_unit: TimeUnit::Nanosecond,
+            prefetch: 0,
+            dictionary_key_type: None,
+            map_field_names: MapFieldNames::default(),
+            map_key_mode: MapKeyMode::default(),
+            output_timestamp_tz: None,
+            use_utf8_view: false,
+            predicates: Vec::new(),
+            timestamp_overflow: TimestampOverflowMode::default(),
+            stripe_concurrency: 1,
+            coalesce_gap_threshold: DEFAULT_COALESCE_GAP_THRESHOLD,
+            coalesce_max_merged_size: DEFAULT_COALESCE_MAX_MERGED_SIZE,
+            ignore_writer_tz: false,
+            row_range: None,
+            decode_value_limit: DecodeLimits::UNLIMITED,
         }
     }
 
@@ -63,11 +111,40 @@ impl<R> ArrowReaderBuilder<R> {
         self
     }
 
+    /// Restricts reading to the columns selected by `projection` (see
+    /// [`ProjectionMask::roots`]/[`ProjectionMask::named_roots`]/[`ProjectionMask::paths`]).
+    /// Unprojected columns' present/data streams are never read or decompressed, so this is
+    /// the way to scan a subset of a wide file cheaply, not just to trim the output schema.
     pub fn with_projection(mut self, projection: ProjectionMask) -> Self {
         self.projection = projection;
         self
     }
 
+    /// Convenience for [`with_projection`](Self::with_projection) that resolves dotted
+    /// column paths via [`ProjectionMask::paths`] against this file's root type, e.g.
+    /// `["order.customer.id", "order.items"]`. See [`ProjectionMask::paths`] for exactly
+    /// how a path resolves through nested structs/lists/maps.
+    pub fn with_projection_by_names<T: AsRef<str>>(mut self, paths: &[T]) -> Result<Self> {
+        self.projection = ProjectionMask::paths(self.file_metadata.root_data_type(), paths)?;
+        Ok(self)
+    }
+
+    /// Reports and decodes columns at `schema` instead of the schema this file's own ORC
+    /// types would naturally produce (see [`Self::schema`]). Resolved by column name, not
+    /// position, against [`NaiveStripeDecoder`](crate::array_decoder::NaiveStripeDecoder):
+    ///
+    /// - A name present in both is decoded at its natural type, then cast to `schema`'s type
+    ///   for that column with `arrow-rs`'s [`cast`](arrow::compute::cast), if the two differ.
+    ///   An unsupported cast (e.g. `Binary` to `Int64`) fails the read with a descriptive
+    ///   error up front, rather than partway through a batch.
+    /// - A name `schema` asks for that isn't one of this file's columns is filled with an
+    ///   all-null array of the requested type, for every row.
+    /// - A column this file has that `schema` doesn't mention is dropped, the same as an
+    ///   unprojected one.
+    ///
+    /// This is the tool for reading many ORC files sharing a logical table schema that
+    /// nonetheless evolved slightly between writers (a column added later, an `Int` widened
+    /// to a `Long`), without a separate post-read pass over every batch.
     pub fn with_schema(mut self, schema: SchemaRef) -> Self {
         self.schema_ref = Some(schema);
         self
@@ -79,11 +156,202 @@ impl<R> ArrowReaderBuilder<R> {
         self
     }
 
+    /// Restricts the scan to a contiguous window of rows: `skip_rows` rows are dropped from
+    /// the start, then up to `num_rows` rows are yielded (or every remaining row, if `None`),
+    /// like seeking an archive cursor to a logical offset before reading. Whole stripes that
+    /// fall entirely before the window aren't even read, only the stripe straddling
+    /// `skip_rows` pays for decoding a prefix it then discards. [`ArrowReader::total_row_count`]
+    /// keeps reporting the full file's row count regardless of this window.
+    pub fn with_row_range(mut self, skip_rows: u64, num_rows: Option<u64>) -> Self {
+        self.row_range = Some((skip_rows, num_rows));
+        self
+    }
+
+    /// Adds a [`Predicate`] used to skip whole stripes via their column statistics
+    /// before decompressing any of their data streams. Can be called more than once;
+    /// every predicate added this way must hold for a stripe to be scanned (they're
+    /// ANDed together). See [`Predicate`]'s docs for exactly what it can and can't prove.
+    pub fn with_predicate(mut self, predicate: Predicate) -> Self {
+        self.predicates.push(predicate);
+        self
+    }
+
+    /// Sets how an out-of-range or precision-losing timestamp value is handled during
+    /// decoding. Defaults to [`TimestampOverflowMode::Error`], matching historical behavior:
+    /// see its other variants to keep a scan running past a single bad value in an otherwise
+    /// valid stripe, instead of failing it.
+    pub fn with_timestamp_overflow(mut self, mode: TimestampOverflowMode) -> Self {
+        self.timestamp_overflow = mode;
+        self
+    }
+
+    /// Caps the total number of values any single RLE-decoded stream (e.g. an `Int`/`Long`
+    /// column's `Data` stream, or a `String`/`List`/`Map` column's `Length` stream) is willing
+    /// to decode over the lifetime of the read, so a corrupt or adversarial file that replays
+    /// an absurdly long sequence of maximal-length runs can't be used to force unbounded work
+    /// out of this reader. Defaults to [`DecodeLimits::UNLIMITED`], matching historical
+    /// behavior; set this when reading untrusted files.
+    pub fn with_decode_value_limit(mut self, max_values: usize) -> Self {
+        self.decode_value_limit = DecodeLimits::new(max_values);
+        self
+    }
+
+    /// Decodes each projected column of a batch on a rayon thread instead of serially on the
+    /// calling thread, using up to `n` threads. Column decoders read disjoint streams, so this
+    /// is safe whenever a stripe has more than one projected column to spread across threads.
+    /// `n <= 1` (the default) keeps decoding single-threaded on the caller. Nested column
+    /// decoders (struct/list/map/union fields) share the same pool and fan their own
+    /// children out across it too -- see `StructArrayDecoder`'s handling of `decode_pool`.
+    pub fn with_decode_parallelism(mut self, n: usize) -> Self {
+        self.decode_parallelism = n;
+        self
+    }
+
+    /// Reads up to `n` stripes ahead of the one currently being consumed and decodes them
+    /// concurrently on a thread pool, instead of [`ArrowReader::next`] decoding one stripe
+    /// at a time on the caller. Output batches are still surfaced in stripe order: each
+    /// stripe is fully decoded into its own buffer of [`RecordBatch`]es on the pool, and
+    /// those buffers are drained in the order their stripes were read, so this only
+    /// overlaps CPU work across stripes -- it doesn't reorder or interleave their rows.
+    /// Reading stripe bytes from the underlying reader stays serial either way. `n <= 1`
+    /// (the default) keeps the original one-stripe-at-a-time behavior.
+    pub fn with_decode_concurrency(mut self, n: usize) -> Self {
+        self.stripe_concurrency = n;
+        self
+    }
+
+    /// Sets the [`TimeUnit`] timestamp columns are reported in when the schema is computed
+    /// automatically (i.e. [`with_schema`](Self::with_schema) was not called). Defaults to
+    /// `Nanosecond`, but ORC's timestamp range exceeds what an i64 count of nanoseconds
+    /// since the epoch can represent (roughly years 1677-2262); pick a coarser unit to
+    /// trade precision for range when reading historical or far-future data.
+    pub fn with_timestamp_unit(mut self, timestamp_unit: TimeUnit) -> Self {
+        self.timestamp_unit = timestamp_unit;
+        self
+    }
+
+    /// Only used by [`build_async`](Self::build_async): allows up to `n` stripe reads beyond
+    /// the one currently being awaited to be in flight at once, so I/O for stripe `N+1`
+    /// (and beyond, up to `N+n`) can overlap decoding of stripe `N`. Decoded batches are
+    /// still surfaced in stripe order. `n = 0` (the default) keeps the original
+    /// one-stripe-at-a-time behavior, which is appropriate for local files but leaves
+    /// throughput on the table against high-latency object stores.
+    pub fn with_prefetch(mut self, n: usize) -> Self {
+        self.prefetch = n;
+        self
+    }
+
+    /// A stripe's streams separated by less than `gap_threshold` bytes are fetched as a
+    /// single read instead of one per stream (up to
+    /// [`with_coalesce_max_merged_size`](Self::with_coalesce_max_merged_size)), trading a
+    /// few unwanted bytes read from the gap for fewer round-trips to the underlying reader.
+    /// Applies to both [`build`](Self::build) and [`build_async`](Self::build_async).
+    /// Defaults to 1 MiB.
+    pub fn with_coalesce_gap_threshold(mut self, gap_threshold: u64) -> Self {
+        self.coalesce_gap_threshold = gap_threshold;
+        self
+    }
+
+    /// Caps how large a single merged read from
+    /// [`with_coalesce_gap_threshold`](Self::with_coalesce_gap_threshold) is allowed to grow,
+    /// even if every gap along the way is small enough to bridge. Applies to both
+    /// [`build`](Self::build) and [`build_async`](Self::build_async). Defaults to 8 MiB.
+    pub fn with_coalesce_max_merged_size(mut self, max_merged_size: u64) -> Self {
+        self.coalesce_max_merged_size = max_merged_size;
+        self
+    }
+
+    /// Opts every `String`/`Varchar`/`Char` column into dictionary-preserving decoding when
+    /// the schema is computed automatically (i.e. [`with_schema`](Self::with_schema) was not
+    /// called), reporting them as `Dictionary(key_type, Utf8)` instead of plain `Utf8`.
+    ///
+    /// `key_type` should be an unsigned integer type (`UInt8`/`UInt16`/`UInt32`/`UInt64`).
+    /// Passing `UInt64` asks each stripe to instead pick the narrowest of those four that
+    /// fits its own dictionary, since most ORC dictionaries are small enough that a full
+    /// 64-bit key would waste 4-8x the memory for no benefit; any other width is honored
+    /// exactly as requested, which is useful when a stable, fixed-width schema matters more
+    /// than shaving a few bytes per key.
+    /// See [`Column::arrow_data_type`](crate::column::Column::arrow_data_type).
+    pub fn with_dictionary_key_type(mut self, key_type: ArrowDataType) -> Self {
+        self.dictionary_key_type = Some(key_type);
+        self
+    }
+
+    /// Names the `entries`/key/value Arrow fields generated for a `Map` column when the
+    /// schema is computed automatically (i.e. [`with_schema`](Self::with_schema) was not
+    /// called). Defaults to this crate's historical `"entries"`/`"keys"`/`"values"`, but
+    /// other ORC readers (e.g. PyArrow) name these `"key"`/`"value"` instead; Arrow treats
+    /// `Map` types with differently-named fields as incompatible, so merging batches read
+    /// from both kinds of writer requires picking one naming up front.
+    pub fn with_map_field_names(mut self, map_field_names: MapFieldNames) -> Self {
+        self.map_field_names = map_field_names;
+        self
+    }
+
+    /// Controls whether `Map` columns are decoded as-is, checked for duplicate keys, or
+    /// sorted by key; see [`MapKeyMode`]. Defaults to [`MapKeyMode::Unordered`], matching
+    /// ORC's own lack of any ordering or uniqueness guarantee for map entries.
+    pub fn with_map_key_mode(mut self, map_key_mode: MapKeyMode) -> Self {
+        self.map_key_mode = map_key_mode;
+        self
+    }
+
+    /// Labels timestamp columns of the automatically-computed schema (i.e.
+    /// [`with_schema`](Self::with_schema) was not called) with `tz` instead of the
+    /// default (no timezone for plain `TIMESTAMP` columns, `"UTC"` for
+    /// `TIMESTAMP WITH LOCAL TIME ZONE` columns); every value is converted into `tz`'s
+    /// wall clock during decoding, not just relabeled. `tz` must be a timezone
+    /// [`chrono_tz::Tz`] recognizes (e.g. `"America/New_York"`) or decoding that column
+    /// will fail.
+    pub fn with_timestamp_timezone(mut self, tz: impl Into<Arc<str>>) -> Self {
+        self.output_timestamp_tz = Some(tz.into());
+        self
+    }
+
+    /// Opts every `String`/`Varchar`/`Char` column into `Utf8View` decoding when the schema
+    /// is computed automatically (i.e. [`with_schema`](Self::with_schema) was not called),
+    /// instead of the default plain `Utf8`. `Utf8View` stores short strings inline and longer
+    /// ones via buffer views into the decoded byte runs, cutting allocation and copy overhead
+    /// for columns with many short values and enabling zero-copy slicing downstream.
+    ///
+    /// Ignored for a column that also has [`with_dictionary_key_type`](Self::with_dictionary_key_type)
+    /// applied to it, since that already opts the column into a different (dictionary-preserving)
+    /// representation.
+    pub fn with_utf8_view(mut self, use_utf8_view: bool) -> Self {
+        self.use_utf8_view = use_utf8_view;
+        self
+    }
+
+    /// Opts a plain `TIMESTAMP` column out of the conversion from the writer's recorded
+    /// timezone (see [`Stripe::writer_tz`](crate::stripe::Stripe::writer_tz)) that's otherwise
+    /// always applied, reinterpreting its writer-local wall clock values as UTC instants
+    /// exactly as recorded instead. Has no effect on `TIMESTAMP WITH LOCAL TIME ZONE` columns,
+    /// which are already encoded as UTC instants regardless of writer timezone. The raw
+    /// per-stripe writer timezone is still available via
+    /// [`Stripe::writer_timezone_name`](crate::stripe::Stripe::writer_timezone_name)
+    /// even when this is set.
+    pub fn with_ignore_writer_timezone(mut self, ignore: bool) -> Self {
+        self.ignore_writer_tz = ignore;
+        self
+    }
+
     /// Returns the currently computed schema
     ///
     /// Unless [`with_schema`](Self::with_schema) was called, this is computed dynamically
     /// based on the current projection and the underlying file format.
     pub fn schema(&self) -> SchemaRef {
+        self.schema_ref
+            .clone()
+            .unwrap_or_else(|| self.native_schema())
+    }
+
+    /// The schema this file's columns would be reported under if
+    /// [`with_schema`](Self::with_schema) had never been called, regardless of whether it
+    /// actually was. [`NaiveStripeDecoder`](crate::array_decoder::NaiveStripeDecoder) decodes
+    /// every column at this schema first, then resolves the result against
+    /// [`Self::schema`] by column name -- see its doc comment for how a name that's missing,
+    /// renamed, or retyped between the two is handled.
+    pub(crate) fn native_schema(&self) -> SchemaRef {
         let projected_data_type = self
             .file_metadata
             .root_data_type()
@@ -94,9 +362,65 @@ impl<R> ArrowReaderBuilder<R> {
             .iter()
             .map(|(key, value)| (key.clone(), String::from_utf8_lossy(value).to_string()))
             .collect::<HashMap<_, _>>();
-        self.schema_ref
-            .clone()
-            .unwrap_or_else(|| Arc::new(projected_data_type.create_arrow_schema(&metadata)))
+        Arc::new(projected_data_type.create_arrow_schema(
+            &metadata,
+            self.timestamp_unit,
+            self.dictionary_key_type.as_ref(),
+            &self.map_field_names,
+            self.output_timestamp_tz.as_ref(),
+            self.use_utf8_view,
+        ))
+    }
+
+    /// Checks every `Timestamp`/`TimestampWithLocalTimezone` column selected by the current
+    /// projection against the file's column statistics (see
+    /// [`FileMetadata::column_file_statistics`]), returning a descriptive error if any of them
+    /// holds a value too far in the future or the past for [`with_timestamp_unit`]'s chosen
+    /// [`TimeUnit`] to represent once scaled -- most commonly `Nanosecond` (the default),
+    /// which overflows an `i64` past roughly the year 2262.
+    ///
+    /// Not called automatically by [`build`](Self::build)/[`build_async`](Self::build_async):
+    /// both return the reader directly rather than a `Result`, so turning an out-of-range
+    /// timestamp into a hard error there would be a breaking change to their signatures. Call
+    /// this explicitly first if you'd rather reject such a file upfront than have individual
+    /// batches affected according to [`with_timestamp_overflow`](Self::with_timestamp_overflow)
+    /// once decoding is already underway. Silently does nothing for a column the file didn't
+    /// write timestamp statistics for.
+    pub fn validate_timestamp_range(&self) -> Result<()> {
+        let projected_data_type = self
+            .file_metadata
+            .root_data_type()
+            .project(&self.projection);
+        let column_statistics = self.file_metadata.column_file_statistics();
+        for column_index in projected_data_type.timestamp_column_indices() {
+            let Some(TypeStatistics::Timestamp {
+                min_utc, max_utc, ..
+            }) = column_statistics
+                .get(column_index)
+                .and_then(ColumnStatistics::type_statistics)
+            else {
+                continue;
+            };
+            let fits = |ms: i64| -> bool {
+                let scaled = match self.timestamp_unit {
+                    TimeUnit::Second => ms as i128 / 1_000,
+                    TimeUnit::Millisecond => ms as i128,
+                    TimeUnit::Microsecond => ms as i128 * 1_000,
+                    TimeUnit::Nanosecond => ms as i128 * 1_000_000,
+                };
+                (i64::MIN as i128..=i64::MAX as i128).contains(&scaled)
+            };
+            ensure!(
+                fits(*min_utc) && fits(*max_utc),
+                error::TimestampUnitOverflowSnafu {
+                    column_index,
+                    unit: self.timestamp_unit,
+                    min_utc_ms: *min_utc,
+                    max_utc_ms: *max_utc,
+                }
+            );
+        }
+        Ok(())
     }
 }
 
@@ -108,22 +432,66 @@ impl<R: ChunkReader> ArrowReaderBuilder<R> {
 
     pub fn build(self) -> ArrowReader<R> {
         let schema_ref = self.schema();
+        let native_schema = self.native_schema();
+        // Recurses into struct children (see `RootDataType::project`), so this is also what
+        // makes projecting a single nested field pull only that field's own sub-columns'
+        // streams, not its whole parent struct.
         let projected_data_type = self
             .file_metadata
             .root_data_type()
             .project(&self.projection);
-        let cursor = Cursor {
+        let predicates = self
+            .predicates
+            .iter()
+            .filter_map(|predicate| predicate.resolve(self.file_metadata.root_data_type()))
+            .collect();
+        let mut cursor = Cursor {
             reader: self.reader,
             file_metadata: self.file_metadata,
             projected_data_type,
             stripe_index: 0,
             file_byte_range: self.file_byte_range,
+            predicates,
+            coalesce_gap_threshold: self.coalesce_gap_threshold,
+            coalesce_max_merged_size: self.coalesce_max_merged_size,
+            ignore_writer_tz: self.ignore_writer_tz,
+        };
+        let (rows_to_skip, rows_remaining) = match self.row_range {
+            Some((skip_rows, num_rows)) => {
+                let stripes = cursor.get_stripe_metadatas();
+                let mut cumulative = 0u64;
+                let mut start_index = stripes.len();
+                let mut skip_within_stripe = 0u64;
+                for (index, info) in stripes.iter().enumerate() {
+                    let rows = info.number_of_rows();
+                    if cumulative + rows <= skip_rows {
+                        cumulative += rows;
+                        continue;
+                    }
+                    start_index = index;
+                    skip_within_stripe = skip_rows - cumulative;
+                    break;
+                }
+                cursor.stripe_index = start_index;
+                (skip_within_stripe, num_rows)
+            }
+            None => (0, None),
         };
         ArrowReader {
             cursor,
             schema_ref,
+            native_schema,
             current_stripe: None,
             batch_size: self.batch_size,
+            decode_parallelism: self.decode_parallelism,
+            timestamp_overflow: self.timestamp_overflow,
+            decode_value_limit: self.decode_value_limit,
+            map_key_mode: self.map_key_mode,
+            stripe_concurrency: self.stripe_concurrency,
+            decode_pool: None,
+            decoding_stripes: VecDeque::new(),
+            rows_to_skip,
+            rows_remaining,
         }
     }
 }
@@ -131,8 +499,31 @@ impl<R: ChunkReader> ArrowReaderBuilder<R> {
 pub struct ArrowReader<R> {
     cursor: Cursor<R>,
     schema_ref: SchemaRef,
+    /// See [`ArrowReaderBuilder::native_schema`].
+    native_schema: SchemaRef,
     current_stripe: Option<Box<dyn Iterator<Item = Result<RecordBatch>> + Send>>,
     batch_size: usize,
+    decode_parallelism: usize,
+    timestamp_overflow: TimestampOverflowMode,
+    /// Set via [`ArrowReaderBuilder::with_decode_value_limit`]; caps how many values any
+    /// single RLE-decoded stream will decode over the life of the read.
+    decode_value_limit: DecodeLimits,
+    /// Set via [`ArrowReaderBuilder::with_map_key_mode`].
+    map_key_mode: MapKeyMode,
+    /// Set via [`ArrowReaderBuilder::with_decode_concurrency`]; `<= 1` keeps the original
+    /// one-stripe-at-a-time path in [`Self::try_advance_stripe`].
+    stripe_concurrency: usize,
+    /// Built lazily the first time a stripe is decoded concurrently.
+    decode_pool: Option<Arc<rayon::ThreadPool>>,
+    /// One entry per stripe read ahead but not yet drained, oldest first, each resolving to
+    /// that whole stripe's decoded batches once its pool thread finishes.
+    decoding_stripes: VecDeque<mpsc::Receiver<Result<Vec<RecordBatch>>>>,
+    /// Set by [`ArrowReaderBuilder::with_row_range`]: rows still to drop from the front of
+    /// the first stripe straddling the requested window, before any row is yielded.
+    rows_to_skip: u64,
+    /// Set by [`ArrowReaderBuilder::with_row_range`]: rows still to yield before the window
+    /// closes. `None` means unbounded (read to the end of the file).
+    rows_remaining: Option<u64>,
 }
 
 impl<R> ArrowReader<R> {
@@ -143,17 +534,95 @@ impl<R> ArrowReader<R> {
 
 impl<R: ChunkReader> ArrowReader<R> {
     fn try_advance_stripe(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        if self.stripe_concurrency > 1 {
+            return self.try_advance_stripe_concurrent();
+        }
         let stripe = self.cursor.next().transpose()?;
         match stripe {
             Some(stripe) => {
-                let decoder =
-                    NaiveStripeDecoder::new(stripe, self.schema_ref.clone(), self.batch_size)?;
+                let row_selection = self.cursor.row_selection_for(&stripe)?;
+                let decoder = NaiveStripeDecoder::new(
+                    stripe,
+                    self.schema_ref.clone(),
+                    self.native_schema.clone(),
+                    self.batch_size,
+                    self.decode_parallelism,
+                    self.timestamp_overflow,
+                    self.decode_value_limit,
+                    self.map_key_mode,
+                    row_selection,
+                )?;
                 self.current_stripe = Some(Box::new(decoder));
-                self.next().transpose()
+                self.next_raw_batch()
             }
             None => Ok(None),
         }
     }
+
+    /// Drains the oldest still-decoding stripe, topping the pipeline back up to
+    /// `stripe_concurrency` stripes in flight on either side of the drain.
+    fn try_advance_stripe_concurrent(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        self.top_up_decoding_stripes()?;
+        let Some(receiver) = self.decoding_stripes.pop_front() else {
+            return Ok(None);
+        };
+        let batches = receiver
+            .recv()
+            .map_err(|e| ArrowError::ExternalError(Box::new(e)))??;
+        self.current_stripe = Some(Box::new(batches.into_iter().map(Ok)));
+        self.top_up_decoding_stripes()?;
+        self.next_raw_batch()
+    }
+
+    /// Reads (serially) and dispatches to the pool (concurrently) however many more
+    /// stripes are needed to bring `decoding_stripes` up to `stripe_concurrency` entries.
+    fn top_up_decoding_stripes(&mut self) -> Result<(), ArrowError> {
+        let stripe_concurrency = self.stripe_concurrency;
+        let pool = self
+            .decode_pool
+            .get_or_insert_with(|| {
+                Arc::new(
+                    rayon::ThreadPoolBuilder::new()
+                        .num_threads(stripe_concurrency)
+                        .build()
+                        .expect("failed to build stripe decode thread pool"),
+                )
+            })
+            .clone();
+        while self.decoding_stripes.len() < self.stripe_concurrency {
+            let Some(stripe) = self.cursor.next().transpose()? else {
+                break;
+            };
+            let row_selection = self.cursor.row_selection_for(&stripe)?;
+            let schema_ref = self.schema_ref.clone();
+            let native_schema = self.native_schema.clone();
+            let batch_size = self.batch_size;
+            let decode_parallelism = self.decode_parallelism;
+            let timestamp_overflow = self.timestamp_overflow;
+            let decode_value_limit = self.decode_value_limit;
+            let map_key_mode = self.map_key_mode;
+            let (sender, receiver) = mpsc::sync_channel(1);
+            pool.spawn(move || {
+                let result = NaiveStripeDecoder::new(
+                    stripe,
+                    schema_ref,
+                    native_schema,
+                    batch_size,
+                    decode_parallelism,
+                    timestamp_overflow,
+                    decode_value_limit,
+                    map_key_mode,
+                    row_selection,
+                )
+                .and_then(|decoder| decoder.collect());
+                // A closed receiver (the `ArrowReader` was dropped mid-decode) just means
+                // nobody wants this stripe's batches anymore.
+                let _ = sender.send(result);
+            });
+            self.decoding_stripes.push_back(receiver);
+        }
+        Ok(())
+    }
 }
 
 impl<R: ChunkReader> RecordBatchReader for ArrowReader<R> {
@@ -162,21 +631,63 @@ impl<R: ChunkReader> RecordBatchReader for ArrowReader<R> {
     }
 }
 
-impl<R: ChunkReader> Iterator for ArrowReader<R> {
-    type Item = Result<RecordBatch, ArrowError>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl<R: ChunkReader> ArrowReader<R> {
+    /// Pulls the next batch straight out of the current (or next) stripe, with no regard for
+    /// [`ArrowReaderBuilder::with_row_range`]'s window -- [`Iterator::next`] is what applies
+    /// that on top of this.
+    fn next_raw_batch(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
         match self.current_stripe.as_mut() {
             Some(stripe) => {
                 match stripe
                     .next()
                     .map(|batch| batch.map_err(|err| ArrowError::ExternalError(Box::new(err))))
                 {
-                    Some(rb) => Some(rb),
-                    None => self.try_advance_stripe().transpose(),
+                    Some(rb) => rb.map(Some),
+                    None => self.try_advance_stripe(),
                 }
             }
-            None => self.try_advance_stripe().transpose(),
+            None => self.try_advance_stripe(),
+        }
+    }
+
+    /// Applies the [`ArrowReaderBuilder::with_row_range`] window to a freshly decoded batch:
+    /// drops any rows still owed to `rows_to_skip`, then truncates once `rows_remaining` rows
+    /// have been handed out. Returns `None` when the whole batch fell inside the skipped
+    /// prefix, meaning the caller should go fetch another one.
+    fn apply_row_window(&mut self, mut batch: RecordBatch) -> Option<RecordBatch> {
+        if self.rows_to_skip > 0 {
+            let skip = self.rows_to_skip.min(batch.num_rows() as u64) as usize;
+            batch = batch.slice(skip, batch.num_rows() - skip);
+            self.rows_to_skip -= skip as u64;
+            if batch.num_rows() == 0 {
+                return None;
+            }
+        }
+        if let Some(remaining) = self.rows_remaining {
+            let keep = (batch.num_rows() as u64).min(remaining) as usize;
+            batch = batch.slice(0, keep);
+            self.rows_remaining = Some(remaining - keep as u64);
+        }
+        Some(batch)
+    }
+}
+
+impl<R: ChunkReader> Iterator for ArrowReader<R> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rows_remaining == Some(0) {
+            return None;
+        }
+        loop {
+            let batch = match self.next_raw_batch() {
+                Ok(Some(batch)) => batch,
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            };
+            if let Some(batch) = self.apply_row_window(batch) {
+                return Some(Ok(batch));
+            }
         }
     }
 }
@@ -187,6 +698,10 @@ pub(crate) struct Cursor<R> {
     pub projected_data_type: RootDataType,
     pub stripe_index: usize,
     pub file_byte_range: Option<Range<usize>>,
+    pub predicates: Vec<ResolvedPredicate>,
+    pub coalesce_gap_threshold: u64,
+    pub coalesce_max_merged_size: u64,
+    pub ignore_writer_tz: bool,
 }
 
 impl<R: ChunkReader> Cursor<R> {
@@ -199,12 +714,94 @@ impl<R: ChunkReader> Cursor<R> {
                     let offset = info.offset() as usize;
                     range.contains(&offset)
                 })
+                .filter(|info| self.stripe_may_match(info))
                 .map(|info| info.to_owned())
                 .collect::<Vec<_>>()
         } else {
-            self.file_metadata.stripe_metadatas().to_vec()
+            self.file_metadata
+                .stripe_metadatas()
+                .iter()
+                .filter(|info| self.stripe_may_match(info))
+                .map(|info| info.to_owned())
+                .collect::<Vec<_>>()
         }
     }
+
+    /// Whether `info`'s column statistics fail to rule out every [`Predicate`] added via
+    /// [`ArrowReaderBuilder::with_predicate`]; a stripe is skipped only once some
+    /// predicate's statistics prove none of its rows can match.
+    fn stripe_may_match(&self, info: &StripeMetadata) -> bool {
+        self.predicates
+            .iter()
+            .all(|predicate| info.can_match(predicate))
+    }
+
+    /// Narrows `stripe` down to the rows its surviving `rowIndexStride` groups cover,
+    /// using whichever predicates' columns have a bloom filter and/or row index statistics
+    /// recorded in this stripe. Returns `None` (keep every row) when there's no
+    /// `rowIndexStride` to map strides to row ranges with, or when no predicate's column
+    /// has either source of per-stride pruning here.
+    fn row_selection_for(&self, stripe: &Stripe) -> Result<Option<RowSelection>> {
+        let Some(row_index_stride) = self.file_metadata.row_index_stride() else {
+            return Ok(None);
+        };
+        let row_index_stride = row_index_stride as usize;
+        let number_of_rows = stripe.number_of_rows();
+        let num_strides = number_of_rows.div_ceil(row_index_stride.max(1));
+
+        let mut mask: Option<Vec<bool>> = None;
+        let mut and_mask = |mask: &mut Option<Vec<bool>>, predicate_mask: Vec<bool>| {
+            *mask = Some(match mask.take() {
+                Some(existing) => existing
+                    .into_iter()
+                    .zip(predicate_mask)
+                    .map(|(a, b)| a && b)
+                    .collect(),
+                None => predicate_mask,
+            });
+        };
+
+        for predicate in &self.predicates {
+            let Some(column_index) = predicate.column_index() else {
+                continue;
+            };
+            let Some(column) = stripe
+                .columns()
+                .iter()
+                .find(|column| column.column_id() as usize == column_index)
+            else {
+                continue;
+            };
+            if let Some(strides) = stripe.bloom_filters(column)? {
+                if strides.len() == num_strides {
+                    let predicate_mask = strides
+                        .iter()
+                        .map(|stride| predicate.stride_may_match_bloom_filter(stride))
+                        .collect();
+                    and_mask(&mut mask, predicate_mask);
+                }
+                // Else: can't line these strides up with row ranges with confidence, so
+                // drop this bloom filter's contribution rather than risk computing the
+                // wrong ranges.
+            }
+            if let Some(statistics) = stripe.row_group_statistics(column)? {
+                if statistics.len() == num_strides {
+                    let predicate_mask = statistics
+                        .iter()
+                        .map(|stats| predicate.stride_may_match_statistics(stats))
+                        .collect();
+                    and_mask(&mut mask, predicate_mask);
+                }
+                // Else: same reasoning as the bloom filter case above.
+            }
+        }
+
+        Ok(
+            mask.map(|mask| {
+                RowSelection::from_stride_mask(&mask, row_index_stride, number_of_rows)
+            }),
+        )
+    }
 }
 
 impl<R: ChunkReader> Iterator for Cursor<R> {
@@ -219,6 +816,9 @@ impl<R: ChunkReader> Iterator for Cursor<R> {
                     &self.file_metadata,
                     &self.projected_data_type.clone(),
                     info,
+                    self.coalesce_gap_threshold,
+                    self.coalesce_max_merged_size,
+                    self.ignore_writer_tz,
                 );
                 self.stripe_index += 1;
                 stripe
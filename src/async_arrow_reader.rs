@@ -15,6 +15,19 @@
 // specific language governing permissions and limitations
 // under the License.
 
+//! An async counterpart to [`ArrowReader`](crate::arrow_reader::ArrowReader) for reading
+//! over an [`AsyncChunkReader`] (e.g. an object store range-request client) instead of a
+//! blocking [`ChunkReader`](crate::reader::ChunkReader). Built the same way --
+//! [`ArrowReaderBuilder::try_new_async`] plus the same `with_batch_size`/`with_projection`/
+//! `with_schema`/`with_file_byte_range` calls as the sync builder -- but finished off with
+//! [`ArrowReaderBuilder::build_async`] to get an [`ArrowStreamReader`] implementing
+//! [`futures::Stream<Item = Result<RecordBatch, ArrowError>>`] instead of `Iterator`.
+//!
+//! [`ArrowReaderBuilder::with_prefetch`] controls how many stripe byte-range fetches run
+//! ahead of the one currently being decoded, so the next stripe's I/O overlaps the current
+//! stripe's CPU-bound decode rather than happening strictly after it.
+
+use std::collections::VecDeque;
 use std::fmt::Formatter;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -26,109 +39,174 @@ use arrow::record_batch::RecordBatch;
 use futures::future::BoxFuture;
 use futures::{ready, Stream};
 use futures_util::FutureExt;
+use tokio::sync::Mutex;
 
 use crate::array_decoder::NaiveStripeDecoder;
 use crate::arrow_reader::Cursor;
+use crate::encoding::integer::DecodeLimits;
+use crate::encoding::timestamp::TimestampOverflowMode;
 use crate::error::Result;
-use crate::reader::metadata::read_metadata_async;
+use crate::reader::metadata::{read_metadata_async, FileMetadata};
 use crate::reader::AsyncChunkReader;
-use crate::stripe::{Stripe, StripeMetadata};
+use crate::schema::{MapKeyMode, RootDataType};
+use crate::stripe::Stripe;
 use crate::ArrowReaderBuilder;
 
 type BoxedDecoder = Box<dyn Iterator<Item = Result<RecordBatch>> + Send>;
 
-enum StreamState<T> {
-    /// At the start of a new row group, or the end of the file stream
-    Init,
+enum StreamState {
+    /// Topping up the in-flight queue and/or waiting on the read at its head
+    Reading,
     /// Decoding a batch
     Decoding(BoxedDecoder),
-    /// Reading data from input
-    Reading(BoxFuture<'static, Result<(StripeFactory<T>, Option<Stripe>)>>),
     /// Error
     Error,
 }
 
-impl<T> std::fmt::Debug for StreamState<T> {
+impl std::fmt::Debug for StreamState {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            StreamState::Init => write!(f, "StreamState::Init"),
+            StreamState::Reading => write!(f, "StreamState::Reading"),
             StreamState::Decoding(_) => write!(f, "StreamState::Decoding"),
-            StreamState::Reading(_) => write!(f, "StreamState::Reading"),
             StreamState::Error => write!(f, "StreamState::Error"),
         }
     }
 }
 
-impl<R: Send> From<Cursor<R>> for StripeFactory<R> {
-    fn from(c: Cursor<R>) -> Self {
+impl<R: Send> StripeFactory<R> {
+    /// Builds a factory for a [`build_async`](ArrowReaderBuilder::build_async) stream, from
+    /// the same [`Cursor`] the sync reader builds from plus the coalescing settings only the
+    /// async path acts on (see [`Stripe::new_async`]).
+    fn new(
+        c: Cursor<R>,
+        coalesce_gap_threshold: u64,
+        coalesce_max_merged_size: u64,
+        ignore_writer_tz: bool,
+    ) -> Self {
+        // `file_byte_range` filtering only depends on static per-stripe offsets, so it can be
+        // resolved once up front rather than re-checked every time a read is queued.
+        let pending_stripes = c
+            .file_metadata
+            .stripe_metadatas()
+            .iter()
+            .enumerate()
+            .skip(c.stripe_index)
+            .filter(|(_, info)| match &c.file_byte_range {
+                Some(range) => range.contains(&(info.offset() as usize)),
+                None => true,
+            })
+            .map(|(index, _)| index)
+            .collect();
         Self {
-            inner: c,
-            is_end: false,
+            reader: Arc::new(Mutex::new(c.reader)),
+            file_metadata: c.file_metadata,
+            projected_data_type: c.projected_data_type,
+            pending_stripes,
+            coalesce_gap_threshold,
+            coalesce_max_merged_size,
+            ignore_writer_tz,
         }
     }
 }
 
+/// Hands out reads for the stripes that still need to be fetched, sharing a single reader
+/// across any number of concurrently in-flight reads.
 struct StripeFactory<R> {
-    inner: Cursor<R>,
-    is_end: bool,
-}
-
-pub struct ArrowStreamReader<R: AsyncChunkReader> {
-    factory: Option<Box<StripeFactory<R>>>,
-    batch_size: usize,
-    schema_ref: SchemaRef,
-    state: StreamState<R>,
+    reader: Arc<Mutex<R>>,
+    file_metadata: Arc<FileMetadata>,
+    projected_data_type: RootDataType,
+    /// Indices, in file order, of stripes still to be queued for reading.
+    pending_stripes: VecDeque<usize>,
+    coalesce_gap_threshold: u64,
+    coalesce_max_merged_size: u64,
+    ignore_writer_tz: bool,
 }
 
 impl<R: AsyncChunkReader + 'static> StripeFactory<R> {
-    async fn read_next_stripe_inner(&mut self, info: &StripeMetadata) -> Result<Stripe> {
-        let inner = &mut self.inner;
-
-        inner.stripe_index += 1;
-
-        Stripe::new_async(
-            &mut inner.reader,
-            &inner.file_metadata,
-            &inner.projected_data_type,
-            info,
+    /// Pops the next pending stripe and returns a future that reads it. Only the cheap
+    /// metadata (an `Arc` clone and a stripe index) is cloned per call; the reader itself is
+    /// shared, so many of these futures can be polled concurrently.
+    fn next_read(&mut self) -> Option<BoxFuture<'static, Result<Stripe>>> {
+        let stripe_index = self.pending_stripes.pop_front()?;
+        let reader = Arc::clone(&self.reader);
+        let file_metadata = Arc::clone(&self.file_metadata);
+        let projected_data_type = self.projected_data_type.clone();
+        let coalesce_gap_threshold = self.coalesce_gap_threshold;
+        let coalesce_max_merged_size = self.coalesce_max_merged_size;
+        let ignore_writer_tz = self.ignore_writer_tz;
+        Some(
+            async move {
+                let info = file_metadata
+                    .stripe_metadatas()
+                    .get(stripe_index)
+                    .expect("pending stripe index is always within the file's stripes")
+                    .clone();
+                let mut reader = reader.lock().await;
+                Stripe::new_async(
+                    &mut *reader,
+                    &file_metadata,
+                    &projected_data_type,
+                    &info,
+                    coalesce_gap_threshold,
+                    coalesce_max_merged_size,
+                    ignore_writer_tz,
+                )
+                .await
+            }
+            .boxed(),
         )
-        .await
     }
+}
 
-    async fn read_next_stripe(mut self) -> Result<(Self, Option<Stripe>)> {
-        let info = self
-            .inner
-            .file_metadata
-            .stripe_metadatas()
-            .get(self.inner.stripe_index)
-            .cloned();
-
-        if let Some(info) = info {
-            if let Some(range) = self.inner.file_byte_range.clone() {
-                let offset = info.offset() as usize;
-                if !range.contains(&offset) {
-                    self.inner.stripe_index += 1;
-                    return Ok((self, None));
-                }
-            }
-            match self.read_next_stripe_inner(&info).await {
-                Ok(stripe) => Ok((self, Some(stripe))),
-                Err(err) => Err(err),
-            }
-        } else {
-            self.is_end = true;
-            Ok((self, None))
-        }
-    }
+pub struct ArrowStreamReader<R: AsyncChunkReader> {
+    factory: StripeFactory<R>,
+    batch_size: usize,
+    schema_ref: SchemaRef,
+    /// See [`ArrowReaderBuilder::native_schema`](crate::arrow_reader::ArrowReaderBuilder::native_schema).
+    native_schema: SchemaRef,
+    decode_parallelism: usize,
+    /// How many stripe reads beyond the one currently being awaited may be in flight at once.
+    prefetch: usize,
+    in_flight: VecDeque<BoxFuture<'static, Result<Stripe>>>,
+    state: StreamState,
+    timestamp_overflow: TimestampOverflowMode,
+    decode_value_limit: DecodeLimits,
+    map_key_mode: MapKeyMode,
 }
 
 impl<R: AsyncChunkReader + 'static> ArrowStreamReader<R> {
-    pub(crate) fn new(cursor: Cursor<R>, batch_size: usize, schema_ref: SchemaRef) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        cursor: Cursor<R>,
+        batch_size: usize,
+        schema_ref: SchemaRef,
+        native_schema: SchemaRef,
+        decode_parallelism: usize,
+        prefetch: usize,
+        timestamp_overflow: TimestampOverflowMode,
+        decode_value_limit: DecodeLimits,
+        map_key_mode: MapKeyMode,
+        coalesce_gap_threshold: u64,
+        coalesce_max_merged_size: u64,
+        ignore_writer_tz: bool,
+    ) -> Self {
         Self {
-            factory: Some(Box::new(cursor.into())),
+            factory: StripeFactory::new(
+                cursor,
+                coalesce_gap_threshold,
+                coalesce_max_merged_size,
+                ignore_writer_tz,
+            ),
             batch_size,
             schema_ref,
-            state: StreamState::Init,
+            native_schema,
+            decode_parallelism,
+            prefetch,
+            in_flight: VecDeque::new(),
+            state: StreamState::Reading,
+            timestamp_overflow,
+            decode_value_limit,
+            map_key_mode,
         }
     }
 
@@ -136,6 +214,17 @@ impl<R: AsyncChunkReader + 'static> ArrowStreamReader<R> {
         self.schema_ref.clone()
     }
 
+    /// Launches additional stripe reads, if any remain, until up to `prefetch` of them are
+    /// queued behind the one at the head of `in_flight`.
+    fn top_up_in_flight(&mut self) {
+        while self.in_flight.len() <= self.prefetch {
+            match self.factory.next_read() {
+                Some(fut) => self.in_flight.push_back(fut),
+                None => break,
+            }
+        }
+    }
+
     fn poll_next_inner(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -150,45 +239,48 @@ impl<R: AsyncChunkReader + 'static> ArrowStreamReader<R> {
                         self.state = StreamState::Error;
                         return Poll::Ready(Some(Err(e)));
                     }
-                    None => self.state = StreamState::Init,
+                    None => self.state = StreamState::Reading,
                 },
-                StreamState::Init => {
-                    let factory = self.factory.take().expect("lost factory");
-                    if factory.is_end {
-                        return Poll::Ready(None);
-                    }
-
-                    let fut = factory.read_next_stripe().boxed();
+                StreamState::Reading => {
+                    self.top_up_in_flight();
 
-                    self.state = StreamState::Reading(fut)
-                }
-                StreamState::Reading(f) => match ready!(f.poll_unpin(cx)) {
-                    Ok((factory, Some(stripe))) => {
-                        self.factory = Some(Box::new(factory));
-                        match NaiveStripeDecoder::new(
-                            stripe,
-                            self.schema_ref.clone(),
-                            self.batch_size,
-                        ) {
-                            Ok(decoder) => {
-                                self.state = StreamState::Decoding(Box::new(decoder));
-                            }
-                            Err(e) => {
-                                self.state = StreamState::Error;
-                                return Poll::Ready(Some(Err(e)));
+                    let Some(next) = self.in_flight.front_mut() else {
+                        // No reads in flight and none pending: the file is exhausted.
+                        return Poll::Ready(None);
+                    };
+                    match ready!(next.poll_unpin(cx)) {
+                        Ok(stripe) => {
+                            self.in_flight.pop_front();
+                            match NaiveStripeDecoder::new(
+                                stripe,
+                                self.schema_ref.clone(),
+                                self.native_schema.clone(),
+                                self.batch_size,
+                                self.decode_parallelism,
+                                self.timestamp_overflow,
+                                self.decode_value_limit,
+                                self.map_key_mode,
+                                // `StripeFactory` doesn't resolve `Predicate`s the way
+                                // `Cursor::row_selection_for` does for the sync reader, so
+                                // there's no stride mask to build a selection from here yet.
+                                None,
+                            ) {
+                                Ok(decoder) => {
+                                    self.state = StreamState::Decoding(Box::new(decoder));
+                                }
+                                Err(e) => {
+                                    self.state = StreamState::Error;
+                                    return Poll::Ready(Some(Err(e)));
+                                }
                             }
                         }
+                        Err(e) => {
+                            self.in_flight.pop_front();
+                            self.state = StreamState::Error;
+                            return Poll::Ready(Some(Err(e)));
+                        }
                     }
-                    Ok((factory, None)) => {
-                        self.factory = Some(Box::new(factory));
-                        // All rows skipped, read next row group
-                        self.state = StreamState::Init;
-                    }
-                    Err(e) => {
-                        self.state = StreamState::Error;
-                        return Poll::Ready(Some(Err(e)));
-                    }
-                },
+                }
                 StreamState::Error => return Poll::Ready(None), // Ends the stream as error happens.
             }
         }
@@ -210,19 +302,83 @@ impl<R: AsyncChunkReader + 'static> ArrowReaderBuilder<R> {
         Ok(Self::new(reader, file_metadata))
     }
 
+    /// Fetches and decodes `column_name`'s `BloomFilter` stream for the stripe at
+    /// `stripe_index`, one [`BloomFilter`](crate::bloom_filter::BloomFilter) per
+    /// `rowIndexStride`-sized row group, reading only that stream's bytes rather than the
+    /// whole stripe. Returns `None` if `column_name` isn't a root-level column, `stripe_index`
+    /// is out of range, or the stripe simply has no bloom filter recorded for that column.
+    ///
+    /// Meant for pruning whole stripes by an equality predicate before committing to read
+    /// any of their data, e.g. alongside [`Self::file_metadata`]'s stripe statistics in a
+    /// query engine integration; [`Self::with_predicate`] instead narrows an
+    /// already-scanned stripe down to its matching `rowIndexStride` groups.
+    pub async fn read_stripe_bloom_filter(
+        &mut self,
+        stripe_index: usize,
+        column_name: &str,
+    ) -> Result<Option<Vec<crate::bloom_filter::BloomFilter>>> {
+        let Some(column_id) = self
+            .file_metadata
+            .root_data_type()
+            .children()
+            .iter()
+            .find(|col| col.name() == column_name)
+            .map(|col| col.data_type().column_index() as u32)
+        else {
+            return Ok(None);
+        };
+        let Some(info) = self
+            .file_metadata
+            .stripe_metadatas()
+            .get(stripe_index)
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        let file_metadata = Arc::clone(&self.file_metadata);
+        crate::stripe::read_bloom_filter_async(
+            &mut self.reader,
+            &file_metadata,
+            &info,
+            column_id,
+        )
+        .await
+    }
+
     pub fn build_async(self) -> ArrowStreamReader<R> {
+        let schema_ref = self.schema();
+        let native_schema = self.native_schema();
         let projected_data_type = self
-            .file_metadata()
+            .file_metadata
             .root_data_type()
             .project(&self.projection);
-        let schema_ref = self.schema();
+        let predicates = self
+            .predicates
+            .iter()
+            .filter_map(|predicate| predicate.resolve(self.file_metadata.root_data_type()))
+            .collect();
         let cursor = Cursor {
             reader: self.reader,
             file_metadata: self.file_metadata,
             projected_data_type,
             stripe_index: 0,
             file_byte_range: self.file_byte_range,
+            predicates,
+            ignore_writer_tz: self.ignore_writer_tz,
         };
-        ArrowStreamReader::new(cursor, self.batch_size, schema_ref)
+        ArrowStreamReader::new(
+            cursor,
+            self.batch_size,
+            schema_ref,
+            native_schema,
+            self.decode_parallelism,
+            self.prefetch,
+            self.timestamp_overflow,
+            self.decode_value_limit,
+            self.map_key_mode,
+            self.coalesce_gap_threshold,
+            self.coalesce_max_merged_size,
+            self.ignore_writer_tz,
+        )
     }
 }
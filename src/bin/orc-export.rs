@@ -18,7 +18,18 @@
 use std::{fs::File, io, path::PathBuf};
 
 use anyhow::Result;
-use arrow::{array::RecordBatch, csv, datatypes::DataType, error::ArrowError, json};
+use arrow::{
+    array::RecordBatch,
+    csv,
+    datatypes::{DataType, TimeUnit},
+    error::ArrowError,
+    ipc::{
+        self,
+        writer::{FileWriter, IpcWriteOptions},
+    },
+    json,
+    record_batch::RecordBatchReader,
+};
 use clap::{Parser, ValueEnum};
 use json::writer::{JsonFormat, LineDelimited};
 use orc_rust::{projection::ProjectionMask, reader::metadata::read_metadata, ArrowReaderBuilder};
@@ -41,6 +52,9 @@ struct Cli {
     /// export only provided columns. Comma separated list
     #[arg(short, long, value_delimiter = ',')]
     columns: Option<Vec<String>>,
+    /// Buffer-level compression for Arrow IPC output. Ignored for other formats
+    #[arg(value_enum, long, default_value_t = IpcCompression::None)]
+    compression: IpcCompression,
 }
 
 #[derive(Clone, Debug, PartialEq, ValueEnum)]
@@ -49,12 +63,34 @@ enum FileFormat {
     Csv,
     /// Output data in json format
     Json,
+    /// Output data in Arrow IPC (Feather V2) format. Unlike csv/json, this losslessly
+    /// preserves Binary, Decimal, and nested columns
+    Arrow,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+enum IpcCompression {
+    #[default]
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl From<IpcCompression> for Option<ipc::CompressionType> {
+    fn from(value: IpcCompression) -> Self {
+        match value {
+            IpcCompression::None => None,
+            IpcCompression::Lz4 => Some(ipc::CompressionType::LZ4_FRAME),
+            IpcCompression::Zstd => Some(ipc::CompressionType::ZSTD),
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
 enum OutputWriter<W: io::Write, F: JsonFormat> {
     Csv(csv::Writer<W>),
     Json(json::Writer<W, F>),
+    Arrow(FileWriter<W>),
 }
 
 impl<W, F> OutputWriter<W, F>
@@ -66,6 +102,7 @@ where
         match self {
             OutputWriter::Csv(w) => w.write(batch),
             OutputWriter::Json(w) => w.write(batch),
+            OutputWriter::Arrow(w) => w.write(batch),
         }
     }
 
@@ -73,6 +110,7 @@ where
         match self {
             OutputWriter::Csv(_) => Ok(()),
             OutputWriter::Json(w) => w.finish(),
+            OutputWriter::Arrow(w) => w.finish(),
         }
     }
 }
@@ -84,25 +122,29 @@ fn main() -> Result<()> {
     let mut f = File::open(&cli.file)?;
     let metadata = read_metadata(&mut f)?;
 
-    // Select columns which should be exported (Binary and Decimal are not supported)
+    // Select columns which should be exported (csv/json can't represent Binary, and json
+    // can't represent Decimal, so those are dropped unless exporting to Arrow)
     let cols: Vec<usize> = metadata
         .root_data_type()
         .children()
         .iter()
         .enumerate()
         // TODO: handle nested types
-        .filter(|(_, nc)| match nc.data_type().to_arrow_data_type() {
-            DataType::Binary => false,
-            DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
-                matches!(cli.format, FileFormat::Csv)
-            }
-            _ => {
-                if let Some(cols) = &cli.columns {
+        .filter(|(_, nc)| {
+            let type_supported = match nc.data_type().to_arrow_data_type(TimeUnit::Nanosecond, None)
+            {
+                DataType::Binary => matches!(cli.format, FileFormat::Arrow),
+                DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+                    matches!(cli.format, FileFormat::Csv | FileFormat::Arrow)
+                }
+                _ => true,
+            };
+            type_supported
+                && if let Some(cols) = &cli.columns {
                     cols.iter().any(|c| nc.name().eq(c))
                 } else {
                     true
                 }
-            }
         })
         .map(|(i, _)| i)
         .collect();
@@ -123,7 +165,18 @@ fn main() -> Result<()> {
         FileFormat::Json => {
             OutputWriter::Json(json::WriterBuilder::new().build::<_, LineDelimited>(writer))
         }
-        _ => OutputWriter::Csv(csv::WriterBuilder::new().with_header(true).build(writer)),
+        FileFormat::Arrow => {
+            let options =
+                IpcWriteOptions::default().try_with_compression(cli.compression.into())?;
+            OutputWriter::Arrow(FileWriter::try_new_with_options(
+                writer,
+                &reader.schema(),
+                options,
+            )?)
+        }
+        FileFormat::Csv => {
+            OutputWriter::Csv(csv::WriterBuilder::new().with_header(true).build(writer))
+        }
     };
 
     // Convert data
@@ -59,6 +59,12 @@ fn main() -> Result<(), Box<dyn Error>> {
                 &metadata,
                 metadata.root_data_type(),
                 stripe_metadata,
+                // This is a one-off CLI dump rather than a latency-sensitive scan, so there's
+                // nothing to gain from coalescing; a 0 max merged size keeps every stream its
+                // own read regardless of gap_threshold.
+                0,
+                0,
+                false,
             )?;
             println!("stripe index: {i}");
             println!("number of rows: {}", stripe.number_of_rows());
@@ -0,0 +1,358 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reads the per-row-index-stride bloom filters ORC optionally stores alongside a
+//! stripe's `RowIndex` stream, so an equality or `IN` [`Predicate`](crate::predicate::Predicate)
+//! can test "could this stride contain this value?" without decoding any column data.
+
+use crate::predicate::PredicateValue;
+use crate::proto;
+
+/// The seed ORC's writers use for the `BloomFilterUtf8`/`BloomFilter` encodings. Matches
+/// `org.apache.orc.util.Murmur3.DEFAULT_SEED` so hashes computed here line up with what a
+/// real ORC writer stored.
+const ORC_BLOOM_FILTER_SEED: u64 = 104729;
+
+/// One stride's worth of an ORC bloom filter: a bit array tested via `k` independent hash
+/// positions derived from a single 64-bit Murmur3 hash, per the `BloomFilter` description in
+/// the ORC format spec.
+///
+/// Public so callers outside this crate (e.g. a query engine integration pruning whole
+/// stripes ahead of any I/O) can hold the decoded filters and test values against them; see
+/// [`ArrowReaderBuilder::read_stripe_bloom_filter`](crate::arrow_reader::ArrowReaderBuilder)
+/// for how to obtain one.
+#[derive(Debug)]
+pub struct BloomFilter {
+    bitset: Vec<u64>,
+    num_hash_functions: u32,
+}
+
+impl BloomFilter {
+    fn num_bits(&self) -> u64 {
+        self.bitset.len() as u64 * 64
+    }
+
+    fn get_bit(&self, index: u64) -> bool {
+        let word = self.bitset[(index / 64) as usize];
+        word & (1 << (index % 64)) != 0
+    }
+
+    /// `false` only if `value` is proven absent from this stride; `true` otherwise
+    /// (including false positives, which bloom filters inherently allow).
+    pub fn may_contain(&self, value: &PredicateValue) -> bool {
+        if self.num_bits() == 0 {
+            // No bits recorded (e.g. an empty stride): can't prove anything.
+            return true;
+        }
+        let hash = hash_predicate_value(value);
+        let h1 = hash as i64 as i32 as i64; // low 32 bits, sign-extended like ORC's Java impl
+        let h2 = (hash >> 32) as i32 as i64;
+        let num_bits = self.num_bits() as i64;
+        for i in 1..=self.num_hash_functions as i64 {
+            let mut combined_hash = h1.wrapping_add(i.wrapping_mul(h2));
+            if combined_hash < 0 {
+                combined_hash = !combined_hash;
+            }
+            if !self.get_bit((combined_hash % num_bits) as u64) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl From<&proto::BloomFilter> for BloomFilter {
+    fn from(value: &proto::BloomFilter) -> Self {
+        Self {
+            bitset: value.bitset.clone(),
+            num_hash_functions: value.num_hash_functions(),
+        }
+    }
+}
+
+/// Decodes every stride's bloom filter recorded for a single column's `BloomFilter` stream.
+pub(crate) fn decode_bloom_filters(index: &proto::BloomFilterIndex) -> Vec<BloomFilter> {
+    index.bloom_filter.iter().map(BloomFilter::from).collect()
+}
+
+/// Hashes a predicate value the same way an ORC writer would before setting/testing bits,
+/// matching [`Self::may_contain`]'s expectations for each [`PredicateValue`] variant.
+fn hash_predicate_value(value: &PredicateValue) -> u64 {
+    match value {
+        PredicateValue::Integer(v) => murmur3_x64_128(&v.to_le_bytes(), ORC_BLOOM_FILTER_SEED).0,
+        PredicateValue::Float(v) => {
+            murmur3_x64_128(&v.to_bits().to_le_bytes(), ORC_BLOOM_FILTER_SEED).0
+        }
+        PredicateValue::String(v) => murmur3_x64_128(v.as_bytes(), ORC_BLOOM_FILTER_SEED).0,
+        PredicateValue::Date(v) => {
+            murmur3_x64_128(&(*v as i64).to_le_bytes(), ORC_BLOOM_FILTER_SEED).0
+        }
+        PredicateValue::Timestamp(v) => murmur3_x64_128(&v.to_le_bytes(), ORC_BLOOM_FILTER_SEED).0,
+        PredicateValue::Boolean(v) => {
+            murmur3_x64_128(&[*v as u8], ORC_BLOOM_FILTER_SEED).0
+        }
+    }
+}
+
+/// The 128-bit x64 variant of MurmurHash3, returning `(h1, h2)`. ORC only ever uses `h1`
+/// (see [`hash_predicate_value`]), but both halves are computed together since the
+/// algorithm mixes them throughout.
+fn murmur3_x64_128(data: &[u8], seed: u64) -> (u64, u64) {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    let mut h1 = seed;
+    let mut h2 = seed;
+
+    let nblocks = data.len() / 16;
+    for block in data[..nblocks * 16].chunks_exact(16) {
+        let mut k1 = u64::from_le_bytes(block[0..8].try_into().unwrap());
+        let mut k2 = u64::from_le_bytes(block[8..16].try_into().unwrap());
+
+        k1 = k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+        h1 ^= k1;
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dce729);
+
+        k2 = k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+        h2 ^= k2;
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x38495ab5);
+    }
+
+    let tail = &data[nblocks * 16..];
+    let mut k1: u64 = 0;
+    let mut k2: u64 = 0;
+    if tail.len() > 8 {
+        for (i, &byte) in tail[8..].iter().enumerate().rev() {
+            k2 ^= (byte as u64) << (8 * i);
+        }
+        k2 = k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+        h2 ^= k2;
+    }
+    if !tail.is_empty() {
+        let head_len = tail.len().min(8);
+        for (i, &byte) in tail[..head_len].iter().enumerate().rev() {
+            k1 ^= (byte as u64) << (8 * i);
+        }
+        k1 = k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+        h1 ^= k1;
+    }
+
+    h1 ^= data.len() as u64;
+    h2 ^= data.len() as u64;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix64(h1);
+    h2 = fmix64(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+
+    (h1, h2)
+}
+
+/// Opts a subset of a stripe's columns into [`BloomFilter`] index streams when writing an ORC
+/// file, so a reader's equality/`IN` predicate can later test "could this stripe contain this
+/// value?" without decoding column data (see [`BloomFilter::may_contain`]). Off by default --
+/// passed to [`ArrowWriterBuilder::with_bloom_filters`](crate::arrow_writer::ArrowWriterBuilder::with_bloom_filters).
+///
+/// Only integer and UTF-8 string columns have a raw byte representation to hash (ORC hashes
+/// UTF-8 bytes for strings and 8-byte little-endian for integers); naming any other column is
+/// silently a no-op, same as naming a column that doesn't exist.
+///
+/// This writer doesn't yet chunk a stripe into row-index strides (see the row-index-stride
+/// note on [`ArrowWriterBuilder::try_build`](crate::arrow_writer::ArrowWriterBuilder::try_build)),
+/// so this builds one bloom filter per column per *stripe* rather than per stride.
+#[derive(Debug, Clone)]
+pub struct BloomFilterConfig {
+    columns: std::collections::HashSet<String>,
+    false_positive_probability: f64,
+    expected_num_entries: usize,
+}
+
+impl BloomFilterConfig {
+    /// Build a bloom filter for exactly the named columns.
+    pub fn new(columns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            false_positive_probability: 0.05,
+            // Matches a stock ORC writer's default row-index-stride row count, which is what
+            // each bloom filter would otherwise be sized for.
+            expected_num_entries: 10_000,
+        }
+    }
+
+    /// Target false positive probability for each bloom filter. Default `0.05`.
+    pub fn with_false_positive_probability(mut self, false_positive_probability: f64) -> Self {
+        self.false_positive_probability = false_positive_probability;
+        self
+    }
+
+    /// Expected number of distinct entries per bloom filter, used to size its bitset.
+    /// Exceeding this just degrades the false positive rate -- it never causes a false
+    /// negative. Default `10_000`.
+    pub fn with_expected_num_entries(mut self, expected_num_entries: usize) -> Self {
+        self.expected_num_entries = expected_num_entries;
+        self
+    }
+
+    pub(crate) fn is_enabled_for(&self, column_name: &str) -> bool {
+        self.columns.contains(column_name)
+    }
+
+    pub(crate) fn new_builder(&self) -> BloomFilterBuilder {
+        BloomFilterBuilder::new(self.expected_num_entries, self.false_positive_probability)
+    }
+}
+
+/// Accumulates a single column's bloom filter across the values encoded into a stripe so far,
+/// mirroring the "take the buffered state, leave it ready for the next stripe" pattern
+/// [`ColumnStatisticsBuilder`](crate::writer::statistics::ColumnStatisticsBuilder) already uses.
+#[derive(Debug, Clone)]
+pub(crate) struct BloomFilterBuilder {
+    bitset: Vec<u64>,
+    num_hash_functions: u32,
+}
+
+impl BloomFilterBuilder {
+    /// `m = ceil(-n*ln(p)/(ln2)^2)` rounded up to a multiple of 64 bits, and
+    /// `k = max(1, round((m/n)*ln2))`, per the ORC bloom filter spec.
+    fn new(expected_num_entries: usize, false_positive_probability: f64) -> Self {
+        let n = expected_num_entries.max(1) as f64;
+        let m = -n * false_positive_probability.ln() / std::f64::consts::LN_2.powi(2);
+        let num_bits = ((m / 64.0).ceil() as u64 * 64).max(64);
+        let num_hash_functions =
+            (((num_bits as f64 / n) * std::f64::consts::LN_2).round() as u32).max(1);
+        Self {
+            bitset: vec![0u64; (num_bits / 64) as usize],
+            num_hash_functions,
+        }
+    }
+
+    fn num_bits(&self) -> u64 {
+        self.bitset.len() as u64 * 64
+    }
+
+    fn set_bit(&mut self, index: u64) {
+        self.bitset[(index / 64) as usize] |= 1 << (index % 64);
+    }
+
+    /// Hashes `bytes` the same way [`BloomFilter::may_contain`] will later test it, and sets
+    /// each of this filter's `k` bit positions for it.
+    pub(crate) fn add_bytes(&mut self, bytes: &[u8]) {
+        let hash = murmur3_x64_128(bytes, ORC_BLOOM_FILTER_SEED).0;
+        let h1 = hash as i64 as i32 as i64; // low 32 bits, sign-extended like ORC's Java impl
+        let h2 = (hash >> 32) as i32 as i64;
+        let num_bits = self.num_bits() as i64;
+        for i in 1..=self.num_hash_functions as i64 {
+            let mut combined_hash = h1.wrapping_add(i.wrapping_mul(h2));
+            if combined_hash < 0 {
+                combined_hash = !combined_hash;
+            }
+            self.set_bit((combined_hash % num_bits) as u64);
+        }
+    }
+
+    /// Snapshots the accumulated bitset into a [`proto::BloomFilter`] and resets back to an
+    /// empty filter of the same size, ready for the next stripe.
+    pub(crate) fn finish(&mut self) -> proto::BloomFilter {
+        let bitset = std::mem::replace(&mut self.bitset, vec![0u64; self.bitset.len()]);
+        proto::BloomFilter {
+            num_hash_functions: Some(self.num_hash_functions),
+            bitset,
+        }
+    }
+}
+
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^= k >> 33;
+    k
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn murmur3_empty_input_matches_known_vector() {
+        // MurmurHash3_x64_128("", seed=0) is widely published as all-zero output.
+        assert_eq!(murmur3_x64_128(b"", 0), (0, 0));
+    }
+
+    #[test]
+    fn murmur3_is_deterministic_and_seed_sensitive() {
+        let a = murmur3_x64_128(b"hello", ORC_BLOOM_FILTER_SEED);
+        let b = murmur3_x64_128(b"hello", ORC_BLOOM_FILTER_SEED);
+        assert_eq!(a, b);
+
+        let different_seed = murmur3_x64_128(b"hello", 0);
+        assert_ne!(a, different_seed);
+    }
+
+    #[test]
+    fn bloom_filter_never_false_negative_for_set_bit() {
+        // A single-word, single-hash-function filter with every bit set must report every
+        // value as possibly present: bloom filters may false-positive but never false-negative.
+        let filter = BloomFilter {
+            bitset: vec![u64::MAX],
+            num_hash_functions: 4,
+        };
+        assert!(filter.may_contain(&PredicateValue::Integer(42)));
+        assert!(filter.may_contain(&PredicateValue::String("anything".to_owned())));
+    }
+
+    #[test]
+    fn bloom_filter_rejects_when_any_bit_unset() {
+        let filter = BloomFilter {
+            bitset: vec![0],
+            num_hash_functions: 4,
+        };
+        assert!(!filter.may_contain(&PredicateValue::Integer(42)));
+    }
+
+    #[test]
+    fn bloom_filter_builder_roundtrips_through_proto_into_reader() {
+        let mut builder = BloomFilterBuilder::new(1_000, 0.05);
+        builder.add_bytes(&42i64.to_le_bytes());
+        builder.add_bytes(b"hello");
+
+        let filter = BloomFilter::from(&builder.finish());
+        assert!(filter.may_contain(&PredicateValue::Integer(42)));
+        assert!(filter.may_contain(&PredicateValue::String("hello".to_owned())));
+        assert!(!filter.may_contain(&PredicateValue::String("goodbye".to_owned())));
+    }
+
+    #[test]
+    fn bloom_filter_builder_finish_resets_to_empty() {
+        let mut builder = BloomFilterBuilder::new(1_000, 0.05);
+        builder.add_bytes(b"hello");
+        let _ = builder.finish();
+
+        let filter = BloomFilter::from(&builder.finish());
+        assert!(!filter.may_contain(&PredicateValue::String("hello".to_owned())));
+    }
+}
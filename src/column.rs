@@ -17,6 +17,7 @@
 
 use std::sync::Arc;
 
+use arrow::datatypes::DataType as ArrowDataType;
 use bytes::Bytes;
 use snafu::ResultExt;
 
@@ -48,6 +49,24 @@ impl Column {
             .unwrap_or_default() as usize
     }
 
+    /// Picks the narrowest unsigned integer Arrow key type that can index every entry of
+    /// this stripe's dictionary, wrapping `value_type` as `Dictionary(key_type, value_type)`.
+    /// Only meaningful for dictionary-encoded columns, where a 64-bit key would otherwise
+    /// waste 4-8x the memory most ORC dictionaries (which tend to be small) actually need.
+    pub fn arrow_data_type(&self, value_type: ArrowDataType) -> ArrowDataType {
+        let dictionary_size = self.dictionary_size();
+        let key_type = if dictionary_size <= u8::MAX as usize + 1 {
+            ArrowDataType::UInt8
+        } else if dictionary_size <= u16::MAX as usize + 1 {
+            ArrowDataType::UInt16
+        } else if dictionary_size <= u32::MAX as usize + 1 {
+            ArrowDataType::UInt32
+        } else {
+            ArrowDataType::UInt64
+        };
+        ArrowDataType::Dictionary(Box::new(key_type), Box::new(value_type))
+    }
+
     pub fn encoding(&self) -> ColumnEncoding {
         let column = self.data_type.column_index();
         self.footer.columns[column].clone()
@@ -0,0 +1,144 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A push-based decoder that performs no IO of its own, mirroring the design of
+//! `arrow-csv`'s `Decoder`: the caller feeds in bytes from whatever source it likes (a
+//! blocking `Read`, an async `Stream`, an `object_store` response, ...) and drains
+//! completed [`RecordBatch`]es back out, rather than this crate owning the read loop.
+
+use arrow::record_batch::RecordBatch;
+use bytes::{Bytes, BytesMut};
+use snafu::ResultExt;
+
+use crate::arrow_reader::{ArrowReader, ArrowReaderBuilder};
+use crate::error::{ArrowSnafu, Result};
+use crate::projection::ProjectionMask;
+
+/// Builds a [`Decoder`], mirroring the options [`ArrowReaderBuilder`] exposes for the
+/// IO-coupled readers.
+pub struct DecoderBuilder {
+    batch_size: usize,
+    projection: ProjectionMask,
+}
+
+impl Default for DecoderBuilder {
+    fn default() -> Self {
+        Self {
+            batch_size: 8192,
+            projection: ProjectionMask::all(),
+        }
+    }
+}
+
+impl DecoderBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn with_projection(mut self, projection: ProjectionMask) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub fn build(self) -> Decoder {
+        Decoder {
+            buffer: BytesMut::new(),
+            batch_size: self.batch_size,
+            projection: self.projection,
+            state: DecoderState::Buffering,
+        }
+    }
+}
+
+enum DecoderState {
+    /// Still accumulating bytes; [`Decoder::finish`] hasn't been called yet.
+    Buffering,
+    /// [`Decoder::finish`] parsed the footer; remaining batches to hand out via
+    /// [`Decoder::flush`].
+    Decoding(Box<ArrowReader<Bytes>>),
+    /// [`Decoder::flush`] has drained every batch.
+    Done,
+}
+
+/// Decodes a complete in-memory ORC file, fed to it incrementally via [`Decoder::decode`],
+/// into a sequence of [`RecordBatch`]es drained via [`Decoder::flush`].
+///
+/// Unlike a text format such as CSV, an ORC file can only be parsed starting from its
+/// *last* bytes (the postscript and footer point back at everything else), so this can't
+/// decode incrementally the way a push-based CSV decoder can: [`Decoder::decode`] just
+/// buffers whatever it's given, and decoding only actually starts once [`Decoder::finish`]
+/// is called to signal that every byte of the file has been pushed in. From there,
+/// [`Decoder::flush`] drives the same stripe-at-a-time decode loop [`ArrowReader`] uses,
+/// one batch at a time, so callers that already drive IO themselves (an async runtime, an
+/// `object_store`-backed source) don't need a blocking [`std::io::Read`] in the loop.
+pub struct Decoder {
+    buffer: BytesMut,
+    batch_size: usize,
+    projection: ProjectionMask,
+    state: DecoderState,
+}
+
+impl Decoder {
+    /// Buffers `buf` in full and returns `buf.len()`, matching the push-decoder convention
+    /// of reporting bytes consumed. Always buffers the whole slice: unlike a prefix-parsed
+    /// format, no byte of an ORC file can be decoded until [`Decoder::finish`] is called, so
+    /// there's never a partial amount to report.
+    ///
+    /// A no-op once [`Decoder::finish`] has already been called.
+    pub fn decode(&mut self, buf: &[u8]) -> Result<usize> {
+        if matches!(self.state, DecoderState::Buffering) {
+            self.buffer.extend_from_slice(buf);
+        }
+        Ok(buf.len())
+    }
+
+    /// Signals that every byte of the file has been passed to [`Decoder::decode`], parsing
+    /// the footer and readying every stripe to be handed out through [`Decoder::flush`].
+    ///
+    /// A no-op if already called.
+    pub fn finish(&mut self) -> Result<()> {
+        if matches!(self.state, DecoderState::Buffering) {
+            let bytes = std::mem::take(&mut self.buffer).freeze();
+            let reader = ArrowReaderBuilder::try_new(bytes)?
+                .with_batch_size(self.batch_size)
+                .with_projection(self.projection.clone())
+                .build();
+            self.state = DecoderState::Decoding(Box::new(reader));
+        }
+        Ok(())
+    }
+
+    /// Returns the next decoded [`RecordBatch`], or `None` if either [`Decoder::finish`]
+    /// hasn't been called yet or every stripe has already been drained.
+    pub fn flush(&mut self) -> Result<Option<RecordBatch>> {
+        match &mut self.state {
+            DecoderState::Decoding(reader) => match reader.next() {
+                Some(batch) => Ok(Some(batch.context(ArrowSnafu)?)),
+                None => {
+                    self.state = DecoderState::Done;
+                    Ok(None)
+                }
+            },
+            DecoderState::Buffering | DecoderState::Done => Ok(None),
+        }
+    }
+}
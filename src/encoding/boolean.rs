@@ -15,8 +15,6 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::io::Read;
-
 use arrow::{
     array::BooleanBufferBuilder,
     buffer::{BooleanBuffer, NullBuffer},
@@ -27,6 +25,7 @@ use crate::{error::Result, memory::EstimateMemory};
 
 use super::{
     byte::{ByteRleDecoder, ByteRleEncoder},
+    io_nostd::Read,
     PrimitiveValueDecoder, PrimitiveValueEncoder,
 };
 
@@ -114,15 +113,20 @@ impl BooleanEncoder {
 
     /// Produce ORC present stream bytes and reset internal builder.
     pub fn finish(&mut self) -> Bytes {
-        // TODO: don't throw away allocation?
         let bb = self.builder.finish();
         // We use BooleanBufferBuilder so offset is 0
-        let bytes = bb.values();
-        // Reverse bits as ORC stores from MSB
-        let bytes = bytes.iter().map(|b| b.reverse_bits()).collect::<Vec<_>>();
-        for &b in bytes.as_slice() {
-            self.byte_encoder.write_one(b as i8);
+        // Reverse bits as ORC stores from MSB, feeding the byte encoder directly
+        // instead of collecting into an intermediate Vec first.
+        for &b in bb.values() {
+            self.byte_encoder.write_one(b.reverse_bits() as i8);
         }
+        // `self.builder` itself can't be reset to reuse its allocation here:
+        // `BooleanBufferBuilder::finish` hands back an arrow-rs `BooleanBuffer`
+        // by value with no API to reclaim its backing `Buffer` as a builder
+        // again, so a fresh `BooleanBufferBuilder` every stripe is unavoidable
+        // short of vendoring that type. `self.byte_encoder.take_inner()` below
+        // is already allocation-free (it freezes its `BytesMut` into `Bytes`
+        // via `std::mem::take`, not a copy).
         self.byte_encoder.take_inner()
     }
 }
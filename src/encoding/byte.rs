@@ -15,17 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use bytemuck::must_cast_slice;
+use bytemuck::{must_cast_slice, must_cast_slice_mut};
 use bytes::{BufMut, BytesMut};
-use snafu::ResultExt;
+use snafu::OptionExt;
 
 use crate::{
-    error::{IoSnafu, Result},
+    error::{OutOfSpecSnafu, Result},
     memory::EstimateMemory,
 };
-use std::io::Read;
 
-use super::{rle::GenericRle, util::read_u8, PrimitiveValueEncoder};
+use super::{io_nostd::Read, rle::GenericRle, util::read_u8, PrimitiveValueEncoder};
 
 const MAX_LITERAL_LENGTH: usize = 128;
 const MIN_REPEAT_LENGTH: usize = 3;
@@ -123,6 +122,38 @@ impl ByteRleEncoder {
         }
     }
 
+    /// Like repeatedly calling [`Self::process_value`] over `values`, but bulk-advances
+    /// through a stretch already in Run mode using [`leading_run_len`]'s word-at-a-time
+    /// scan instead of a branch per byte -- the common case for null-heavy or
+    /// low-cardinality `Int8` columns, where a single run can span thousands of bytes.
+    ///
+    /// Only an established run is fast-pathed: entering a new run still takes
+    /// [`MIN_REPEAT_LENGTH`] scalar steps through [`Self::process_value`] (the Literal-mode
+    /// bookkeeping it does -- `tail_run_length`, flushing a broken literal prefix -- isn't
+    /// worth duplicating for a fast path aimed at long runs), and a byte that breaks a run
+    /// always falls back to one scalar step so run-breaking/literal-mode transitions stay
+    /// exactly as [`Self::process_value`] would have produced them.
+    fn process_slice(&mut self, mut values: &[u8]) {
+        while let Some(&first) = values.first() {
+            if let Some(run_value) = self.run_value {
+                if first == run_value {
+                    let matched = leading_run_len(values, run_value);
+                    let room = MAX_REPEAT_LENGTH - self.num_literals;
+                    let consumed = matched.min(room);
+                    self.num_literals += consumed;
+                    if self.num_literals == MAX_REPEAT_LENGTH {
+                        write_run(&mut self.writer, run_value, MAX_REPEAT_LENGTH);
+                        self.clear_state();
+                    }
+                    values = &values[consumed..];
+                    continue;
+                }
+            }
+            self.process_value(first);
+            values = &values[1..];
+        }
+    }
+
     fn clear_state(&mut self) {
         self.run_value = None;
         self.tail_run_length = 0;
@@ -165,12 +196,42 @@ impl PrimitiveValueEncoder<i8> for ByteRleEncoder {
         self.process_value(value as u8);
     }
 
+    fn write_slice(&mut self, values: &[i8]) {
+        self.process_slice(must_cast_slice(values));
+    }
+
     fn take_inner(&mut self) -> bytes::Bytes {
         self.flush();
         std::mem::take(&mut self.writer).into()
     }
 }
 
+/// Length of the run of `target` bytes at the start of `bytes`, found a word at a time
+/// rather than one byte at a time: each 8-byte lane is XORed against `target` broadcast
+/// to every byte, and the first non-matching byte (if any) falls out of
+/// [`u64::trailing_zeros`] on that XOR, since a matching byte XORs to zero while a
+/// differing one leaves at least one bit set. Falls back to a per-byte scan for the
+/// `< 8`-byte remainder.
+fn leading_run_len(bytes: &[u8], target: u8) -> usize {
+    let broadcast = u64::from_le_bytes([target; 8]);
+    let mut chunks = bytes.chunks_exact(8);
+    let mut len = 0;
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().expect("chunk of size 8"));
+        let diff = word ^ broadcast;
+        if diff == 0 {
+            len += 8;
+            continue;
+        }
+        return len + (diff.trailing_zeros() / 8) as usize;
+    }
+    len + chunks
+        .remainder()
+        .iter()
+        .take_while(|&&b| b == target)
+        .count()
+}
+
 fn write_run(writer: &mut BytesMut, value: u8, run_length: usize) {
     debug_assert!(
         (MIN_REPEAT_LENGTH..=MAX_REPEAT_LENGTH).contains(&run_length),
@@ -236,12 +297,243 @@ impl<R: Read> GenericRle<i8> for ByteRleDecoder<R> {
             // List of values
             let length = 0x100 - header as usize;
             self.leftovers.resize(length, 0);
-            self.reader
-                .read_exact(&mut self.leftovers)
-                .context(IoSnafu)?;
+            self.reader.read_exact(&mut self.leftovers)?;
         }
         Ok(())
     }
+
+    fn decode_batch_into(&mut self, out: &mut [i8]) -> Result<usize> {
+        let mut written = 0;
+        while written < out.len() {
+            let header = read_u8(&mut self.reader)?;
+            if header < 0x80 {
+                // Run of repeated value
+                let length = header as usize + MIN_REPEAT_LENGTH;
+                let value = read_u8(&mut self.reader)? as i8;
+                if written + length > out.len() {
+                    // Doesn't fit: fall back to the internal buffer for this run, so
+                    // the leftover is picked up via `available`/`advance` as usual.
+                    self.index = 0;
+                    self.leftovers.clear();
+                    self.leftovers.extend(std::iter::repeat(value as u8).take(length));
+                    break;
+                }
+                out[written..written + length].fill(value);
+                written += length;
+            } else {
+                // List of values
+                let length = 0x100 - header as usize;
+                if written + length > out.len() {
+                    self.index = 0;
+                    self.leftovers.clear();
+                    self.leftovers.resize(length, 0);
+                    self.reader.read_exact(&mut self.leftovers)?;
+                    break;
+                }
+                let dest = must_cast_slice_mut(&mut out[written..written + length]);
+                self.reader.read_exact(dest)?;
+                written += length;
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// Either a Run's single repeated value, expanded into an owned buffer, or a Literals
+/// sequence borrowed straight out of the source slice -- see [`SliceByteRleDecoder`].
+enum SliceChunk<'a> {
+    Borrowed(&'a [i8]),
+    Owned(Vec<i8>),
+}
+
+impl SliceChunk<'_> {
+    fn as_slice(&self) -> &[i8] {
+        match self {
+            SliceChunk::Borrowed(values) => values,
+            SliceChunk::Owned(values) => values,
+        }
+    }
+}
+
+/// A [`ByteRleDecoder`] counterpart for callers that already hold the whole stream as an
+/// in-memory `&[u8]` (e.g. a stripe buffer read up front rather than streamed), avoiding
+/// `ByteRleDecoder`'s per-run copy into its `leftovers: Vec<u8>`: a Literals run is instead
+/// handed back as a `&'a [i8]` sub-slice of the original buffer (via `bytemuck`), with no
+/// allocation at all. Only a Run sequence still allocates, since its repeated value has no
+/// standalone slice in the source to borrow -- that one value is expanded into an owned
+/// `Vec<i8>` same as `ByteRleDecoder` would.
+///
+/// Implements [`GenericRle<i8>`] rather than reading through [`super::io_nostd::Read`], so it
+/// plugs into the same blanket [`PrimitiveValueDecoder<i8>`](super::PrimitiveValueDecoder)
+/// impl that [`ByteRleDecoder`] uses; callers choose between the two based on whether they
+/// hold a borrowed slice or something that only implements `Read`.
+pub struct SliceByteRleDecoder<'a> {
+    data: &'a [u8],
+    /// Offset into `data` of the next unread byte.
+    pos: usize,
+    current: SliceChunk<'a>,
+    /// Index into `current` to make it act like a queue, as with `ByteRleDecoder::index`.
+    index: usize,
+}
+
+impl<'a> SliceByteRleDecoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            current: SliceChunk::Borrowed(&[]),
+            index: 0,
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        let byte = *self.data.get(self.pos).context(OutOfSpecSnafu {
+            msg: "unexpected end of input",
+        })?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_slice(&mut self, length: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(length)
+            .filter(|&end| end <= self.data.len())
+            .context(OutOfSpecSnafu {
+                msg: "unexpected end of input",
+            })?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+}
+
+impl GenericRle<i8> for SliceByteRleDecoder<'_> {
+    fn advance(&mut self, n: usize) {
+        self.index += n
+    }
+
+    fn available(&self) -> &[i8] {
+        &self.current.as_slice()[self.index..]
+    }
+
+    fn decode_batch(&mut self) -> Result<()> {
+        self.index = 0;
+
+        let header = self.read_u8()?;
+        self.current = if header < 0x80 {
+            // Run of repeated value
+            let length = header as usize + MIN_REPEAT_LENGTH;
+            let value = self.read_u8()? as i8;
+            SliceChunk::Owned(vec![value; length])
+        } else {
+            // List of values, borrowed directly out of `data`
+            let length = 0x100 - header as usize;
+            SliceChunk::Borrowed(must_cast_slice(self.read_slice(length)?))
+        };
+        Ok(())
+    }
+}
+
+/// Where a resumable [`PushByteRleDecoder`] is partway through decoding a header/run/literal
+/// record, carried over between [`PushByteRleDecoder::push`] calls when input runs out
+/// mid-record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PushState {
+    NeedHeader,
+    NeedRunValue { length: usize },
+    NeedLiterals { remaining: usize },
+}
+
+/// A [`ByteRleDecoder`] counterpart for input that arrives in pieces -- a non-blocking
+/// socket, a stripe streamed over the wire, a decompressor that only ever yields partial
+/// buffers -- where `ByteRleDecoder::decode_batch`'s `read_exact` would simply fail on a
+/// short read. [`Self::push`] instead consumes however many bytes are available, decodes
+/// as many complete values as that allows into [`Self::leftovers`], and carries over any
+/// partial run/literal-sequence state to the next call rather than erroring.
+///
+/// Feeding the same bytes to [`Self::push`] split into however many pieces they happen to
+/// arrive in is guaranteed to produce the same [`Self::leftovers`] as feeding them all in
+/// one call.
+pub struct PushByteRleDecoder {
+    state: PushState,
+    /// Every value decoded so far across all [`Self::push`] calls. Callers drain this
+    /// however suits them (e.g. `std::mem::take`); `push` only ever appends to it.
+    pub leftovers: Vec<i8>,
+}
+
+impl Default for PushByteRleDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PushByteRleDecoder {
+    pub fn new() -> Self {
+        Self {
+            state: PushState::NeedHeader,
+            leftovers: Vec::new(),
+        }
+    }
+
+    /// Feed the next chunk of input. Decodes as many complete records as `input` covers,
+    /// appending their values to [`Self::leftovers`], and stores the residual state (e.g.
+    /// a Run seen but not yet its value, or a partial Literals run) if `input` ends
+    /// mid-record.
+    pub fn push(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            match self.state {
+                PushState::NeedHeader => {
+                    let header = input[0];
+                    input = &input[1..];
+                    self.state = if header < 0x80 {
+                        // Run of repeated value
+                        PushState::NeedRunValue {
+                            length: header as usize + MIN_REPEAT_LENGTH,
+                        }
+                    } else {
+                        // List of values
+                        PushState::NeedLiterals {
+                            remaining: 0x100 - header as usize,
+                        }
+                    };
+                }
+                PushState::NeedRunValue { length } => {
+                    let value = input[0] as i8;
+                    input = &input[1..];
+                    self.leftovers.extend(std::iter::repeat(value).take(length));
+                    self.state = PushState::NeedHeader;
+                }
+                PushState::NeedLiterals { remaining } => {
+                    let take = remaining.min(input.len());
+                    self.leftovers
+                        .extend_from_slice(must_cast_slice(&input[..take]));
+                    input = &input[take..];
+                    self.state = if take == remaining {
+                        PushState::NeedHeader
+                    } else {
+                        PushState::NeedLiterals {
+                            remaining: remaining - take,
+                        }
+                    };
+                }
+            }
+        }
+    }
+
+    /// Call once no more input is coming. Errors only if input ended mid-record (a
+    /// truncated stream) -- a clean `NeedHeader` boundary is always a valid place to stop,
+    /// since every fully-decoded value was already appended to [`Self::leftovers`] by
+    /// [`Self::push`] as it went.
+    pub fn finish(&self) -> Result<()> {
+        match self.state {
+            PushState::NeedHeader => Ok(()),
+            _ => OutOfSpecSnafu {
+                msg: "byte RLE input ended mid-record",
+            }
+            .fail(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -336,5 +628,122 @@ mod tests {
             let out = roundtrip_byte_rle_helper(&values).unwrap();
             prop_assert_eq!(out, values);
         }
+
+        #[test]
+        fn write_slice_matches_write_one(
+            sequences in prop::collection::vec(byte_sequence_strategy(), 1..200)
+        ) {
+            // write_slice's bulk Run-mode fast path must produce byte-identical output
+            // to feeding the same values through write_one one at a time.
+            let values = generate_bytes_from_sequences(sequences);
+
+            let mut scalar = ByteRleEncoder::new();
+            for &value in &values {
+                scalar.write_one(value);
+            }
+            scalar.flush();
+
+            let mut bulk = ByteRleEncoder::new();
+            bulk.write_slice(&values);
+            bulk.flush();
+
+            prop_assert_eq!(scalar.take_inner(), bulk.take_inner());
+        }
+
+        #[test]
+        fn push_decoder_matches_whole_buffer_decode(
+            sequences in prop::collection::vec(byte_sequence_strategy(), 1..200),
+            // Arbitrary split points (mod encoded length) to chop the encoded bytes up.
+            split_points in prop::collection::vec(0..usize::MAX, 0..20),
+        ) {
+            let values = generate_bytes_from_sequences(sequences);
+
+            let mut writer = ByteRleEncoder::new();
+            writer.write_slice(&values);
+            writer.flush();
+            let encoded = writer.take_inner();
+
+            let mut splits: Vec<usize> = split_points
+                .into_iter()
+                .map(|s| if encoded.is_empty() { 0 } else { s % encoded.len() })
+                .collect();
+            splits.sort_unstable();
+            splits.dedup();
+
+            let mut decoder = PushByteRleDecoder::new();
+            let mut offset = 0;
+            for split in splits {
+                decoder.push(&encoded[offset..split]);
+                offset = split;
+            }
+            decoder.push(&encoded[offset..]);
+            decoder.finish().unwrap();
+
+            prop_assert_eq!(decoder.leftovers, values);
+        }
+
+        #[test]
+        fn slice_decoder_matches_reader_decoder(
+            sequences in prop::collection::vec(byte_sequence_strategy(), 1..200)
+        ) {
+            let values = generate_bytes_from_sequences(sequences);
+
+            let mut writer = ByteRleEncoder::new();
+            writer.write_slice(&values);
+            writer.flush();
+            let encoded = writer.take_inner();
+
+            let mut slice_decoder = SliceByteRleDecoder::new(&encoded);
+            let mut actual = vec![0; values.len()];
+            slice_decoder.decode(&mut actual).unwrap();
+
+            prop_assert_eq!(actual, values);
+        }
+    }
+
+    #[test]
+    fn slice_decoder_borrows_literals_without_copying() {
+        // A single Literals run: the decoded slice should point into `encoded` itself
+        // rather than an internal allocation.
+        let values: Vec<i8> = (0..10).collect();
+        let mut writer = ByteRleEncoder::new();
+        writer.write_slice(&values);
+        writer.flush();
+        let encoded = writer.take_inner();
+
+        let mut decoder = SliceByteRleDecoder::new(&encoded);
+        decoder.decode_batch().unwrap();
+        let available = decoder.available();
+        assert_eq!(available, values.as_slice());
+        // The returned slice is backed by `encoded`'s own bytes, not a copy.
+        let literal_bytes = must_cast_slice::<i8, u8>(available);
+        assert_eq!(
+            literal_bytes.as_ptr(),
+            encoded[encoded.len() - literal_bytes.len()..].as_ptr()
+        );
+    }
+
+    #[test]
+    fn slice_decoder_errors_on_truncated_input() {
+        // A Run header with no value byte following it.
+        let mut decoder = SliceByteRleDecoder::new(&[0x01]);
+        assert!(decoder.decode_batch().is_err());
+
+        // A Literals header promising more bytes than were ever supplied.
+        let mut decoder = SliceByteRleDecoder::new(&[0xfe, 0x44]);
+        assert!(decoder.decode_batch().is_err());
+    }
+
+    #[test]
+    fn push_decoder_errors_on_truncated_input() {
+        // A Run header with no value byte following it.
+        let mut decoder = PushByteRleDecoder::new();
+        decoder.push(&[0x01]);
+        assert!(decoder.finish().is_err());
+
+        // A Literals header promising more bytes than were ever supplied.
+        let mut decoder = PushByteRleDecoder::new();
+        decoder.push(&[0xfe, 0x44]);
+        assert!(decoder.finish().is_err());
     }
 }
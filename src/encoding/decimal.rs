@@ -17,11 +17,18 @@
 
 use std::io::Read;
 
-use crate::error::Result;
+use arrow::datatypes::i256;
+use bytes::{Bytes, BytesMut};
+use snafu::ResultExt;
+
+use crate::{
+    error::{IoSnafu, OutOfSpecSnafu, Result},
+    memory::EstimateMemory,
+};
 
 use super::{
-    integer::{read_varint_zigzagged, SignedEncoding},
-    PrimitiveValueDecoder,
+    integer::{read_varint_zigzagged, write_varint_zigzagged, SignedEncoding},
+    PrimitiveValueDecoder, PrimitiveValueEncoder,
 };
 
 /// Read stream of zigzag encoded varints as i128 (unbound).
@@ -43,3 +50,106 @@ impl<R: Read> PrimitiveValueDecoder<i128> for UnboundedVarintStreamDecoder<R> {
         Ok(())
     }
 }
+
+/// Write stream of zigzag encoded varints from i128 (unbound).
+///
+/// Counterpart to [`UnboundedVarintStreamDecoder`], for decimal columns whose precision
+/// fits within an `i128` (at most 38 digits).
+pub struct UnboundedVarintStreamEncoder {
+    data: BytesMut,
+}
+
+impl EstimateMemory for UnboundedVarintStreamEncoder {
+    fn estimate_memory_size(&self) -> usize {
+        self.data.len()
+    }
+}
+
+impl PrimitiveValueEncoder<i128> for UnboundedVarintStreamEncoder {
+    fn new() -> Self {
+        Self {
+            data: BytesMut::new(),
+        }
+    }
+
+    fn write_one(&mut self, value: i128) {
+        write_varint_zigzagged::<i128, SignedEncoding>(&mut self.data, value);
+    }
+
+    fn take_inner(&mut self) -> Bytes {
+        std::mem::take(&mut self.data).into()
+    }
+}
+
+/// Read stream of zigzag encoded varints as i256 (unbound).
+///
+/// `i256` doesn't implement the `PrimInt`/`CheckedShl` bounds that
+/// [`VarintSerde`](super::integer::VarintSerde) requires, so this can't go through the
+/// generic [`read_varint_zigzagged`]; instead it accumulates the same base-128 zigzag
+/// format directly against `i256` arithmetic. Used for decimal columns whose precision
+/// exceeds what fits in an `i128` (i.e. more than 38 digits).
+pub struct UnboundedVarintStreamDecoder256<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> UnboundedVarintStreamDecoder256<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    fn read_one(&mut self) -> Result<i256> {
+        // i256 is 256 bits wide, and a base-128 varint needs ceil(256 / 7) = 37 bytes
+        // at most; an unterminated run of continuation bytes beyond that is corrupt.
+        const MAX_VARINT_BYTES: usize = 37;
+
+        let mut magnitude = i256::ZERO;
+        let mut multiplier = i256::ONE;
+        let mut byte_count = 0;
+        loop {
+            let mut byte = [0u8; 1];
+            self.reader.read_exact(&mut byte).context(IoSnafu)?;
+            let byte = byte[0];
+            byte_count += 1;
+            let is_last_byte = byte & 0x80 == 0;
+            let without_continuation_bit = (byte & 0x7F) as i128;
+            // Same overlong-encoding rejection as the generic varint reader.
+            if is_last_byte && without_continuation_bit == 0 && byte_count > 1 {
+                return OutOfSpecSnafu {
+                    msg: "varint is not in canonical form (overlong encoding)",
+                }
+                .fail();
+            }
+            magnitude = magnitude + i256::from_i128(without_continuation_bit) * multiplier;
+            if is_last_byte {
+                break;
+            }
+            if byte_count >= MAX_VARINT_BYTES {
+                return OutOfSpecSnafu {
+                    msg: "varint exceeds maximum length for its integer width",
+                }
+                .fail();
+            }
+            multiplier = multiplier * i256::from_i128(128);
+        }
+        Ok(zigzag_decode_i256(magnitude))
+    }
+}
+
+impl<R: Read> PrimitiveValueDecoder<i256> for UnboundedVarintStreamDecoder256<R> {
+    fn decode(&mut self, out: &mut [i256]) -> Result<()> {
+        for x in out.iter_mut() {
+            *x = self.read_one()?;
+        }
+        Ok(())
+    }
+}
+
+fn zigzag_decode_i256(n: i256) -> i256 {
+    let two = i256::from_i128(2);
+    let half = n / two;
+    if n - half * two == i256::ZERO {
+        half
+    } else {
+        -(half + i256::ONE)
+    }
+}
@@ -19,12 +19,8 @@ use std::marker::PhantomData;
 
 use bytemuck::{must_cast_slice, must_cast_slice_mut};
 use bytes::{Bytes, BytesMut};
-use snafu::ResultExt;
 
-use crate::{
-    error::{IoSnafu, Result},
-    memory::EstimateMemory,
-};
+use crate::{encoding::io_nostd::Read, error::Result, memory::EstimateMemory};
 
 use super::{PrimitiveValueDecoder, PrimitiveValueEncoder};
 
@@ -36,12 +32,23 @@ pub trait Float:
 impl Float for f32 {}
 impl Float for f64 {}
 
-pub struct FloatDecoder<F: Float, R: std::io::Read> {
+/// No run encoding to decode -- floats/doubles are stored as their raw IEEE 754 bytes -- so
+/// this is the only [`PrimitiveValueDecoder`] that doesn't need `std::io::Read` itself, just
+/// a source of bytes. It decodes against the crate-local [`Read`] rather than
+/// [`std::io::Read`] directly so it can run unchanged in a future `alloc`-only build of this
+/// module (see the no_std note on [`io_nostd`](crate::encoding::io_nostd)); a blanket impl
+/// keeps every current `std::io::Read` caller (files, decompressors) working unchanged.
+///
+/// A whole batch is decoded in one call: [`Self::decode`] reads the entire run's bytes
+/// straight into the caller's output slice (reinterpreted in place via [`bytemuck`]) rather
+/// than issuing a read per value, so there's no per-value syscall/virtual-call overhead to
+/// begin with.
+pub struct FloatDecoder<F: Float, R: Read> {
     reader: R,
     phantom: std::marker::PhantomData<F>,
 }
 
-impl<F: Float, R: std::io::Read> FloatDecoder<F, R> {
+impl<F: Float, R: Read> FloatDecoder<F, R> {
     pub fn new(reader: R) -> Self {
         Self {
             reader,
@@ -50,11 +57,15 @@ impl<F: Float, R: std::io::Read> FloatDecoder<F, R> {
     }
 }
 
-impl<F: Float, R: std::io::Read> PrimitiveValueDecoder<F> for FloatDecoder<F, R> {
+impl<F: Float, R: Read> PrimitiveValueDecoder<F> for FloatDecoder<F, R> {
     fn decode(&mut self, out: &mut [F]) -> Result<()> {
+        // A single `read_exact` over the whole output slice, rather than one `read` per
+        // value: it loops internally until the buffer is full (erroring on EOF instead of
+        // silently accepting a short read), and is the bulk decode path for this type --
+        // there's no per-run encoding to take advantage of, so reinterpreting one
+        // contiguous byte buffer is already the fastest decode this format allows.
         let bytes = must_cast_slice_mut::<F, u8>(out);
-        self.reader.read_exact(bytes).context(IoSnafu)?;
-        Ok(())
+        self.reader.read_exact(bytes)
     }
 }
 
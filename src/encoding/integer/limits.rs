@@ -0,0 +1,132 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Resource limits to guard integer RLE decoding against malformed or hostile streams
+//! that are otherwise well-formed enough to keep producing values -- not a substitute
+//! for the decoders' own handling of a stream that can't produce any more at all (see
+//! [`DecodeLimits`]'s doc comment).
+
+use snafu::ensure;
+
+use crate::{
+    encoding::PrimitiveValueDecoder,
+    error::{OutOfSpecSnafu, Result},
+};
+
+/// Caps the total number of values a [`PrimitiveValueDecoder`] is willing to decode over
+/// its lifetime, so that a corrupt or adversarial stream (e.g. one that replays an
+/// absurdly long sequence of maximal-length runs) cannot be used to force unbounded work
+/// across many `decode()` calls.
+///
+/// This only checks the running total *between* `decode()` calls, so it does nothing for
+/// a stream that can't make progress *within* a single call -- that's a decoder-internal
+/// correctness bug (the blanket `PrimitiveValueDecoder::decode` impl for
+/// [`GenericRle`](crate::encoding::rle::GenericRle) types is guarded separately there, by
+/// erroring as soon as a `decode_batch()` call fails to refill its buffer) rather than
+/// something a values-decoded budget can catch.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeLimits {
+    max_values: usize,
+}
+
+impl DecodeLimits {
+    /// No limit is applied; equivalent to the decoder's previous unbounded behaviour.
+    pub const UNLIMITED: Self = Self {
+        max_values: usize::MAX,
+    };
+
+    pub fn new(max_values: usize) -> Self {
+        Self { max_values }
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// Wraps a [`PrimitiveValueDecoder`], rejecting further decoding once more than
+/// [`DecodeLimits::max_values`] values have been requested in total.
+pub struct LimitedDecoder<V, D> {
+    inner: D,
+    limits: DecodeLimits,
+    decoded_so_far: usize,
+    _marker: std::marker::PhantomData<V>,
+}
+
+impl<V, D: PrimitiveValueDecoder<V>> LimitedDecoder<V, D> {
+    pub fn new(inner: D, limits: DecodeLimits) -> Self {
+        Self {
+            inner,
+            limits,
+            decoded_so_far: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<V, D: PrimitiveValueDecoder<V>> PrimitiveValueDecoder<V> for LimitedDecoder<V, D> {
+    fn decode(&mut self, out: &mut [V]) -> Result<()> {
+        self.decoded_so_far = self.decoded_so_far.saturating_add(out.len());
+        ensure!(
+            self.decoded_so_far <= self.limits.max_values,
+            OutOfSpecSnafu {
+                msg: "decode limit exceeded: stream requested more values than the configured maximum",
+            }
+        );
+        self.inner.decode(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DummyDecoder;
+
+    impl PrimitiveValueDecoder<i32> for DummyDecoder {
+        fn decode(&mut self, out: &mut [i32]) -> Result<()> {
+            out.fill(0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn allows_decoding_within_limit() {
+        let mut decoder = LimitedDecoder::new(DummyDecoder, DecodeLimits::new(10));
+        let mut out = vec![0; 5];
+        decoder.decode(&mut out).unwrap();
+        decoder.decode(&mut out).unwrap();
+    }
+
+    #[test]
+    fn rejects_decoding_beyond_limit() {
+        let mut decoder = LimitedDecoder::new(DummyDecoder, DecodeLimits::new(10));
+        let mut out = vec![0; 5];
+        decoder.decode(&mut out).unwrap();
+        decoder.decode(&mut out).unwrap();
+        assert!(decoder.decode(&mut out).is_err());
+    }
+
+    #[test]
+    fn unlimited_never_rejects() {
+        let mut decoder = LimitedDecoder::new(DummyDecoder, DecodeLimits::UNLIMITED);
+        let mut out = vec![0; 1_000_000];
+        decoder.decode(&mut out).unwrap();
+    }
+}
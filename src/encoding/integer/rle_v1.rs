@@ -15,22 +15,38 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! Handling decoding of Integer Run Length Encoded V1 data in ORC files
+//! Handling decoding of Integer Run Length Encoded V1 data in ORC files.
+//!
+//! ORC's newer writers default to [RLE v2](super::rle_v2), but older files (and some
+//! column kinds even from newer writers) still use this original, simpler V1 format, so
+//! both [`RleV1Decoder`] and [`RleV1Encoder`] are kept around and selected by a stream's
+//! [`column_encoding::Kind`](crate::proto::column_encoding::Kind) (`Direct`/`Dictionary`
+//! pick V1; the `V2` suffix picks V2), matching how the C++ writer gained equivalent
+//! V1/V2 selection in ORC-343.
 
-use std::{io::Read, marker::PhantomData};
+use std::marker::PhantomData;
 
+use bytes::{BufMut, BytesMut};
 use snafu::OptionExt;
 
 use crate::{
     encoding::{
+        io_nostd::Read,
         rle::GenericRle,
         util::{read_u8, try_read_u8},
+        PrimitiveValueEncoder,
     },
     error::{OutOfSpecSnafu, Result},
+    memory::EstimateMemory,
 };
 
-use super::{util::read_varint_zigzagged, EncodingSign, NInt};
+use super::{
+    util::{read_varint_zigzagged, write_varint_zigzagged},
+    EncodingSign, NInt,
+};
 
+const MAX_LITERAL_LENGTH: usize = 128;
+const MIN_RUN_LENGTH: usize = 3;
 const MAX_RUN_LENGTH: usize = 130;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -60,6 +76,10 @@ impl EncodingType {
 }
 
 /// Decodes a stream of Integer Run Length Encoded version 1 bytes.
+///
+/// Overrides [`GenericRle::decode_batch_into`] below to decode a `Run`/`Literals` sequence
+/// straight into the caller's output slice whenever it fits, the same vectorized-bulk-decode
+/// treatment the V2 reader already got.
 pub struct RleV1Decoder<N: NInt, R: Read, S: EncodingSign> {
     reader: R,
     decoded_ints: Vec<N>,
@@ -122,6 +142,43 @@ fn read_run<N: NInt, R: Read, S: EncodingSign>(
     Ok(())
 }
 
+fn read_literals_into<N: NInt, R: Read, S: EncodingSign>(
+    reader: &mut R,
+    out: &mut [N],
+) -> Result<()> {
+    for slot in out.iter_mut() {
+        *slot = read_varint_zigzagged::<_, _, S>(reader)?;
+    }
+    Ok(())
+}
+
+fn read_run_into<N: NInt, R: Read, S: EncodingSign>(
+    reader: &mut R,
+    out: &mut [N],
+    delta: i8,
+) -> Result<()> {
+    let mut base = read_varint_zigzagged::<_, _, S>(reader)?;
+    out[0] = base;
+    if delta < 0 {
+        let delta = N::from_u8(delta.unsigned_abs());
+        for slot in &mut out[1..] {
+            base = base.checked_sub(&delta).context(OutOfSpecSnafu {
+                msg: "over/underflow when decoding patched base integer",
+            })?;
+            *slot = base;
+        }
+    } else {
+        let delta = N::from_u8(delta as u8);
+        for slot in &mut out[1..] {
+            base = base.checked_add(&delta).context(OutOfSpecSnafu {
+                msg: "over/underflow when decoding patched base integer",
+            })?;
+            *slot = base;
+        }
+    }
+    Ok(())
+}
+
 impl<N: NInt, R: Read, S: EncodingSign> GenericRle<N> for RleV1Decoder<N, R, S> {
     fn advance(&mut self, n: usize) {
         self.current_head += n;
@@ -145,12 +202,197 @@ impl<N: NInt, R: Read, S: EncodingSign> GenericRle<N> for RleV1Decoder<N, R, S>
             None => Ok(()),
         }
     }
+
+    fn decode_batch_into(&mut self, out: &mut [N]) -> Result<usize> {
+        let mut written = 0;
+        while written < out.len() {
+            match EncodingType::from_header(&mut self.reader)? {
+                Some(EncodingType::Literals { length }) => {
+                    if written + length > out.len() {
+                        // Doesn't fit: fall back to the internal buffer for this run, so
+                        // the leftover is picked up via `available`/`advance` as usual.
+                        self.current_head = 0;
+                        self.decoded_ints.clear();
+                        read_literals::<_, _, S>(&mut self.reader, &mut self.decoded_ints, length)?;
+                        break;
+                    }
+                    let out = &mut out[written..written + length];
+                    read_literals_into::<_, _, S>(&mut self.reader, out)?;
+                    written += length;
+                }
+                Some(EncodingType::Run { length, delta }) => {
+                    if written + length > out.len() {
+                        self.current_head = 0;
+                        self.decoded_ints.clear();
+                        read_run::<_, _, S>(
+                            &mut self.reader,
+                            &mut self.decoded_ints,
+                            length,
+                            delta,
+                        )?;
+                        break;
+                    }
+                    let out = &mut out[written..written + length];
+                    read_run_into::<_, _, S>(&mut self.reader, out, delta)?;
+                    written += length;
+                }
+                None => break,
+            }
+        }
+        Ok(written)
+    }
+}
+
+fn write_literals<N: NInt, S: EncodingSign>(writer: &mut BytesMut, literals: &[N]) {
+    debug_assert!(
+        !literals.is_empty() && literals.len() <= MAX_LITERAL_LENGTH,
+        "literals length must be in [1, 128]"
+    );
+    // Literals are encoded with a negative length byte, in [-128, -1].
+    // Use `wrapping_neg` since `literals.len() == MAX_LITERAL_LENGTH` (128) maps to
+    // -128i8, which cannot be represented by negating a positive i8.
+    let header = (literals.len() as u8).wrapping_neg();
+    writer.put_slice(&[header]);
+    for &value in literals {
+        write_varint_zigzagged::<_, S>(writer, value);
+    }
+}
+
+fn write_run<N: NInt, S: EncodingSign>(writer: &mut BytesMut, base: N, delta: i8, length: usize) {
+    debug_assert!(
+        (MIN_RUN_LENGTH..=MAX_RUN_LENGTH).contains(&length),
+        "run length must be in [3, 130]"
+    );
+    // Header encodes length - 3 in [0, 127].
+    let header = (length - MIN_RUN_LENGTH) as u8;
+    writer.put_slice(&[header, delta as u8]);
+    write_varint_zigzagged::<_, S>(writer, base);
+}
+
+/// Encodes a stream of Integer Run Length Encoded version 1 bytes, the write-side
+/// counterpart to [`RleV1Decoder`].
+///
+/// Buffers up to [`MAX_LITERAL_LENGTH`] values while tracking the length of the
+/// trailing chain of values that share a constant, byte-sized delta. Once that chain
+/// reaches [`MIN_RUN_LENGTH`], any preceding buffered values are flushed as Literals
+/// and we switch into a Run, continuing to extend it while the delta holds, up to
+/// [`MAX_RUN_LENGTH`].
+pub struct RleV1Encoder<N: NInt, S: EncodingSign> {
+    writer: BytesMut,
+    /// Buffered values not yet written out. While in a Run, only holds the run's
+    /// values (starting with its base).
+    literals: Vec<N>,
+    /// Set once `literals` has switched to holding an in-progress Run.
+    run_delta: Option<i8>,
+    /// Length of the trailing chain of buffered values (while not in a Run) that are
+    /// related by `tail_delta`. Reset to 1 whenever the chain is broken.
+    tail_run_length: usize,
+    /// Delta between consecutive values in the trailing chain, valid once
+    /// `tail_run_length >= 2`.
+    tail_delta: i8,
+    phantom: PhantomData<S>,
+}
+
+impl<N: NInt, S: EncodingSign> RleV1Encoder<N, S> {
+    fn process_value(&mut self, value: N) {
+        if let Some(delta) = self.run_delta {
+            let last = *self.literals.last().expect("run must have a base value");
+            let extends_run = self.literals.len() < MAX_RUN_LENGTH
+                && last.as_i64() + delta as i64 == value.as_i64();
+            if extends_run {
+                self.literals.push(value);
+            } else {
+                self.flush();
+                self.literals.push(value);
+                self.tail_run_length = 1;
+            }
+            return;
+        }
+
+        if let Some(&last) = self.literals.last() {
+            match i8::try_from(value.as_i64() - last.as_i64()) {
+                Ok(delta) if self.tail_run_length >= 2 && delta == self.tail_delta => {
+                    self.tail_run_length += 1;
+                }
+                Ok(delta) => {
+                    self.tail_delta = delta;
+                    self.tail_run_length = 2;
+                }
+                Err(_) => {
+                    self.tail_run_length = 1;
+                }
+            }
+        } else {
+            self.tail_run_length = 1;
+        }
+        self.literals.push(value);
+
+        if self.tail_run_length == MIN_RUN_LENGTH {
+            // Flush any literals preceding the run (everything but its last
+            // MIN_RUN_LENGTH values), then switch into Run mode.
+            let run_start = self.literals.len() - MIN_RUN_LENGTH;
+            if run_start > 0 {
+                let run_values = self.literals.split_off(run_start);
+                write_literals::<_, S>(&mut self.writer, &self.literals);
+                self.literals = run_values;
+            }
+            self.run_delta = Some(self.tail_delta);
+        } else if self.literals.len() == MAX_LITERAL_LENGTH {
+            write_literals::<_, S>(&mut self.writer, &self.literals);
+            self.literals.clear();
+            self.tail_run_length = 0;
+        }
+    }
+
+    /// Flush any buffered values to the writer.
+    fn flush(&mut self) {
+        if self.literals.is_empty() {
+            return;
+        }
+        if let Some(delta) = self.run_delta.take() {
+            let base = self.literals[0];
+            write_run::<_, S>(&mut self.writer, base, delta, self.literals.len());
+        } else {
+            write_literals::<_, S>(&mut self.writer, &self.literals);
+        }
+        self.literals.clear();
+    }
+}
+
+impl<N: NInt, S: EncodingSign> EstimateMemory for RleV1Encoder<N, S> {
+    fn estimate_memory_size(&self) -> usize {
+        self.writer.len() + self.literals.len()
+    }
+}
+
+impl<N: NInt, S: EncodingSign> PrimitiveValueEncoder<N> for RleV1Encoder<N, S> {
+    fn new() -> Self {
+        Self {
+            writer: BytesMut::new(),
+            literals: Vec::with_capacity(MAX_LITERAL_LENGTH),
+            run_delta: None,
+            tail_run_length: 0,
+            tail_delta: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    fn write_one(&mut self, value: N) {
+        self.process_value(value);
+    }
+
+    fn take_inner(&mut self) -> bytes::Bytes {
+        self.flush();
+        std::mem::take(&mut self.writer).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
 
+    use proptest::prelude::*;
+
     use crate::encoding::{integer::UnsignedEncoding, PrimitiveValueDecoder};
 
     use super::*;
@@ -183,4 +425,44 @@ mod tests {
 
         Ok(())
     }
+
+    fn roundtrip_helper(values: &[i64]) -> Result<Vec<i64>> {
+        let mut encoder = RleV1Encoder::<i64, UnsignedEncoding>::new();
+        encoder.write_slice(values);
+        let bytes = encoder.take_inner();
+
+        let mut reader = RleV1Decoder::<i64, _, UnsignedEncoding>::new(Cursor::new(bytes));
+        let mut actual = vec![0; values.len()];
+        reader.decode(&mut actual)?;
+        Ok(actual)
+    }
+
+    #[test]
+    fn test_roundtrip_encoder_basic() -> Result<()> {
+        assert_eq!(roundtrip_helper(&[7; 100])?, vec![7; 100]);
+        assert_eq!(
+            roundtrip_helper(&(1..=100).rev().collect::<Vec<_>>())?,
+            (1..=100).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(roundtrip_helper(&[2, 3, 6, 7, 11])?, vec![2, 3, 6, 7, 11]);
+        // Mix of literals, a run, then more literals.
+        assert_eq!(
+            roundtrip_helper(&[1, 9, 5, 5, 5, 5, 5, 5, 20, 1, 2])?,
+            vec![1, 9, 5, 5, 5, 5, 5, 5, 20, 1, 2]
+        );
+        // Run broken by a delta too large to fit in a byte.
+        assert_eq!(
+            roundtrip_helper(&[0, 1, 2, 3, 1_000_000_000])?,
+            vec![0, 1, 2, 3, 1_000_000_000]
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip_encoder_proptest(values in prop::collection::vec(-10_000i64..10_000, 0..2000)) {
+            let actual = roundtrip_helper(&values)?;
+            prop_assert_eq!(actual, values);
+        }
+    }
 }
@@ -15,8 +15,6 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::io::Read;
-
 use bytes::{BufMut, BytesMut};
 use snafu::OptionExt;
 
@@ -31,6 +29,7 @@ use crate::{
             },
             EncodingSign, SignedEncoding, VarintSerde,
         },
+        io_nostd::Read,
         util::read_u8,
     },
     error::{OrcError, OutOfSpecSnafu, Result},
@@ -41,11 +40,17 @@ use super::NInt;
 /// We use i64 and u64 for delta to make things easier and to avoid edge cases,
 /// as for example for i16, the delta may be too large to represent in an i16.
 // TODO: expand on the above
+///
+/// `wrapping`, when set, accumulates using [`NInt::add_i64_wrapping`]/[`NInt::sub_i64_wrapping`]
+/// instead of erroring on over/underflow, to match Java/C++ ORC writers whose delta
+/// reader silently wraps in two's-complement `long` arithmetic (see
+/// [`RleV2Decoder::with_wrapping_delta`](super::RleV2Decoder::with_wrapping_delta)).
 pub fn read_delta_values<N: NInt, R: Read, S: EncodingSign>(
     reader: &mut R,
     out_ints: &mut Vec<N>,
     deltas: &mut Vec<i64>,
     header: u8,
+    wrapping: bool,
 ) -> Result<()> {
     // Encoding format:
     // 2 bytes header
@@ -73,13 +78,31 @@ pub fn read_delta_values<N: NInt, R: Read, S: EncodingSign>(
 
     // Always signed since can be decreasing sequence
     let delta_base = read_varint_zigzagged::<i64, _, SignedEncoding>(reader)?;
+    let is_increasing = delta_base.is_positive();
     // TODO: does this get inlined?
-    let op: fn(N, i64) -> Option<N> = if delta_base.is_positive() {
-        |acc, delta| acc.add_i64(delta)
+    let op: fn(N, i64) -> Option<N> = match (is_increasing, wrapping) {
+        (true, false) => |acc, delta| acc.add_i64(delta),
+        (true, true) => |acc, delta| Some(acc.add_i64_wrapping(delta)),
+        (false, false) => |acc, delta| acc.sub_i64(delta),
+        (false, true) => |acc, delta| Some(acc.sub_i64_wrapping(delta)),
+    };
+    // `delta_base.abs()` panics (debug) / silently misbehaves (release) for
+    // `i64::MIN`, whose magnitude (2^63) has no positive `i64` representation.
+    // Compute the magnitude as `u64` instead, which is exact for every input.
+    let delta_base_magnitude = delta_base.unsigned_abs();
+    let delta_base: i64 = if wrapping {
+        // Adding/subtracting this bit pattern (mod 2^64) is equivalent to
+        // adding/subtracting the true magnitude, so the reinterpret cast is exact
+        // even when the magnitude itself overflows a positive i64.
+        delta_base_magnitude as i64
     } else {
-        |acc, delta| acc.sub_i64(delta)
+        delta_base_magnitude
+            .try_into()
+            .ok()
+            .context(OutOfSpecSnafu {
+                msg: "delta magnitude overflows i64 and wrapping delta decoding is not enabled",
+            })?
     };
-    let delta_base = delta_base.abs(); // TODO: i64::MIN?
 
     if delta_bit_width == 0 {
         // If width is 0 then all values have fixed delta of delta_base
@@ -181,16 +204,86 @@ fn derive_delta_header(delta_width: usize, run_length: usize) -> [u8; 2] {
     [header1, header2]
 }
 
+/// A single delta run identified by [`plan_delta_runs`], ready to be serialized by
+/// [`write_fixed_delta`] (when `max_delta` is `None`) or [`write_varying_delta`]
+/// (passing `max_delta.unwrap()`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeltaRun<N: NInt> {
+    pub base_value: N,
+    pub first_delta: i64,
+    /// `None` for a fixed-delta run, where every delta equals `first_delta`.
+    /// `Some(max(|d|))` over `subsequent_deltas` for a varying-delta run.
+    pub max_delta: Option<i64>,
+    pub subsequent_deltas: Vec<i64>,
+}
+
+/// Greedily segments an arbitrary value sequence into delta runs, the way a conforming
+/// writer would pack a difference stream into RLE v2 Delta sub-encodings.
+///
+/// First differences `d[i] = values[i] - values[i-1]` are computed, and a run is split
+/// whenever: its length would exceed [`MAX_RUN_LENGTH`] (the reader fixes a single
+/// add/subtract operator and bit width for the whole run), or `d` changes sign (the
+/// reader derives that operator from the sign of the run's first delta alone, so a run
+/// must stay monotonic). Within a monotonic stretch, a run starts out fixed-delta and
+/// stays that way as long as `d[i]` keeps equalling the run's first delta; the first
+/// value that diverges (without reversing direction) converts the rest of the run to
+/// varying-delta, tracked via `max(|d|)` to pick the aligned bit width on write.
+pub fn plan_delta_runs<N: NInt>(values: &[N]) -> Vec<DeltaRun<N>> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start + 1 < values.len() {
+        let base_value = values[start];
+        let first_delta = values[start + 1]
+            .as_i64()
+            .saturating_sub(base_value.as_i64());
+        let sign = first_delta.signum();
+
+        let mut subsequent_deltas = Vec::new();
+        let mut max_delta = first_delta.saturating_abs();
+        let mut is_fixed = true;
+        // Index of the last value folded into the run so far; run length so far is
+        // always `2 + subsequent_deltas.len()` (base value + first delta + these).
+        let mut end = start + 1;
+        while end + 1 < values.len() && 2 + subsequent_deltas.len() < MAX_RUN_LENGTH {
+            let delta = values[end + 1]
+                .as_i64()
+                .saturating_sub(values[end].as_i64());
+            let delta_sign = delta.signum();
+            if sign != 0 && delta_sign != 0 && delta_sign != sign {
+                // Direction reversed; the reader can't represent this within one run.
+                break;
+            }
+            if is_fixed && delta != first_delta {
+                is_fixed = false;
+            }
+            if !is_fixed {
+                max_delta = max_delta.max(delta.saturating_abs());
+            }
+            subsequent_deltas.push(delta);
+            end += 1;
+        }
+
+        runs.push(DeltaRun {
+            base_value,
+            first_delta,
+            max_delta: (!is_fixed).then_some(max_delta),
+            subsequent_deltas,
+        });
+        start = end + 1;
+    }
+    runs
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
 
+    use proptest::prelude::*;
+
     use crate::encoding::integer::UnsignedEncoding;
 
     use super::*;
 
-    // TODO: figure out how to write proptests for these
-
     #[test]
     fn test_fixed_delta_positive() {
         let mut buf = BytesMut::new();
@@ -203,6 +296,7 @@ mod tests {
             &mut out,
             &mut deltas,
             header,
+            false,
         )
         .unwrap();
 
@@ -222,6 +316,7 @@ mod tests {
             &mut out,
             &mut deltas,
             header,
+            false,
         )
         .unwrap();
 
@@ -246,6 +341,7 @@ mod tests {
             &mut out,
             &mut deltas,
             header,
+            false,
         )
         .unwrap();
 
@@ -275,6 +371,7 @@ mod tests {
             &mut out,
             &mut deltas,
             header,
+            false,
         )
         .unwrap();
 
@@ -286,4 +383,296 @@ mod tests {
         }
         assert_eq!(expected, out);
     }
+
+    #[test]
+    fn test_fixed_delta_overflow_errors_without_wrapping() {
+        let mut buf = BytesMut::new();
+        let mut out = vec![];
+        let mut deltas = vec![];
+        write_fixed_delta::<i64, SignedEncoding>(&mut buf, i64::MAX, 1, 0);
+        let header = buf[0];
+        let err = read_delta_values::<i64, _, SignedEncoding>(
+            &mut Cursor::new(&buf[1..]),
+            &mut out,
+            &mut deltas,
+            header,
+            false,
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_fixed_delta_overflow_wraps_when_enabled() {
+        let mut buf = BytesMut::new();
+        let mut out = vec![];
+        let mut deltas = vec![];
+        write_fixed_delta::<i64, SignedEncoding>(&mut buf, i64::MAX, 1, 0);
+        let header = buf[0];
+        read_delta_values::<i64, _, SignedEncoding>(
+            &mut Cursor::new(&buf[1..]),
+            &mut out,
+            &mut deltas,
+            header,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(vec![i64::MAX, i64::MIN], out);
+    }
+
+    #[test]
+    fn plan_delta_runs_too_short_for_a_run() {
+        assert_eq!(plan_delta_runs::<i64>(&[]), vec![]);
+        assert_eq!(plan_delta_runs::<i64>(&[42]), vec![]);
+    }
+
+    #[test]
+    fn plan_delta_runs_single_fixed_run() {
+        let values: Vec<i64> = (0..20).map(|i| i * 10).collect();
+        let runs = plan_delta_runs(&values);
+        assert_eq!(
+            runs,
+            vec![DeltaRun {
+                base_value: 0,
+                first_delta: 10,
+                max_delta: None,
+                subsequent_deltas: vec![10; 18],
+            }]
+        );
+    }
+
+    #[test]
+    fn plan_delta_runs_single_varying_run() {
+        let values = [0i64, 10, 16, 114, 126];
+        let runs = plan_delta_runs(&values);
+        assert_eq!(
+            runs,
+            vec![DeltaRun {
+                base_value: 0,
+                first_delta: 10,
+                max_delta: Some(98),
+                subsequent_deltas: vec![6, 98, 12],
+            }]
+        );
+    }
+
+    #[test]
+    fn plan_delta_runs_splits_on_sign_change() {
+        // Deltas are +10, +10, -5, -10: direction reverses between the 3rd and 4th
+        // values, so the run must break there, leaving just enough of a tail (2
+        // values) to form a second run.
+        let values = [0i64, 10, 20, 15, 5];
+        let runs = plan_delta_runs(&values);
+        assert_eq!(
+            runs,
+            vec![
+                DeltaRun {
+                    base_value: 0,
+                    first_delta: 10,
+                    max_delta: None,
+                    subsequent_deltas: vec![10],
+                },
+                DeltaRun {
+                    base_value: 15,
+                    first_delta: -10,
+                    max_delta: None,
+                    subsequent_deltas: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_delta_runs_splits_at_max_run_length() {
+        let values: Vec<i64> = (0..(MAX_RUN_LENGTH as i64 + 5)).collect();
+        let runs = plan_delta_runs(&values);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].subsequent_deltas.len() + 2, MAX_RUN_LENGTH);
+        assert_eq!(runs[1].subsequent_deltas.len() + 2, 5);
+    }
+
+    #[test]
+    fn plan_delta_runs_roundtrips_through_writers_and_reader() {
+        // Monotonic increasing with varying deltas: plans as a single run.
+        let values = [0i64, 10, 16, 114, 126, 225];
+        let runs = plan_delta_runs(&values);
+        assert_eq!(runs.len(), 1);
+        let run = &runs[0];
+
+        let mut buf = BytesMut::new();
+        match run.max_delta {
+            None => write_fixed_delta::<i64, UnsignedEncoding>(
+                &mut buf,
+                run.base_value,
+                run.first_delta,
+                run.subsequent_deltas.len(),
+            ),
+            Some(max_delta) => write_varying_delta::<i64, UnsignedEncoding>(
+                &mut buf,
+                run.base_value,
+                run.first_delta,
+                max_delta,
+                &run.subsequent_deltas,
+            ),
+        }
+        let header = buf[0];
+        let mut out = vec![];
+        let mut deltas = vec![];
+        read_delta_values::<i64, _, UnsignedEncoding>(
+            &mut Cursor::new(&buf[1..]),
+            &mut out,
+            &mut deltas,
+            header,
+            false,
+        )
+        .unwrap();
+        assert_eq!(out, values);
+    }
+
+    /// Plans `values` into delta runs via [`plan_delta_runs`] and round-trips each one
+    /// through the writers and [`read_delta_values`], returning the concatenation of the
+    /// decoded runs plus how many leading values they covered.
+    ///
+    /// A lone value left over at the end (too short to pair up into a run of its own,
+    /// see [`plan_delta_runs_too_short_for_a_run`]) is left uncovered rather than
+    /// dropped silently, so callers can assert `covered` is `values.len()` or one less.
+    fn roundtrip_via_planned_runs(values: &[i64], wrapping: bool) -> Result<(Vec<i64>, usize)> {
+        let mut out = vec![];
+        let mut deltas = vec![];
+        let mut covered = 0;
+        for run in plan_delta_runs(values) {
+            let mut buf = BytesMut::new();
+            match run.max_delta {
+                None => write_fixed_delta::<i64, SignedEncoding>(
+                    &mut buf,
+                    run.base_value,
+                    run.first_delta,
+                    run.subsequent_deltas.len(),
+                ),
+                Some(max_delta) => write_varying_delta::<i64, SignedEncoding>(
+                    &mut buf,
+                    run.base_value,
+                    run.first_delta,
+                    max_delta,
+                    &run.subsequent_deltas,
+                ),
+            }
+            let header = buf[0];
+            read_delta_values::<i64, _, SignedEncoding>(
+                &mut Cursor::new(&buf[1..]),
+                &mut out,
+                &mut deltas,
+                header,
+                wrapping,
+            )?;
+            covered += 2 + run.subsequent_deltas.len();
+        }
+        Ok((out, covered))
+    }
+
+    /// Values near `i64::MIN`/`MAX`, where delta accumulation is most likely to
+    /// over/underflow, mixed in with fully arbitrary values.
+    fn extreme_leaning_i64() -> impl Strategy<Value = i64> {
+        prop_oneof![
+            1 => Just(i64::MIN),
+            1 => Just(i64::MIN + 1),
+            1 => Just(i64::MAX),
+            1 => Just(i64::MAX - 1),
+            6 => any::<i64>(),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn plan_delta_runs_roundtrip_arbitrary(values in prop::collection::vec(any::<i64>(), 0..2_000)) {
+            let (out, covered) = roundtrip_via_planned_runs(&values, true)?;
+            prop_assert!(covered == values.len() || covered + 1 == values.len());
+            prop_assert_eq!(out, &values[..covered]);
+        }
+
+        // Strictly monotonic ramp: first differences never change sign, so this plans
+        // as one or more fixed-delta runs, only splitting on `MAX_RUN_LENGTH`.
+        #[test]
+        fn plan_delta_runs_roundtrip_monotonic_ramp(
+            start in any::<i64>(),
+            step in -1_000i64..=1_000,
+            len in 0usize..2_000,
+        ) {
+            let values: Vec<i64> = (0..len as i64)
+                .map(|i| start.wrapping_add(step.wrapping_mul(i)))
+                .collect();
+            let (out, covered) = roundtrip_via_planned_runs(&values, true)?;
+            prop_assert!(covered == values.len() || covered + 1 == values.len());
+            prop_assert_eq!(out, &values[..covered]);
+        }
+
+        // Alternating +magnitude/-magnitude deltas: first differences flip sign on every
+        // step, forcing a fresh run every 2 values.
+        #[test]
+        fn plan_delta_runs_roundtrip_sign_alternating(
+            base in any::<i64>(),
+            magnitudes in prop::collection::vec(1i64..1_000, 0..2_000),
+        ) {
+            let mut values = Vec::with_capacity(magnitudes.len() + 1);
+            let mut v = base;
+            values.push(v);
+            for (i, m) in magnitudes.iter().enumerate() {
+                v = if i % 2 == 0 { v.wrapping_add(*m) } else { v.wrapping_sub(*m) };
+                values.push(v);
+            }
+            let (out, covered) = roundtrip_via_planned_runs(&values, true)?;
+            prop_assert!(covered == values.len() || covered + 1 == values.len());
+            prop_assert_eq!(out, &values[..covered]);
+        }
+
+        // All values identical: zero delta throughout, the degenerate fixed-delta case.
+        #[test]
+        fn plan_delta_runs_roundtrip_all_equal(value in any::<i64>(), len in 0usize..2_000) {
+            let values = vec![value; len];
+            let (out, covered) = roundtrip_via_planned_runs(&values, true)?;
+            prop_assert!(covered == values.len() || covered + 1 == values.len());
+            prop_assert_eq!(out, &values[..covered]);
+        }
+
+        // A single monotonic ramp far longer than `MAX_RUN_LENGTH`, so it must split
+        // purely on the run-length cap rather than a sign change.
+        #[test]
+        fn plan_delta_runs_roundtrip_long_ramp_past_max_run_length(
+            start in any::<i64>(),
+            len in (MAX_RUN_LENGTH + 1)..(MAX_RUN_LENGTH * 3),
+        ) {
+            let values: Vec<i64> = (0..len as i64).map(|i| start.wrapping_add(i)).collect();
+            let (out, covered) = roundtrip_via_planned_runs(&values, true)?;
+            prop_assert!(covered == values.len() || covered + 1 == values.len());
+            prop_assert_eq!(out, &values[..covered]);
+            prop_assert!(plan_delta_runs(&values).len() >= 2);
+        }
+
+        // Values clustered near the `i64` extremes: wrapping mode is infallible by
+        // construction, so this must never error regardless of how the deltas overflow.
+        #[test]
+        fn plan_delta_runs_roundtrip_near_extremes(
+            values in prop::collection::vec(extreme_leaning_i64(), 0..500),
+        ) {
+            let (out, covered) = roundtrip_via_planned_runs(&values, true)?;
+            prop_assert!(covered == values.len() || covered + 1 == values.len());
+            prop_assert_eq!(out, &values[..covered]);
+        }
+
+        // Same extreme-leaning inputs with wrapping disabled: over/underflow must
+        // surface as a well-formed `OutOfSpec` error, never a panic, and any run that
+        // does decode successfully must still match its covered prefix exactly.
+        #[test]
+        fn plan_delta_runs_non_wrapping_never_panics(
+            values in prop::collection::vec(extreme_leaning_i64(), 0..500),
+        ) {
+            match roundtrip_via_planned_runs(&values, false) {
+                Ok((out, covered)) => {
+                    prop_assert!(covered == values.len() || covered + 1 == values.len());
+                    prop_assert_eq!(out, &values[..covered]);
+                }
+                Err(e) => prop_assert!(matches!(e, OrcError::OutOfSpec { .. })),
+            }
+        }
+    }
 }
@@ -154,5 +154,16 @@ mod tests {
             let out = roundtrip_direct_helper::<_, UnsignedEncoding>(&encoded)?;
             prop_assert_eq!(out, values);
         }
+
+        /// Direct's bit width field tops out at 64 bits (see
+        /// [`rle_v2_decode_bit_width`]), so values are bounded to the `i64` range
+        /// even when decoding into `i128` (e.g. the unscaled part of a Decimal that
+        /// happens to fit in 64 bits).
+        #[test]
+        fn roundtrip_direct_i128(values in prop::collection::vec(any::<i64>().prop_map(i128::from), 1..=512)) {
+            let encoded = values.iter().map(|v| SignedEncoding::zigzag_encode(*v)).collect::<Vec<_>>();
+            let out = roundtrip_direct_helper::<_, SignedEncoding>(&encoded)?;
+            prop_assert_eq!(out, values);
+        }
     }
 }
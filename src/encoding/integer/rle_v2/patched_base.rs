@@ -35,11 +35,13 @@ use crate::{
     error::{OutOfSpecSnafu, Result},
 };
 
+/// Returns the number of patches applied, so callers can detect a patch list that
+/// wasn't actually needed (see [`super::RleV2Decoder::with_strict_mode`]).
 pub fn read_patched_base<N: NInt, R: Read, S: EncodingSign>(
     reader: &mut R,
     out_ints: &mut Vec<N>,
     header: u8,
-) -> Result<()> {
+) -> Result<usize> {
     let encoded_bit_width = (header >> 1) & 0x1F;
     let value_bit_width = rle_v2_decode_bit_width(encoded_bit_width);
     // Bit width derived from u8 above, so impossible to overflow u32
@@ -92,15 +94,20 @@ pub fn read_patched_base<N: NInt, R: Read, S: EncodingSign>(
     // TODO: document and explain below logic
     let mut patch_index = 0;
     let patch_mask = (1 << patch_bit_width) - 1;
-    let mut current_gap = patches[patch_index] >> patch_bit_width;
-    let mut current_patch = patches[patch_index] & patch_mask;
+    let patch_at = |patches: &[i64], index: usize| -> Result<i64> {
+        patches.get(index).copied().context(OutOfSpecSnafu {
+            msg: "patch list exhausted before its gap chain ended",
+        })
+    };
+    let mut current_gap = patch_at(&patches, patch_index)? >> patch_bit_width;
+    let mut current_patch = patch_at(&patches, patch_index)? & patch_mask;
     let mut actual_gap = 0;
 
     while current_gap == 255 && current_patch == 0 {
         actual_gap += 255;
         patch_index += 1;
-        current_gap = patches[patch_index] >> patch_bit_width;
-        current_patch = patches[patch_index] & patch_mask;
+        current_gap = patch_at(&patches, patch_index)? >> patch_bit_width;
+        current_patch = patch_at(&patches, patch_index)? & patch_mask;
     }
     actual_gap += current_gap;
 
@@ -123,15 +130,15 @@ pub fn read_patched_base<N: NInt, R: Read, S: EncodingSign>(
             patch_index += 1;
 
             if patch_index < patches.len() {
-                current_gap = patches[patch_index] >> patch_bit_width;
-                current_patch = patches[patch_index] & patch_mask;
+                current_gap = patch_at(&patches, patch_index)? >> patch_bit_width;
+                current_patch = patch_at(&patches, patch_index)? & patch_mask;
                 actual_gap = 0;
 
                 while current_gap == 255 && current_patch == 0 {
                     actual_gap += 255;
                     patch_index += 1;
-                    current_gap = patches[patch_index] >> patch_bit_width;
-                    current_patch = patches[patch_index] & patch_mask;
+                    current_gap = patch_at(&patches, patch_index)? >> patch_bit_width;
+                    current_patch = patch_at(&patches, patch_index)? & patch_mask;
                 }
 
                 actual_gap += current_gap;
@@ -144,7 +151,7 @@ pub fn read_patched_base<N: NInt, R: Read, S: EncodingSign>(
         }
     }
 
-    Ok(())
+    Ok(patch_list_length)
 }
 
 fn derive_patches(
@@ -216,6 +223,10 @@ fn derive_patches(
     (patches, patch_gap_width)
 }
 
+/// Emits a full Patched Base run: the 4-byte header, `base` in signed-MSB big-endian bytes,
+/// `base_reduced_literals` bit-packed at `brl_95p_bit_width`, then the patch list produced by
+/// [`derive_patches`] (gap-and-value pairs bit-packed together, with `0xFF`-gap filler entries
+/// for outliers spaced further apart than `patch_gap_width` can address in one entry).
 pub fn write_patched_base(
     writer: &mut BytesMut,
     base_reduced_literals: &mut [i64],
@@ -411,4 +422,37 @@ mod tests {
             prop_assert_eq!(actual, expected);
         }
     }
+
+    #[test]
+    fn read_patched_base_truncated_patch_list_errors_instead_of_panicking() {
+        // Two outliers far enough apart (gap > 255) that `derive_patches` must emit a
+        // `0xFF`-gap filler patch before the real one. Lying about the patch count in the
+        // header (claiming only the filler was written) forces the gap chain to look past
+        // the end of the (now too-short) decoded patch list.
+        let mut base_reduced_values = vec![0i64; 300];
+        base_reduced_values[299] = 1 << 20;
+        let brl_95p_bit_width = calculate_percentile_bits(&base_reduced_values, 0.95);
+        let brl_100p_bit_width = calculate_percentile_bits(&base_reduced_values, 1.0);
+        assert_ne!(brl_95p_bit_width, brl_100p_bit_width, "outlier must need patching");
+
+        let mut buf = BytesMut::new();
+        write_patched_base(
+            &mut buf,
+            &mut base_reduced_values,
+            0,
+            brl_100p_bit_width,
+            brl_95p_bit_width,
+        );
+        let header = buf[0];
+        // Header4's low 5 bits hold the patch count; claim there's only the filler entry.
+        buf[3] = (buf[3] & 0xe0) | 1;
+
+        let mut out = vec![];
+        let result = read_patched_base::<i64, _, SignedEncoding>(
+            &mut Cursor::new(&buf[1..]),
+            &mut out,
+            header,
+        );
+        assert!(result.is_err());
+    }
 }
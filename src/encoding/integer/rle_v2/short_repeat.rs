@@ -26,11 +26,14 @@ use crate::{
 
 use super::{NInt, SHORT_REPEAT_MIN_LENGTH};
 
-pub fn read_short_repeat_values<N: NInt, R: Read, S: EncodingSign>(
+/// Reads a Short Repeat run's value and length from an already-read `header`, without
+/// materializing the repeated run anywhere. Shared by [`read_short_repeat_values`] and
+/// [`super::RleV2Decoder`]'s bulk [`decode_batch_into`](crate::encoding::rle::GenericRle::decode_batch_into)
+/// path, which fills the caller's output slice directly instead of going through a `Vec`.
+pub fn read_short_repeat_value<N: NInt, R: Read, S: EncodingSign>(
     reader: &mut R,
-    out_ints: &mut Vec<N>,
     header: u8,
-) -> Result<()> {
+) -> Result<(N, usize)> {
     // Header byte:
     //
     // eeww_wccc
@@ -57,8 +60,16 @@ pub fn read_short_repeat_values<N: NInt, R: Read, S: EncodingSign>(
     let val = N::read_big_endian(reader, byte_width)?;
     let val = S::zigzag_decode(val);
 
-    out_ints.extend(std::iter::repeat(val).take(run_length));
+    Ok((val, run_length))
+}
 
+pub fn read_short_repeat_values<N: NInt, R: Read, S: EncodingSign>(
+    reader: &mut R,
+    out_ints: &mut Vec<N>,
+    header: u8,
+) -> Result<()> {
+    let (val, run_length) = read_short_repeat_value::<_, _, S>(reader, header)?;
+    out_ints.extend(std::iter::repeat(val).take(run_length));
     Ok(())
 }
 
@@ -15,15 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::io::Read;
-
 use bytes::{BufMut, BytesMut};
 use num::Signed;
-use snafu::OptionExt;
+
+use super::varint::{read_varint, read_varint_from_slice, varint_encoded_len, write_varint};
 
 use crate::{
-    encoding::util::read_u8,
-    error::{Result, VarintTooLargeSnafu},
+    encoding::{io_nostd::Read, util::read_u8},
+    error::Result,
 };
 
 use super::{EncodingSign, NInt, VarintSerde};
@@ -41,6 +40,11 @@ pub fn extract_run_length_from_header(first_byte: u8, second_byte: u8) -> usize
 
 /// Read bitpacked integers into provided buffer. `bit_size` can be any value from 1 to 64,
 /// inclusive.
+///
+/// This (together with [`write_packed_ints`]) is the shared arbitrary-bit-width primitive that
+/// Direct, Patched Base and Delta all pack/unpack their values through; the module exposes it as
+/// free functions operating on a byte buffer rather than a dedicated bit-accumulator type, to
+/// match how the rest of this file is structured.
 pub fn read_ints<N: NInt>(
     buffer: &mut Vec<N>,
     expected_no_of_ints: usize,
@@ -233,8 +237,8 @@ pub fn write_aligned_packed_ints<N: NInt>(writer: &mut BytesMut, bit_width: usiz
 }
 
 /// Similar to [`write_aligned_packed_ints`] but the `bit_width` allows any value
-/// in the range `[1, 64]`.
-pub fn write_packed_ints<N: NInt>(writer: &mut BytesMut, bit_width: usize, values: &[N]) {
+/// in the range `[1, 64]`. Returns the number of bytes written.
+pub fn write_packed_ints<N: NInt>(writer: &mut BytesMut, bit_width: usize, values: &[N]) -> usize {
     debug_assert!(
         (1..=64).contains(&bit_width),
         "bit_width must be in the range [1, 64]"
@@ -244,6 +248,10 @@ pub fn write_packed_ints<N: NInt>(writer: &mut BytesMut, bit_width: usize, value
     } else {
         write_unaligned_packed_ints(writer, bit_width, values)
     }
+    // Values are packed bit_width bits apiece with no padding between them, so the total
+    // byte count is fixed by bit_width and the value count regardless of which path above
+    // wrote them.
+    (bit_width * values.len()).div_ceil(8)
 }
 
 fn write_unaligned_packed_ints<N: NInt>(writer: &mut BytesMut, bit_width: usize, values: &[N]) {
@@ -471,54 +479,6 @@ pub fn get_closest_aligned_bit_width(width: usize) -> usize {
     }
 }
 
-/// Decode Base 128 Unsigned Varint
-fn read_varint<N: VarintSerde, R: Read>(reader: &mut R) -> Result<N> {
-    // Varints are encoded as sequence of bytes.
-    // Where the high bit of a byte is set to 1 if the varint
-    // continues into the next byte. Eventually it should terminate
-    // with a byte with high bit of 0.
-    let mut num = N::zero();
-    let mut offset = 0;
-    loop {
-        let byte = read_u8(reader)?;
-        let is_last_byte = byte & 0x80 == 0;
-        let without_continuation_bit = byte & 0x7F;
-        num |= N::from_u8(without_continuation_bit)
-            // Ensure we don't overflow
-            .checked_shl(offset)
-            .context(VarintTooLargeSnafu)?;
-        // Since high bit doesn't contribute to final number,
-        // we need to shift in multiples of 7 to account for this.
-        offset += 7;
-        if is_last_byte {
-            break;
-        }
-    }
-    Ok(num)
-}
-
-/// Encode Base 128 Unsigned Varint
-fn write_varint<N: VarintSerde>(writer: &mut BytesMut, value: N) {
-    // Take max in case value = 0.
-    // Divide by 7 as high bit is always used as continuation flag.
-    let byte_size = value.bits_used().div_ceil(7).max(1);
-    // By default we'll have continuation bit set
-    // TODO: can probably do without Vec allocation?
-    let mut bytes = vec![0x80; byte_size];
-    // Then just clear for the last one
-    let i = bytes.len() - 1;
-    bytes[i] = 0;
-
-    // Encoding 7 bits at a time into bytes
-    let mask = N::from_u8(0x7F);
-    for (i, b) in bytes.iter_mut().enumerate() {
-        let shift = i * 7;
-        *b |= ((value >> shift) & mask).to_u8().unwrap();
-    }
-
-    writer.put_slice(&bytes);
-}
-
 pub fn read_varint_zigzagged<N: VarintSerde, R: Read, S: EncodingSign>(
     reader: &mut R,
 ) -> Result<N> {
@@ -526,11 +486,34 @@ pub fn read_varint_zigzagged<N: VarintSerde, R: Read, S: EncodingSign>(
     Ok(S::zigzag_decode(unsigned))
 }
 
-pub fn write_varint_zigzagged<N: VarintSerde, S: EncodingSign>(writer: &mut BytesMut, value: N) {
+/// Zigzagged counterpart to [`read_varint_from_slice`](super::varint::read_varint_from_slice),
+/// for callers already holding the stream as a `&[u8]`: takes the same unrolled,
+/// bounds-check-free path whenever enough bytes remain, falling back to a bounds-checked
+/// byte-at-a-time loop only when the varint runs to the end of `data`, instead of paying a
+/// per-byte `Read::read` call either way.
+pub fn read_varint_zigzagged_from_slice<N: VarintSerde, S: EncodingSign>(
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<N> {
+    let unsigned = read_varint_from_slice::<N>(data, offset)?;
+    Ok(S::zigzag_decode(unsigned))
+}
+
+/// Returns the number of bytes written, matching [`varint_encoded_len_zigzagged`].
+pub fn write_varint_zigzagged<N: VarintSerde, S: EncodingSign>(
+    writer: &mut BytesMut,
+    value: N,
+) -> usize {
     let value = S::zigzag_encode(value);
     write_varint(writer, value)
 }
 
+/// Zigzagged counterpart to [`varint_encoded_len`], for callers sizing
+/// [`write_varint_zigzagged`]'s output ahead of time without encoding it.
+pub fn varint_encoded_len_zigzagged<N: VarintSerde, S: EncodingSign>(value: N) -> usize {
+    varint_encoded_len(S::zigzag_encode(value))
+}
+
 /// Zigzag encoding stores the sign bit in the least significant bit.
 #[inline]
 pub fn signed_zigzag_decode<N: VarintSerde + Signed>(encoded: N) -> N {
@@ -814,6 +797,42 @@ mod tests {
         read_varint_zigzagged::<N, _, S>(&mut Cursor::new(&buf)).unwrap()
     }
 
+    #[test]
+    fn slice_decode_matches_reader_decode_for_zigzagged_varints() {
+        let mut buf = BytesMut::new();
+        write_varint_zigzagged::<i64, SignedEncoding>(&mut buf, -16_384);
+        write_varint_zigzagged::<i64, SignedEncoding>(&mut buf, 0);
+        write_varint_zigzagged::<i64, SignedEncoding>(&mut buf, i64::MAX);
+
+        let mut offset = 0;
+        assert_eq!(
+            read_varint_zigzagged_from_slice::<i64, SignedEncoding>(&buf, &mut offset).unwrap(),
+            -16_384
+        );
+        assert_eq!(
+            read_varint_zigzagged_from_slice::<i64, SignedEncoding>(&buf, &mut offset).unwrap(),
+            0
+        );
+        assert_eq!(
+            read_varint_zigzagged_from_slice::<i64, SignedEncoding>(&buf, &mut offset).unwrap(),
+            i64::MAX
+        );
+        assert_eq!(offset, buf.len());
+    }
+
+    #[test]
+    fn slice_decode_falls_back_when_zigzagged_varint_runs_to_buffer_end() {
+        // A single `0x00` byte zigzag-decodes to 0 and forces the byte-safe fallback
+        // path in `read_varint_from_slice`, same as the unsigned case.
+        let buf = [0x00];
+        let mut offset = 0;
+        assert_eq!(
+            read_varint_zigzagged_from_slice::<i64, SignedEncoding>(&buf, &mut offset).unwrap(),
+            0
+        );
+        assert_eq!(offset, 1);
+    }
+
     proptest! {
         #[test]
         fn roundtrip_varint_i16(value: i16) {
@@ -844,6 +863,16 @@ mod tests {
             let out = roundtrip_varint::<_, UnsignedEncoding>(value);
             prop_assert_eq!(out, value);
         }
+
+        #[test]
+        fn varint_encoded_len_zigzagged_matches_write_varint_zigzagged(value: i64) {
+            let mut buf = BytesMut::new();
+            write_varint_zigzagged::<_, SignedEncoding>(&mut buf, value);
+            prop_assert_eq!(
+                varint_encoded_len_zigzagged::<_, SignedEncoding>(value),
+                buf.len()
+            );
+        }
     }
 
     #[test]
@@ -894,6 +923,16 @@ mod tests {
         Ok(out)
     }
 
+    #[test]
+    fn write_packed_ints_returns_actual_bytes_written() {
+        for bit_width in [1_usize, 2, 3, 4, 5, 8, 13, 16, 32, 64] {
+            let values = mask_to_bit_width::<i64>(&[1, 2, 3, 4, 5, 6, 7], bit_width);
+            let mut buf = BytesMut::new();
+            let written = write_packed_ints(&mut buf, bit_width, &values);
+            assert_eq!(written, buf.len());
+        }
+    }
+
     proptest! {
         #[test]
         fn roundtrip_packed_ints_serde_i64(
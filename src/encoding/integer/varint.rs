@@ -0,0 +1,339 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Base 128 varint encoding, as used for e.g. string lengths, dictionary offsets
+//! and decimal scales, generic over [`VarintSerde`] so it covers `i16`/`i32`/`i64`/`i128`.
+//!
+//! [`read_varint_from_slice`] is a zero-copy counterpart to [`read_varint`] for callers
+//! already holding the stream as a `&[u8]` (see
+//! [`SliceCursor::read_varint`](crate::encoding::io_nostd::SliceCursor::read_varint)):
+//! the `RleV1`/`RleV2` decoders still go through the generic, one-`Read::read`-call-per-byte
+//! [`read_varint`] internally, so switching their `decode_batch`/`read_*_values` paths over
+//! to the slice-cursor form remains future work.
+
+use bytes::{BufMut, BytesMut};
+use snafu::OptionExt;
+
+use crate::{
+    encoding::io_nostd::Read,
+    encoding::util::read_u8,
+    error::{OutOfSpecSnafu, Result, VarintTooLargeSnafu},
+};
+
+use super::VarintSerde;
+
+/// Varints are at most `ceil(N::BYTE_SIZE * 8 / 7)` bytes long; any stream that hasn't
+/// terminated by then is either corrupt or adversarial, so bail out instead of looping
+/// on an unbounded number of continuation bytes.
+fn max_varint_bytes<N: VarintSerde>() -> usize {
+    (N::BYTE_SIZE * 8).div_ceil(7)
+}
+
+/// Decode Base 128 Unsigned Varint.
+///
+/// Rejects non-canonical (overlong) encodings, i.e. ones with trailing continuation
+/// bytes that contribute no extra bits (e.g. `0x80, 0x00` encoding `0`), and streams
+/// that exceed the maximum number of bytes a valid varint of this width can occupy.
+pub fn read_varint<N: VarintSerde, R: Read>(reader: &mut R) -> Result<N> {
+    // Varints are encoded as sequence of bytes.
+    // Where the high bit of a byte is set to 1 if the varint
+    // continues into the next byte. Eventually it should terminate
+    // with a byte with high bit of 0.
+    let mut num = N::zero();
+    let mut offset = 0;
+    let mut byte_count = 0;
+    loop {
+        let byte = read_u8(reader)?;
+        byte_count += 1;
+        let is_last_byte = byte & 0x80 == 0;
+        let without_continuation_bit = byte & 0x7F;
+        // The terminating byte must carry at least one significant bit, unless it's
+        // also the first byte (i.e. the varint canonically encodes 0 as `0x00`).
+        // A terminating byte of all zeroes past the first position means a shorter
+        // encoding would have represented the same value: non-canonical (overlong).
+        if is_last_byte && without_continuation_bit == 0 && byte_count > 1 {
+            return OutOfSpecSnafu {
+                msg: "varint is not in canonical form (overlong encoding)",
+            }
+            .fail();
+        }
+        num |= N::from_u8(without_continuation_bit)
+            // Ensure we don't overflow
+            .checked_shl(offset)
+            .context(VarintTooLargeSnafu)?;
+        // Since high bit doesn't contribute to final number,
+        // we need to shift in multiples of 7 to account for this.
+        offset += 7;
+        if is_last_byte {
+            break;
+        }
+        if byte_count >= max_varint_bytes::<N>() {
+            return OutOfSpecSnafu {
+                msg: "varint exceeds maximum length for its integer width",
+            }
+            .fail();
+        }
+    }
+    Ok(num)
+}
+
+/// Fast path for [`read_varint_from_slice`], used once the caller has confirmed at least
+/// `max_varint_bytes::<N>()` bytes remain in `data` from `offset`: decodes with an
+/// unrolled loop that indexes straight into the slice instead of bounds-checking (via
+/// `Read::read`) on every byte. Same rejection rules as [`read_varint`] (overlong
+/// encodings, streams exceeding the maximum length for `N`'s width).
+fn read_varint_fast_unrolled<N: VarintSerde>(data: &[u8], offset: usize) -> Result<(N, usize)> {
+    let mut num = N::zero();
+    let mut shift = 0;
+    for i in 0..max_varint_bytes::<N>() {
+        let byte = data[offset + i];
+        let is_last_byte = byte & 0x80 == 0;
+        let without_continuation_bit = byte & 0x7F;
+        if is_last_byte && without_continuation_bit == 0 && i > 0 {
+            return OutOfSpecSnafu {
+                msg: "varint is not in canonical form (overlong encoding)",
+            }
+            .fail();
+        }
+        num |= N::from_u8(without_continuation_bit)
+            .checked_shl(shift)
+            .context(VarintTooLargeSnafu)?;
+        shift += 7;
+        if is_last_byte {
+            return Ok((num, offset + i + 1));
+        }
+    }
+    OutOfSpecSnafu {
+        msg: "varint exceeds maximum length for its integer width",
+    }
+    .fail()
+}
+
+/// Decodes `out.len()` consecutive varints out of `data`, starting at `*offset`, advancing
+/// `*offset` past the bytes consumed. Equivalent to calling [`read_varint_from_slice`]
+/// `out.len()` times, but amortizes that call's own bookkeeping over the whole batch instead
+/// of paying it per varint -- useful for callers like
+/// [`RleV1Decoder::decode_batch_into`](super::rle_v1::RleV1Decoder) that already know how
+/// many literals they need up front.
+///
+/// Unlike a SIMD bit-parallel decoder, this doesn't decode multiple varints per
+/// instruction: each varint's length depends on where the previous one's continuation bit
+/// chain ends, so there's no fixed stride to gather lanes against without first scanning for
+/// continuation-bit boundaries one byte at a time anyway (and this crate has no existing
+/// `std::arch` usage to build that scan on top of). What this function does get from
+/// batching is every item taking [`read_varint_fast_unrolled`]'s bounds-check-free path when
+/// the remaining slice is long enough, same as repeated [`read_varint_from_slice`] calls
+/// would; a true vectorized continuation-bit scan is left as future work.
+pub fn read_varints_batch<N: VarintSerde>(
+    data: &[u8],
+    offset: &mut usize,
+    out: &mut [N],
+) -> Result<()> {
+    for slot in out.iter_mut() {
+        *slot = read_varint_from_slice(data, offset)?;
+    }
+    Ok(())
+}
+
+/// Decodes a varint directly out of `data`, starting at `*offset`, advancing `*offset`
+/// past the bytes consumed. This is the zero-copy counterpart to [`read_varint`] for
+/// callers already holding the whole stream as an in-memory slice (e.g.
+/// [`SliceCursor`](crate::encoding::io_nostd::SliceCursor)): it skips the per-byte
+/// `Read::read` call by indexing the slice directly, taking the unrolled,
+/// bounds-check-free path in [`read_varint_fast_unrolled`] whenever enough bytes remain,
+/// and only falling back to an explicitly bounds-checked byte-at-a-time loop near the end
+/// of `data`. Never reads past `data.len()`.
+pub fn read_varint_from_slice<N: VarintSerde>(data: &[u8], offset: &mut usize) -> Result<N> {
+    if data.len() - *offset >= max_varint_bytes::<N>() {
+        let (value, new_offset) = read_varint_fast_unrolled(data, *offset)?;
+        *offset = new_offset;
+        return Ok(value);
+    }
+
+    let mut num = N::zero();
+    let mut shift = 0;
+    let mut byte_count = 0;
+    loop {
+        let byte = *data.get(*offset).context(OutOfSpecSnafu {
+            msg: "unexpected end of input while decoding varint",
+        })?;
+        *offset += 1;
+        byte_count += 1;
+        let is_last_byte = byte & 0x80 == 0;
+        let without_continuation_bit = byte & 0x7F;
+        if is_last_byte && without_continuation_bit == 0 && byte_count > 1 {
+            return OutOfSpecSnafu {
+                msg: "varint is not in canonical form (overlong encoding)",
+            }
+            .fail();
+        }
+        num |= N::from_u8(without_continuation_bit)
+            .checked_shl(shift)
+            .context(VarintTooLargeSnafu)?;
+        shift += 7;
+        if is_last_byte {
+            return Ok(num);
+        }
+        if byte_count >= max_varint_bytes::<N>() {
+            return OutOfSpecSnafu {
+                msg: "varint exceeds maximum length for its integer width",
+            }
+            .fail();
+        }
+    }
+}
+
+/// Returns the number of bytes [`write_varint`] would emit for `value`, without encoding
+/// it, for callers choosing between encodings (e.g. direct vs. patched base) by comparing
+/// their sizes up front.
+pub fn varint_encoded_len<N: VarintSerde>(value: N) -> usize {
+    // Take max in case value = 0.
+    // Divide by 7 as high bit is always used as continuation flag.
+    value.bits_used().div_ceil(7).max(1)
+}
+
+/// Encode Base 128 Unsigned Varint. Returns the number of bytes written, matching
+/// [`varint_encoded_len`].
+pub fn write_varint<N: VarintSerde>(writer: &mut BytesMut, value: N) -> usize {
+    let byte_size = varint_encoded_len(value);
+    // By default we'll have continuation bit set
+    // TODO: can probably do without Vec allocation?
+    let mut bytes = vec![0x80; byte_size];
+    // Then just clear for the last one
+    let i = bytes.len() - 1;
+    bytes[i] = 0;
+
+    // Encoding 7 bits at a time into bytes
+    let mask = N::from_u8(0x7F);
+    for (i, b) in bytes.iter_mut().enumerate() {
+        let shift = i * 7;
+        *b |= ((value >> shift) & mask).to_u8().unwrap();
+    }
+
+    writer.put_slice(&bytes);
+    byte_size
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn rejects_overlong_encoding() {
+        // 0 canonically encodes as a single 0x00 byte; padding it out with a
+        // continuation byte that adds no bits is non-canonical.
+        let err = read_varint::<i64, _>(&mut Cursor::new(&[0x80, 0x00]));
+        assert!(err.is_err());
+        assert_eq!(
+            "Out of spec, message: varint is not in canonical form (overlong encoding)",
+            err.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_stream_exceeding_max_length() {
+        // i16 only needs up to 3 bytes (ceil(16/7)); an unterminated run of
+        // continuation bytes beyond that must error rather than loop forever.
+        let err = read_varint::<i16, _>(&mut Cursor::new(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]));
+        assert!(err.is_err());
+        assert_eq!(
+            "Out of spec, message: varint exceeds maximum length for its integer width",
+            err.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn accepts_canonical_values() {
+        let mut buf = BytesMut::new();
+        write_varint::<i64>(&mut buf, 16_384);
+        let out = read_varint::<i64, _>(&mut Cursor::new(&buf)).unwrap();
+        assert_eq!(16_384, out);
+    }
+
+    #[test]
+    fn slice_decode_matches_reader_decode() {
+        let mut buf = BytesMut::new();
+        write_varint::<i64>(&mut buf, 16_384);
+        write_varint::<i64>(&mut buf, 0);
+        write_varint::<i64>(&mut buf, i64::MAX);
+
+        let mut offset = 0;
+        assert_eq!(
+            read_varint_from_slice::<i64>(&buf, &mut offset).unwrap(),
+            16_384
+        );
+        assert_eq!(read_varint_from_slice::<i64>(&buf, &mut offset).unwrap(), 0);
+        assert_eq!(
+            read_varint_from_slice::<i64>(&buf, &mut offset).unwrap(),
+            i64::MAX
+        );
+        assert_eq!(offset, buf.len());
+    }
+
+    #[test]
+    fn slice_decode_falls_back_when_varint_runs_to_buffer_end() {
+        // `max_varint_bytes::<i64>()` is 10, so a single-byte buffer forces the
+        // byte-safe fallback path rather than the unrolled one.
+        let buf = [0x00];
+        let mut offset = 0;
+        assert_eq!(read_varint_from_slice::<i64>(&buf, &mut offset).unwrap(), 0);
+        assert_eq!(offset, 1);
+    }
+
+    #[test]
+    fn slice_decode_rejects_overlong_encoding() {
+        let buf = [0x80, 0x00];
+        let mut offset = 0;
+        assert!(read_varint_from_slice::<i64>(&buf, &mut offset).is_err());
+    }
+
+    #[test]
+    fn batch_decode_matches_sequential_single_decodes() {
+        let mut buf = BytesMut::new();
+        write_varint::<i64>(&mut buf, 16_384);
+        write_varint::<i64>(&mut buf, 0);
+        write_varint::<i64>(&mut buf, i64::MAX);
+
+        let mut out = [0i64; 3];
+        let mut offset = 0;
+        read_varints_batch::<i64>(&buf, &mut offset, &mut out).unwrap();
+        assert_eq!(out, [16_384, 0, i64::MAX]);
+        assert_eq!(offset, buf.len());
+    }
+
+    #[test]
+    fn encoded_len_matches_actual_write_varint_output() {
+        for value in [0_i64, 1, 127, 128, 16_383, 16_384, i64::MAX] {
+            let mut buf = BytesMut::new();
+            write_varint::<i64>(&mut buf, value);
+            assert_eq!(varint_encoded_len(value), buf.len());
+        }
+    }
+
+    #[test]
+    fn batch_decode_propagates_error_from_any_item() {
+        let mut buf = BytesMut::new();
+        write_varint::<i64>(&mut buf, 16_384);
+        buf.extend_from_slice(&[0x80, 0x00]);
+
+        let mut out = [0i64; 2];
+        let mut offset = 0;
+        assert!(read_varints_batch::<i64>(&buf, &mut offset, &mut out).is_err());
+    }
+}
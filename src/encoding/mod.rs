@@ -17,6 +17,8 @@
 
 //! Encoding/decoding logic for writing/reading primitive values from ORC types.
 
+use std::mem::MaybeUninit;
+
 use arrow::buffer::NullBuffer;
 use bytes::Bytes;
 
@@ -27,6 +29,7 @@ pub mod byte;
 pub mod decimal;
 pub mod float;
 pub mod integer;
+pub(crate) mod io_nostd;
 mod rle;
 pub mod timestamp;
 mod util;
@@ -58,6 +61,27 @@ pub trait PrimitiveValueDecoder<V> {
     /// the buffer.
     fn decode(&mut self, out: &mut [V]) -> Result<()>;
 
+    /// Decode out.len() values into possibly-uninitialized memory, failing if it cannot
+    /// fill the buffer.
+    ///
+    /// On `Ok`, every element of `out` is guaranteed to have been written. On `Err`,
+    /// some prefix of `out` may have been written and the rest left uninitialized;
+    /// that's fine here since `V` is always one of this crate's `Copy`, no-`Drop` value
+    /// types, so there's nothing that needs dropping either way.
+    ///
+    /// The default implementation goes through [`Self::decode`]: that method only ever
+    /// writes to `out`, never reads from it, so it's sound to hand it a `&mut [V]` view
+    /// over memory that isn't initialized yet and let it fill every slot. This lets a
+    /// caller reserve (but not zero-fill) an Arrow `MutableBuffer`-backed `Vec` and
+    /// decode straight into it; override this method directly only if a decoder can
+    /// skip an intermediate buffer some other way.
+    fn decode_into(&mut self, out: &mut [MaybeUninit<V>]) -> Result<()> {
+        // SAFETY: `MaybeUninit<V>` has the same size and layout as `V`, and `decode`
+        // is guaranteed above to only write to `out`, never read from it.
+        let out = unsafe { &mut *(out as *mut [MaybeUninit<V>] as *mut [V]) };
+        self.decode(out)
+    }
+
     /// Decode into `out` according to the `true` elements in `present`.
     ///
     /// `present` must be the same length as `out`.
@@ -89,6 +113,34 @@ pub trait PrimitiveValueDecoder<V> {
 
         Ok(())
     }
+
+    /// Returns the out-of-band null mask the last [`Self::decode`]/[`Self::decode_spaced`]
+    /// call produced, if any, beyond whatever the column's `Present` stream already encoded.
+    /// `true` means the value at that index is present, `false` means it should be treated
+    /// as null despite having been decoded.
+    ///
+    /// Only the timestamp decoders override this, to null out a value under
+    /// [`TimestampOverflowMode::Null`](crate::encoding::timestamp::TimestampOverflowMode::Null)
+    /// instead of failing the whole batch; every other decoder keeps the default, which never
+    /// introduces nulls of its own.
+    fn take_overflow_mask(&mut self) -> Option<Vec<bool>> {
+        None
+    }
+}
+
+/// Decode `len` values out of `decoder` into a freshly allocated `Vec`, without the
+/// zero-fill a plain `vec![V::default(); len]` followed by [`PrimitiveValueDecoder::decode`]
+/// would need up front.
+pub(crate) fn decode_into_vec<V>(
+    decoder: &mut (impl PrimitiveValueDecoder<V> + ?Sized),
+    len: usize,
+) -> Result<Vec<V>> {
+    let mut data: Vec<V> = Vec::with_capacity(len);
+    decoder.decode_into(data.spare_capacity_mut())?;
+    // SAFETY: `decode_into` only returns `Ok` once every element of its `out` (here,
+    // the spare capacity of `data`) has been written.
+    unsafe { data.set_len(len) };
+    Ok(data)
 }
 
 #[cfg(test)]
@@ -20,18 +20,20 @@ use crate::error::{OutOfSpecSnafu, Result};
 use super::PrimitiveValueDecoder;
 
 mod sealed {
-    use std::io::Read;
+    use std::io::Read as StdRead;
 
     use crate::encoding::{
-        byte::ByteRleDecoder,
+        byte::{ByteRleDecoder, SliceByteRleDecoder},
         integer::{rle_v1::RleV1Decoder, rle_v2::RleV2Decoder, EncodingSign, NInt},
+        io_nostd::Read,
     };
 
     pub trait Rle {}
 
     impl<R: Read> Rle for ByteRleDecoder<R> {}
-    impl<N: NInt, R: Read, S: EncodingSign> Rle for RleV1Decoder<N, R, S> {}
-    impl<N: NInt, R: Read, S: EncodingSign> Rle for RleV2Decoder<N, R, S> {}
+    impl<N: NInt, R: StdRead, S: EncodingSign> Rle for RleV1Decoder<N, R, S> {}
+    impl<N: NInt, R: StdRead, S: EncodingSign> Rle for RleV2Decoder<N, R, S> {}
+    impl Rle for SliceByteRleDecoder<'_> {}
 }
 
 /// Generic decoding behaviour for run length encoded values, such as integers (v1 and v2)
@@ -39,6 +41,16 @@ mod sealed {
 ///
 /// Assumes an internal buffer which acts like a (single headed) queue where values are first
 /// decoded into, before being copied out into the output buffer (usually an Arrow array).
+///
+/// Deliberately has no `skip`/fast-forward primitive for row-group pruning: as
+/// [`crate::row_selection`] documents, this crate currently decodes every row of a stripe and
+/// filters the result down to the selection afterwards, since actually skipping decode would
+/// additionally require seeking each column's stream to the position its `RowIndex` stream
+/// recorded, which this crate doesn't parse yet. A decoder-level `skip` with nothing upstream
+/// able to call it would just be unreachable code. The `skip` that does exist today, on
+/// [`ArrayBatchDecoder`](crate::array_decoder::ArrayBatchDecoder), works at a level above
+/// this trait: it's for cheaply honoring an `OFFSET` or a child slot a parent `Present`
+/// stream already marked null, not for stream-seeking past a whole pruned row group.
 pub trait GenericRle<V: Copy> {
     /// Consume N elements from internal buffer to signify the values having been copied out.
     fn advance(&mut self, n: usize);
@@ -48,11 +60,28 @@ pub trait GenericRle<V: Copy> {
 
     /// This should clear the internal buffer and populate it with the next round of decoded
     /// values.
-    // TODO: Have a version that copies directly into the output buffer (e.g. Arrow array).
-    //       Currently we always decode to the internal buffer first, even if we can copy
-    //       directly to the output and skip the middle man. Ideally the internal buffer
-    //       should only be used for leftovers between calls to PrimitiveValueDecoder::decode.
     fn decode_batch(&mut self) -> Result<()>;
+
+    /// Decode complete runs directly into `out`, for as long as each run fits in the
+    /// remaining space, returning how many values were written. Skips the internal
+    /// buffer entirely in the common case where `out` is large enough to receive a
+    /// whole run, falling back to it only once a run no longer fits (the leftover then
+    /// becomes available for the next call, same as after [`Self::decode_batch`]).
+    ///
+    /// The default implementation just goes through [`Self::decode_batch`] and copies
+    /// out whatever fits, i.e. it doesn't avoid the double copy; override it when the
+    /// sub-encoding's runs are simple enough to decode straight into a slice.
+    fn decode_batch_into(&mut self, out: &mut [V]) -> Result<usize> {
+        if out.is_empty() {
+            return Ok(0);
+        }
+        self.decode_batch()?;
+        let available = self.available();
+        let copying = available.len().min(out.len());
+        out[..copying].copy_from_slice(&available[..copying]);
+        self.advance(copying);
+        Ok(copying)
+    }
 }
 
 impl<V: Copy, G: GenericRle<V> + sealed::Rle> PrimitiveValueDecoder<V> for G {
@@ -65,12 +94,29 @@ impl<V: Copy, G: GenericRle<V> + sealed::Rle> PrimitiveValueDecoder<V> for G {
             return Ok(());
         }
 
+        // No leftovers: try to decode straight into the caller's buffer, skipping the
+        // internal-buffer round-trip for as many whole runs as fit.
+        let mut copied = if available.is_empty() {
+            self.decode_batch_into(out)?
+        } else {
+            0
+        };
+
         // Otherwise progressively decode and copy over chunks.
         let len_to_copy = out.len();
-        let mut copied = 0;
         while copied < len_to_copy {
             if self.available().is_empty() {
                 self.decode_batch()?;
+                // A truncated/malformed stream can hit EOF with values still expected
+                // (e.g. a header claiming more values than the stream actually holds);
+                // `decode_batch` returning `Ok(())` without refilling the buffer would
+                // otherwise spin this loop forever, since `copying` computes to 0 below.
+                if self.available().is_empty() {
+                    return OutOfSpecSnafu {
+                        msg: "Array length less than expected",
+                    }
+                    .fail();
+                }
             }
 
             let copying = self.available().len();
@@ -97,3 +143,37 @@ impl<V: Copy, G: GenericRle<V> + sealed::Rle> PrimitiveValueDecoder<V> for G {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::encoding::integer::rle_v2::RleV2Decoder;
+    use crate::encoding::integer::SignedEncoding;
+    use crate::encoding::PrimitiveValueDecoder;
+
+    /// `fuzz/corpus/rle_v2_delta_decode/empty_stream`: an empty stream can't produce even
+    /// one value, so `decode_batch` returns `Ok(())` immediately at EOF without refilling
+    /// the internal buffer. Before the fill loop in `decode` above checked for this, that
+    /// made it spin forever instead of erroring.
+    #[test]
+    fn decode_on_stream_exhausted_before_any_values_errors_instead_of_hanging() {
+        let mut decoder = RleV2Decoder::<i64, _, SignedEncoding>::new(Cursor::new(&[][..]));
+        let mut out = vec![0; 512];
+        let result = decoder.decode(&mut out);
+        assert!(result.is_err());
+    }
+
+    /// A stream whose header claims more values than it actually holds exhausts partway
+    /// through decoding rather than on the very first `decode_batch` call; the fill loop
+    /// must still error instead of spinning once it runs out of input. `[0x0a, 0x27, 0x10]`
+    /// is a valid Short Repeat run of 5 values elsewhere in this module's tests -- truncate
+    /// off its value bytes so the header is readable but the run data isn't.
+    #[test]
+    fn decode_on_stream_truncated_mid_run_errors_instead_of_hanging() {
+        let mut decoder = RleV2Decoder::<i64, _, SignedEncoding>::new(Cursor::new(&[0x0a][..]));
+        let mut out = vec![0; 512];
+        let result = decoder.decode(&mut out);
+        assert!(result.is_err());
+    }
+}
@@ -18,7 +18,6 @@
 use std::marker::PhantomData;
 
 use arrow::datatypes::{ArrowTimestampType, TimeUnit};
-use snafu::ensure;
 
 use crate::{
     encoding::PrimitiveValueDecoder,
@@ -27,10 +26,47 @@ use crate::{
 
 const NANOSECONDS_IN_SECOND: i64 = 1_000_000_000;
 
+/// How a timestamp value that doesn't fit the requested output [`TimeUnit`] (either it loses
+/// precision, or it's simply out of the range an `i64` count of that unit can represent) is
+/// handled during decoding. Set via
+/// [`with_timestamp_overflow`](crate::ArrowReaderBuilder::with_timestamp_overflow).
+#[derive(Debug, Clone, Copy, Default)]
+pub enum TimestampOverflowMode {
+    /// Fail the whole column decode. Matches the historical behavior.
+    #[default]
+    Error,
+    /// Replace the offending value with a null and keep decoding the rest of the column.
+    Null,
+    /// Truncate the offending value towards zero to the closest value representable in the
+    /// target unit (for a value out of the target unit's `i64` range, clamp to that range
+    /// instead, since there's no truncating a value that's already out of range).
+    Saturate,
+    /// Like `Saturate`, but a value that merely loses sub-unit precision is rounded to the
+    /// nearest representable instant in the target unit, with ties rounding to even, instead
+    /// of being truncated towards zero. A value out of the target unit's `i64` range is still
+    /// clamped the same way `Saturate` clamps it, since there's no nearer in-range value to
+    /// round to.
+    RoundHalfEven,
+}
+
+/// Decodes the raw `(seconds_since_orc_base, nanoseconds)` pairs into epoch-relative values
+/// of `T`; `base_from_epoch` already has any writer-timezone adjustment baked in by the
+/// caller (see [`new_timestamp_decoder`](crate::array_decoder::timestamp::new_timestamp_decoder)),
+/// so this type itself stays timezone-agnostic.
 pub struct TimestampDecoder<T: ArrowTimestampType> {
     base_from_epoch: i64,
     data: Box<dyn PrimitiveValueDecoder<i64> + Send>,
     secondary: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+    overflow: TimestampOverflowMode,
+    /// Set by [`Self::decode`] when `overflow` is [`TimestampOverflowMode::Null`] and at least
+    /// one value in the most recent batch was out of range; drained by
+    /// [`PrimitiveValueDecoder::take_overflow_mask`].
+    overflow_mask: Option<Vec<bool>>,
+    /// Scratch space for `data`/`secondary`'s decoded values, grown (never shrunk) to the
+    /// largest `out.len()` seen by [`Self::decode`] so a full-file scan allocates each of
+    /// these at most once, rather than twice per batch.
+    data_scratch: Vec<i64>,
+    secondary_scratch: Vec<i64>,
     _marker: PhantomData<T>,
 }
 
@@ -39,11 +75,16 @@ impl<T: ArrowTimestampType> TimestampDecoder<T> {
         base_from_epoch: i64,
         data: Box<dyn PrimitiveValueDecoder<i64> + Send>,
         secondary: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+        overflow: TimestampOverflowMode,
     ) -> Self {
         Self {
             base_from_epoch,
             data,
             secondary,
+            overflow,
+            overflow_mask: None,
+            data_scratch: Vec::new(),
+            secondary_scratch: Vec::new(),
             _marker: PhantomData,
         }
     }
@@ -51,19 +92,39 @@ impl<T: ArrowTimestampType> TimestampDecoder<T> {
 
 impl<T: ArrowTimestampType> PrimitiveValueDecoder<T::Native> for TimestampDecoder<T> {
     fn decode(&mut self, out: &mut [T::Native]) -> Result<()> {
-        // TODO: can probably optimize, reuse buffers?
-        let mut data = vec![0; out.len()];
-        let mut secondary = vec![0; out.len()];
-        self.data.decode(&mut data)?;
-        self.secondary.decode(&mut secondary)?;
+        decode_into_scratch(self.data.as_mut(), &mut self.data_scratch, out.len())?;
+        decode_into_scratch(
+            self.secondary.as_mut(),
+            &mut self.secondary_scratch,
+            out.len(),
+        )?;
+        let data = &self.data_scratch[..out.len()];
+        let secondary = &self.secondary_scratch[..out.len()];
+        let mut overflow_mask: Option<Vec<bool>> = None;
         for (index, (&seconds_since_orc_base, &nanoseconds)) in
             data.iter().zip(secondary.iter()).enumerate()
         {
-            out[index] =
-                decode_timestamp::<T>(self.base_from_epoch, seconds_since_orc_base, nanoseconds)?;
+            let value = decode_timestamp::<T>(
+                self.base_from_epoch,
+                seconds_since_orc_base,
+                nanoseconds,
+                self.overflow,
+            )?;
+            out[index] = match value {
+                Some(value) => value,
+                None => {
+                    overflow_mask.get_or_insert_with(|| vec![true; out.len()])[index] = false;
+                    T::Native::default()
+                }
+            };
         }
+        self.overflow_mask = overflow_mask;
         Ok(())
     }
+
+    fn take_overflow_mask(&mut self) -> Option<Vec<bool>> {
+        self.overflow_mask.take()
+    }
 }
 
 /// Arrow TimestampNanosecond type cannot represent the full datetime range of
@@ -73,6 +134,8 @@ pub struct TimestampNanosecondAsDecimalDecoder {
     base_from_epoch: i64,
     data: Box<dyn PrimitiveValueDecoder<i64> + Send>,
     secondary: Box<dyn PrimitiveValueDecoder<i64> + Send>,
+    data_scratch: Vec<i64>,
+    secondary_scratch: Vec<i64>,
 }
 
 impl TimestampNanosecondAsDecimalDecoder {
@@ -85,17 +148,22 @@ impl TimestampNanosecondAsDecimalDecoder {
             base_from_epoch,
             data,
             secondary,
+            data_scratch: Vec::new(),
+            secondary_scratch: Vec::new(),
         }
     }
 }
 
 impl PrimitiveValueDecoder<i128> for TimestampNanosecondAsDecimalDecoder {
     fn decode(&mut self, out: &mut [i128]) -> Result<()> {
-        // TODO: can probably optimize, reuse buffers?
-        let mut data = vec![0; out.len()];
-        let mut secondary = vec![0; out.len()];
-        self.data.decode(&mut data)?;
-        self.secondary.decode(&mut secondary)?;
+        decode_into_scratch(self.data.as_mut(), &mut self.data_scratch, out.len())?;
+        decode_into_scratch(
+            self.secondary.as_mut(),
+            &mut self.secondary_scratch,
+            out.len(),
+        )?;
+        let data = &self.data_scratch[..out.len()];
+        let secondary = &self.secondary_scratch[..out.len()];
         for (index, (&seconds_since_orc_base, &nanoseconds)) in
             data.iter().zip(secondary.iter()).enumerate()
         {
@@ -106,6 +174,22 @@ impl PrimitiveValueDecoder<i128> for TimestampNanosecondAsDecimalDecoder {
     }
 }
 
+/// Decodes `len` values from `decoder` into `scratch[..len]`, growing `scratch` first (and
+/// only growing, never shrinking) if it isn't already at least `len` long. Since `len` is a
+/// batch size that's fixed for the lifetime of an `ArrowReader`, `scratch` settles into its
+/// final capacity after the first call and every later call decodes straight into the same
+/// allocation instead of requesting a fresh one.
+fn decode_into_scratch(
+    decoder: &mut (impl PrimitiveValueDecoder<i64> + ?Sized),
+    scratch: &mut Vec<i64>,
+    len: usize,
+) -> Result<()> {
+    if scratch.len() < len {
+        scratch.resize(len, 0);
+    }
+    decoder.decode(&mut scratch[..len])
+}
+
 fn decode(base: i64, seconds_since_orc_base: i64, nanoseconds: i64) -> (i128, i64, u64) {
     let data = seconds_since_orc_base;
     // TODO: is this a safe cast?
@@ -136,45 +220,93 @@ fn decode(base: i64, seconds_since_orc_base: i64, nanoseconds: i64) -> (i128, i6
     (nanoseconds_since_epoch, seconds, nanoseconds)
 }
 
+/// Rounds `numerator / denominator` (`denominator > 0`) to the nearest integer, ties rounding
+/// to even, rather than truncating towards zero the way plain integer division does.
+fn round_half_even_div(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator.div_euclid(denominator);
+    let remainder_times_two = numerator.rem_euclid(denominator) * 2;
+    match remainder_times_two.cmp(&denominator) {
+        std::cmp::Ordering::Less => quotient,
+        std::cmp::Ordering::Greater => quotient + 1,
+        std::cmp::Ordering::Equal => quotient + (quotient & 1),
+    }
+}
+
+/// Applies `overflow` once either loss of precision or range overflow has been detected,
+/// producing the value `decode_timestamp` should return for that element.
+fn handle_timestamp_overflow<T: ArrowTimestampType>(
+    overflow: TimestampOverflowMode,
+    nanoseconds_since_epoch: i128,
+    nanoseconds_in_timeunit: i128,
+    seconds: i64,
+    nanoseconds: u64,
+    lost_precision: bool,
+) -> Result<Option<i64>> {
+    match overflow {
+        TimestampOverflowMode::Error => DecodeTimestampSnafu {
+            seconds,
+            nanoseconds,
+            to_time_unit: T::UNIT,
+        }
+        .fail(),
+        TimestampOverflowMode::Null => Ok(None),
+        TimestampOverflowMode::Saturate => Ok(Some(
+            (nanoseconds_since_epoch / nanoseconds_in_timeunit)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+        )),
+        // An out-of-range value has no nearer in-range value to round to, so just clamp it
+        // like `Saturate` does; rounding only kicks in for the sub-unit precision loss case.
+        TimestampOverflowMode::RoundHalfEven if lost_precision => Ok(Some(
+            round_half_even_div(nanoseconds_since_epoch, nanoseconds_in_timeunit)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+        )),
+        TimestampOverflowMode::RoundHalfEven => Ok(Some(
+            (nanoseconds_since_epoch / nanoseconds_in_timeunit)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+        )),
+    }
+}
+
 fn decode_timestamp<T: ArrowTimestampType>(
     base: i64,
     seconds_since_orc_base: i64,
     nanoseconds: i64,
-) -> Result<i64> {
+    overflow: TimestampOverflowMode,
+) -> Result<Option<i64>> {
     let (nanoseconds_since_epoch, seconds, nanoseconds) =
         decode(base, seconds_since_orc_base, nanoseconds);
 
-    let nanoseconds_in_timeunit = match T::UNIT {
+    let nanoseconds_in_timeunit: i128 = match T::UNIT {
         TimeUnit::Second => 1_000_000_000,
         TimeUnit::Millisecond => 1_000_000,
         TimeUnit::Microsecond => 1_000,
         TimeUnit::Nanosecond => 1,
     };
 
-    // Error if loss of precision
-    // TODO: make this configurable (e.g. can succeed but truncate)
-    ensure!(
-        nanoseconds_since_epoch % nanoseconds_in_timeunit == 0,
-        DecodeTimestampSnafu {
+    // Loss of precision converting to the target unit.
+    if nanoseconds_since_epoch % nanoseconds_in_timeunit != 0 {
+        return handle_timestamp_overflow::<T>(
+            overflow,
+            nanoseconds_since_epoch,
+            nanoseconds_in_timeunit,
             seconds,
             nanoseconds,
-            to_time_unit: T::UNIT,
-        }
-    );
-
-    // Convert to i64 and error if overflow
-    let num_since_epoch = (nanoseconds_since_epoch / nanoseconds_in_timeunit)
-        .try_into()
-        .or_else(|_| {
-            DecodeTimestampSnafu {
-                seconds,
-                nanoseconds,
-                to_time_unit: T::UNIT,
-            }
-            .fail()
-        })?;
+            true,
+        );
+    }
 
-    Ok(num_since_epoch)
+    // Convert to i64, handling overflow.
+    match (nanoseconds_since_epoch / nanoseconds_in_timeunit).try_into() {
+        Ok(num_since_epoch) => Ok(Some(num_since_epoch)),
+        Err(_) => handle_timestamp_overflow::<T>(
+            overflow,
+            nanoseconds_since_epoch,
+            nanoseconds_in_timeunit,
+            seconds,
+            nanoseconds,
+            false,
+        ),
+    }
 }
 
 fn decode_timestamp_as_i128(base: i64, seconds_since_orc_base: i64, nanoseconds: i64) -> i128 {
@@ -15,17 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::io::Read;
-
-use snafu::ResultExt;
-
-use crate::error::{self, Result};
+use crate::encoding::io_nostd::Read;
+use crate::error::Result;
 
 /// Read single byte.
 #[inline]
 pub fn read_u8(reader: &mut impl Read) -> Result<u8> {
     let mut byte = [0];
-    reader.read_exact(&mut byte).context(error::IoSnafu)?;
+    reader.read_exact(&mut byte)?;
     Ok(byte[0])
 }
 
@@ -33,6 +30,6 @@ pub fn read_u8(reader: &mut impl Read) -> Result<u8> {
 #[inline]
 pub fn try_read_u8(reader: &mut impl Read) -> Result<Option<u8>> {
     let mut byte = [0];
-    let length = reader.read(&mut byte).context(error::IoSnafu)?;
+    let length = reader.read(&mut byte)?;
     Ok((length > 0).then_some(byte[0]))
 }
@@ -17,6 +17,13 @@
 
 use std::io;
 
+// `OrcError::Io`/`DecodeFloat` below still carry a `std::io::Error` directly, and this
+// type also wraps `ArrowError`/`prost::DecodeError`/`object_store::Error`, all of which are
+// themselves `std`-only -- so a `no_std` feature here would need every one of those
+// dependencies to offer a `core`-only error type first, not just a local shim for this
+// enum. `io_nostd::Read`/`Write` (see that module) already let the RLE codecs avoid naming
+// `std::io::Read`/`std::io::Write` directly, narrowing the gap, but this error type and the
+// crate as a whole remain `std`-only for now (see the no_std note on the crate's module docs).
 use arrow::datatypes::DataType as ArrowDataType;
 use arrow::datatypes::TimeUnit;
 use arrow::error::ArrowError;
@@ -31,7 +38,7 @@ use crate::schema::DataType;
 #[snafu(visibility(pub))]
 pub enum OrcError {
     #[snafu(display("Failed to read, source: {}", source))]
-    IoError {
+    Io {
         source: std::io::Error,
         #[snafu(implicit)]
         location: Location,
@@ -159,12 +166,71 @@ pub enum OrcError {
         source: lz4_flex::block::DecompressError,
     },
 
+    #[snafu(display("Failed to build snappy encoder: {}", source))]
+    BuildSnappyEncoder {
+        #[snafu(implicit)]
+        location: Location,
+        source: snap::Error,
+    },
+
     #[snafu(display("Arrow error: {}", source))]
     Arrow {
         source: arrow::error::ArrowError,
         #[snafu(implicit)]
         location: Location,
     },
+
+    #[snafu(display(
+        "File requires the '{}' compression codec, but the '{}' crate feature was disabled at \
+         build time",
+        feature,
+        feature,
+    ))]
+    UnsupportedCompressionFeature {
+        #[snafu(implicit)]
+        location: Location,
+        feature: &'static str,
+    },
+
+    #[snafu(display("Duplicate key found while decoding map column '{}'", name))]
+    DuplicateMapKey {
+        #[snafu(implicit)]
+        location: Location,
+        name: String,
+    },
+
+    #[snafu(display(
+        "Timestamp column index {} has values (min_utc_ms={}, max_utc_ms={}) that {:?} cannot \
+         represent without overflow; pick a coarser TimeUnit via \
+         ArrowReaderBuilder::with_timestamp_unit",
+        column_index,
+        min_utc_ms,
+        max_utc_ms,
+        unit,
+    ))]
+    TimestampUnitOverflow {
+        #[snafu(implicit)]
+        location: Location,
+        column_index: usize,
+        unit: TimeUnit,
+        min_utc_ms: i64,
+        max_utc_ms: i64,
+    },
+
+    #[snafu(display(
+        "Column '{}' is {:?} in the file, but the schema passed to \
+         ArrowReaderBuilder::with_schema asks for {:?}, which arrow-rs cannot cast to",
+        name,
+        from_type,
+        to_type,
+    ))]
+    UnsupportedSchemaCast {
+        #[snafu(implicit)]
+        location: Location,
+        name: String,
+        from_type: ArrowDataType,
+        to_type: ArrowDataType,
+    },
 }
 
 pub type Result<T, E = OrcError> = std::result::Result<T, E>;
@@ -20,40 +20,228 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::{Schema, SchemaRef};
-use datafusion::common::Statistics;
+use datafusion::arrow::datatypes::{Schema, SchemaRef, TimeUnit};
+use datafusion::common::stats::Precision;
+use datafusion::common::{ColumnStatistics, Statistics};
 use datafusion::datasource::file_format::file_compression_type::FileCompressionType;
 use datafusion::datasource::file_format::FileFormat;
-use datafusion::datasource::physical_plan::{FileScanConfig, FileSource};
+use datafusion::datasource::physical_plan::{
+    FileGroup, FileScanConfig, FileSinkConfig, FileSource,
+};
 use datafusion::error::{DataFusionError, Result};
+use datafusion::physical_expr::LexRequirement;
 use datafusion::physical_plan::ExecutionPlan;
 use futures::TryStreamExt;
 use orc_rust::reader::metadata::read_metadata_async;
 
-use crate::OrcSource;
+use crate::pruning::min_max_scalars;
+use crate::{sink::OrcSink, OrcSource};
 use async_trait::async_trait;
 use datafusion::catalog::Session;
 use datafusion::datasource::source::DataSourceExec;
+use datafusion_datasource::sink::DataSinkExec;
+use datafusion_datasource::{FileRange, PartitionedFile};
 use futures_util::StreamExt;
 use object_store::path::Path;
 use object_store::{ObjectMeta, ObjectStore};
 
 use super::object_store_reader::ObjectStoreReader;
 
+/// Target size, in bytes, for each stripe-aligned partition [`repartition_by_stripes`]
+/// produces. There's no ORC-specific guidance to lean on here, so this just mirrors the
+/// ballpark DataFusion's own file sources use for their `repartition_file_min_size`
+/// default, which works out well for typical object store read sizes.
+const TARGET_PARTITION_BYTES: u64 = 128 * 1024 * 1024;
+
 async fn fetch_schema(store: &Arc<dyn ObjectStore>, file: &ObjectMeta) -> Result<(Path, Schema)> {
     let loc_path = file.location.clone();
     let mut reader = ObjectStoreReader::new(Arc::clone(store), file.clone());
     let metadata = read_metadata_async(&mut reader)
         .await
         .map_err(|e| DataFusionError::External(Box::new(e)))?;
-    let schema = metadata
-        .root_data_type()
-        .create_arrow_schema(&HashMap::default());
+    let schema = metadata.root_data_type().create_arrow_schema(
+        &HashMap::default(),
+        TimeUnit::Nanosecond,
+        None,
+        &Default::default(),
+        None,
+        false,
+    );
     Ok((loc_path, schema))
 }
 
+/// Reads the file footer and maps its per-column statistics onto `table_schema`, indexing
+/// the same way [`StripeStatistics`](crate::pruning::StripeStatistics) and [`OrcOpener`]'s
+/// projection do: walking the root data type's direct children by name (root itself is
+/// column index 0, children are offset by +1 from there).
+async fn fetch_statistics(
+    store: &Arc<dyn ObjectStore>,
+    table_schema: &SchemaRef,
+    file: &ObjectMeta,
+) -> Result<Statistics> {
+    let mut reader = ObjectStoreReader::new(Arc::clone(store), file.clone());
+    let metadata = read_metadata_async(&mut reader)
+        .await
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+    let num_rows = metadata.number_of_rows();
+    let file_statistics = metadata.column_file_statistics();
+
+    let column_statistics = table_schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let column_stats = metadata
+                .root_data_type()
+                .children()
+                .find(|named_column| named_column.name() == field.name())
+                .and_then(|named_column| {
+                    file_statistics.get(named_column.data_type().column_index())
+                });
+            let Some(column_stats) = column_stats else {
+                return ColumnStatistics::new_unknown();
+            };
+
+            let (min_value, max_value) = column_stats
+                .type_statistics()
+                .map(min_max_scalars)
+                .unwrap_or((None, None));
+
+            ColumnStatistics {
+                null_count: Precision::Exact(
+                    (num_rows.saturating_sub(column_stats.number_of_values())) as usize,
+                ),
+                min_value: min_value.map_or(Precision::Absent, Precision::Exact),
+                max_value: max_value.map_or(Precision::Absent, Precision::Exact),
+                ..ColumnStatistics::new_unknown()
+            }
+        })
+        .collect();
+
+    Ok(Statistics {
+        num_rows: Precision::Exact(num_rows as usize),
+        column_statistics,
+        ..Statistics::new_unknown(table_schema)
+    })
+}
+
+/// Rewrites `conf`'s file groups so that a single large ORC file's stripes can be scanned
+/// by more than one partition, the same way a multi-file scan already spreads its files
+/// across partitions. Splits always land on stripe boundaries (never mid-stripe, which
+/// `OrcOpener` has no way to resume from), by reading each file's footer up front and
+/// grouping consecutive stripes up to [`TARGET_PARTITION_BYTES`] per group.
+///
+/// A file that already carries a `range` is left alone, on the same "don't narrow a
+/// sub-range further" principle `OrcOpener::open`'s stripe pruning already follows.
+async fn repartition_by_stripes(
+    state: &dyn Session,
+    mut conf: FileScanConfig,
+) -> Result<FileScanConfig> {
+    let store = state.runtime_env().object_store(&conf.object_store_url)?;
+
+    let mut file_groups = Vec::with_capacity(conf.file_groups.len());
+    for file_group in &conf.file_groups {
+        let mut partitioned = Vec::with_capacity(file_group.len());
+        for file in file_group.iter() {
+            if file.range.is_some() {
+                partitioned.push(file.clone());
+                continue;
+            }
+            match stripe_aligned_partitions(&store, file).await {
+                Ok(parts) if !parts.is_empty() => partitioned.extend(parts),
+                _ => partitioned.push(file.clone()),
+            }
+        }
+        file_groups.push(FileGroup::new(partitioned));
+    }
+    conf.file_groups = file_groups;
+    Ok(conf)
+}
+
+/// Splits one [`PartitionedFile`] into stripe-aligned sub-partitions by reading its footer
+/// once and grouping consecutive stripes up to [`TARGET_PARTITION_BYTES`] per group.
+/// Returns an empty `Vec` (rather than a single whole-file entry) when the file has at most
+/// one stripe, so the caller falls back to scanning it as a single, unranged partition.
+async fn stripe_aligned_partitions(
+    store: &Arc<dyn ObjectStore>,
+    file: &PartitionedFile,
+) -> Result<Vec<PartitionedFile>> {
+    let mut reader = ObjectStoreReader::new(Arc::clone(store), file.object_meta.clone());
+    let metadata = read_metadata_async(&mut reader)
+        .await
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    let stripes = metadata.stripe_metadatas();
+    if stripes.len() <= 1 {
+        return Ok(Vec::new());
+    }
+
+    let mut partitions = Vec::new();
+    let mut group_start = stripes[0].offset();
+    let mut group_end = stripes[0].footer_offset() + stripes[0].footer_length();
+    for stripe in &stripes[1..] {
+        let stripe_end = stripe.footer_offset() + stripe.footer_length();
+        if stripe_end - group_start > TARGET_PARTITION_BYTES {
+            partitions.push(partition_with_range(file, group_start, group_end));
+            group_start = stripe.offset();
+        }
+        group_end = stripe_end;
+    }
+    partitions.push(partition_with_range(file, group_start, group_end));
+    Ok(partitions)
+}
+
+fn partition_with_range(file: &PartitionedFile, start: u64, end: u64) -> PartitionedFile {
+    let mut partition = file.clone();
+    partition.range = Some(FileRange {
+        start: start as i64,
+        end: end as i64,
+    });
+    partition
+}
+
 #[derive(Clone, Debug)]
-pub struct OrcFormat;
+pub struct OrcFormat {
+    coalesce_gap_threshold: u64,
+    coalesce_max_merged_size: u64,
+    file_column_name: Option<Arc<str>>,
+}
+
+impl OrcFormat {
+    pub fn new() -> Self {
+        Self {
+            coalesce_gap_threshold: crate::DEFAULT_COALESCE_GAP_THRESHOLD,
+            coalesce_max_merged_size: crate::DEFAULT_COALESCE_MAX_MERGED_SIZE,
+            file_column_name: None,
+        }
+    }
+
+    /// Sets the gap threshold streams fetched for a stripe are coalesced at; see
+    /// [`OrcReadOptions::coalesce_gap_threshold`](crate::OrcReadOptions::coalesce_gap_threshold).
+    pub fn with_coalesce_gap_threshold(mut self, gap_threshold: u64) -> Self {
+        self.coalesce_gap_threshold = gap_threshold;
+        self
+    }
+
+    /// Sets the cap on a single coalesced stream read; see
+    /// [`OrcReadOptions::coalesce_max_merged_size`](crate::OrcReadOptions::coalesce_max_merged_size).
+    pub fn with_coalesce_max_merged_size(mut self, max_merged_size: u64) -> Self {
+        self.coalesce_max_merged_size = max_merged_size;
+        self
+    }
+
+    /// Sets the name [`OrcOpener`] injects the originating file path under; see
+    /// [`OrcReadOptions::file_column_name`](crate::OrcReadOptions::file_column_name).
+    pub fn with_file_column_name(mut self, file_column_name: Option<&str>) -> Self {
+        self.file_column_name = file_column_name.map(Arc::from);
+        self
+    }
+}
+
+impl Default for OrcFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[async_trait]
 impl FileFormat for OrcFormat {
@@ -107,22 +295,47 @@ impl FileFormat for OrcFormat {
     async fn infer_stats(
         &self,
         _state: &dyn Session,
-        _store: &Arc<dyn ObjectStore>,
+        store: &Arc<dyn ObjectStore>,
         table_schema: SchemaRef,
-        _object: &ObjectMeta,
+        object: &ObjectMeta,
     ) -> Result<Statistics> {
-        Ok(Statistics::new_unknown(&table_schema))
+        fetch_statistics(store, &table_schema, object).await
     }
 
+    /// `conf`'s projection (`FileScanConfig::file_column_projection_indices`) already reaches
+    /// the stripe reader without any extra work here: [`OrcSource::create_file_opener`]
+    /// builds an [`OrcOpener`](crate::physical_exec::OrcOpener) from `conf`, which translates
+    /// the projected Arrow field indices into ORC column ids (via each root child's
+    /// `column_index()`, accounting for the nested type tree `create_arrow_schema` produces)
+    /// and passes them to [`ArrowReaderBuilder::with_projection`] as a
+    /// [`ProjectionMask`](orc_rust::projection::ProjectionMask). That mask is threaded down to
+    /// stripe decoding, so array decoders/builders for unselected columns are never
+    /// constructed and their streams are never read.
     async fn create_physical_plan(
         &self,
-        _state: &dyn Session,
+        state: &dyn Session,
         conf: FileScanConfig,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        let conf = repartition_by_stripes(state, conf).await?;
         Ok(DataSourceExec::from_data_source(conf))
     }
 
     fn file_source(&self) -> Arc<dyn FileSource> {
-        Arc::new(OrcSource::default())
+        Arc::new(
+            OrcSource::default()
+                .with_coalesce_settings(self.coalesce_gap_threshold, self.coalesce_max_merged_size)
+                .with_file_column_name(self.file_column_name.clone()),
+        )
+    }
+
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &dyn Session,
+        conf: FileSinkConfig,
+        order_requirements: Option<LexRequirement>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let sink = Arc::new(OrcSink::new(conf));
+        Ok(Arc::new(DataSinkExec::new(input, sink, order_requirements)) as _)
     }
 }
@@ -17,7 +17,11 @@
 
 use crate::physical_exec::OrcOpener;
 use datafusion::common::Statistics;
+use datafusion::config::ConfigOptions;
 use datafusion::datasource::physical_plan::{FileOpener, FileScanConfig, FileSource};
+use datafusion::physical_expr::utils::conjunction;
+use datafusion::physical_expr::PhysicalExpr;
+use datafusion::physical_plan::filter_pushdown::FilterPushdownPropagation;
 use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion_datasource::TableSchema;
 use object_store::ObjectStore;
@@ -29,6 +33,15 @@ pub struct OrcSource {
     metrics: ExecutionPlanMetricsSet,
     statistics: Statistics,
     batch_size: usize,
+    /// Filter pushed down from the query, used to prune whole stripes via their min/max
+    /// statistics in [`OrcOpener`] before any of their bytes are read.
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    /// See [`crate::OrcReadOptions::coalesce_gap_threshold`].
+    coalesce_gap_threshold: u64,
+    /// See [`crate::OrcReadOptions::coalesce_max_merged_size`].
+    coalesce_max_merged_size: u64,
+    /// See [`crate::OrcReadOptions::file_column_name`].
+    file_column_name: Option<Arc<str>>,
 }
 
 impl Default for OrcSource {
@@ -37,6 +50,39 @@ impl Default for OrcSource {
             metrics: ExecutionPlanMetricsSet::default(),
             statistics: Statistics::default(),
             batch_size: 1024,
+            predicate: None,
+            coalesce_gap_threshold: crate::DEFAULT_COALESCE_GAP_THRESHOLD,
+            coalesce_max_merged_size: crate::DEFAULT_COALESCE_MAX_MERGED_SIZE,
+            file_column_name: None,
+        }
+    }
+}
+
+impl OrcSource {
+    /// Returns a copy of this source with stripe pruning driven by `predicate`.
+    pub fn with_predicate(&self, predicate: Arc<dyn PhysicalExpr>) -> Self {
+        Self {
+            predicate: Some(predicate),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this source that coalesces a stripe's stream reads per
+    /// `gap_threshold`/`max_merged_size`; see [`crate::OrcFormat::with_coalesce_gap_threshold`].
+    pub(crate) fn with_coalesce_settings(&self, gap_threshold: u64, max_merged_size: u64) -> Self {
+        Self {
+            coalesce_gap_threshold: gap_threshold,
+            coalesce_max_merged_size: max_merged_size,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this source that injects the originating file path as a column
+    /// named `file_column_name`; see [`crate::OrcFormat::with_file_column_name`].
+    pub(crate) fn with_file_column_name(&self, file_column_name: Option<Arc<str>>) -> Self {
+        Self {
+            file_column_name,
+            ..self.clone()
         }
     }
 }
@@ -46,9 +92,19 @@ impl FileSource for OrcSource {
         &self,
         object_store: Arc<dyn ObjectStore>,
         config: &FileScanConfig,
-        _partition: usize,
+        partition: usize,
     ) -> Arc<dyn FileOpener> {
-        Arc::new(OrcOpener::new(object_store, config, self.batch_size))
+        Arc::new(OrcOpener::new(
+            object_store,
+            config,
+            self.batch_size,
+            self.predicate.clone(),
+            self.coalesce_gap_threshold,
+            self.coalesce_max_merged_size,
+            self.file_column_name.clone(),
+            &self.metrics,
+            partition,
+        ))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -88,4 +144,29 @@ impl FileSource for OrcSource {
     fn file_type(&self) -> &str {
         "orc"
     }
+
+    /// This `FileSource` version of DataFusion's filter pushdown API is how predicates reach
+    /// this source -- `FileFormat::create_physical_plan` in this DataFusion version has no
+    /// `filters` argument of its own to plumb through. The predicate ends up driving stripe
+    /// pruning in [`OrcOpener::open`](crate::physical_exec::OrcOpener::open), which consults
+    /// each stripe's `ColumnStatistics` min/max/hasNull via [`PruningPredicate`] and then
+    /// further narrows the surviving range at bloom-filter (row-group) granularity.
+    ///
+    /// [`PruningPredicate`]: datafusion::physical_optimizer::pruning::PruningPredicate
+    fn try_pushdown_filters(
+        &self,
+        filters: Vec<Arc<dyn PhysicalExpr>>,
+        _config: &ConfigOptions,
+    ) -> datafusion::common::Result<FilterPushdownPropagation<Arc<dyn FileSource>>> {
+        // Every filter is handled identically below -- evaluated against whichever stripes'
+        // min/max statistics prove it can't match, same as a predicate attached directly via
+        // `with_predicate` -- so there's no reason to track them individually. Folding them
+        // into one conjunction also lets a multi-column `WHERE` clause prune a stripe that no
+        // single one of its filters could have ruled out on its own.
+        let Some(predicate) = conjunction(filters.clone()) else {
+            return Ok(FilterPushdownPropagation::unsupported(filters));
+        };
+        let source = Arc::new(self.with_predicate(predicate)) as Arc<dyn FileSource>;
+        Ok(FilterPushdownPropagation::all_supported(filters).with_updated_node(source))
+    }
 }
@@ -41,10 +41,18 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! This crate has no `no_std` mode: it exists to plug ORC into DataFusion's query engine,
+//! and DataFusion itself, along with the `tokio`/`object_store` stack this crate's async
+//! reader and [`sink`] paths build on, requires `std`. Abstracting the column/RLE decode
+//! subsystem (`Column`, `Decompressor`, the `RleV1`/`RleV2` readers) over a minimal
+//! `Read`/`Seek` shim the way a pure-decoder crate might is possible in principle, but
+//! wouldn't buy embedded/WASM-without-std support on its own while every other layer above
+//! it still pulls in `std` transitively.
 
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use datafusion::common::exec_err;
 use datafusion::config::TableOptions;
 use datafusion::dataframe::DataFrame;
@@ -61,19 +69,94 @@ use async_trait::async_trait;
 use self::file_format::OrcFormat;
 
 mod file_format;
+mod file_source;
 mod object_store_reader;
 mod physical_exec;
+mod pruning;
+mod sink;
+
+pub use file_source::OrcSource;
 
 /// Configuration options for reading ORC files.
 #[derive(Clone)]
 pub struct OrcReadOptions<'a> {
     pub file_extension: &'a str,
+    /// The schema to resolve the table to, bypassing per-file schema inference.
+    ///
+    /// Columns the schema names but a given file doesn't have are materialized as all-null
+    /// arrays of the requested type rather than causing an error, and the columns a file does
+    /// have are coerced to the requested types and reordered to match -- see
+    /// [`OrcOpener`](crate::physical_exec::OrcOpener), which does the actual per-file
+    /// adaptation. Mirrors [`ArrowReaderBuilder::with_schema`](orc_rust::ArrowReaderBuilder).
+    pub schema: Option<&'a Schema>,
+    /// Stream reads within a stripe separated by less than this many bytes are merged into a
+    /// single read. Mirrors
+    /// [`ArrowReaderBuilder::with_coalesce_gap_threshold`](orc_rust::ArrowReaderBuilder).
+    pub coalesce_gap_threshold: u64,
+    /// Caps how large a single merged read from [`Self::coalesce_gap_threshold`] may grow.
+    /// Mirrors
+    /// [`ArrowReaderBuilder::with_coalesce_max_merged_size`](orc_rust::ArrowReaderBuilder).
+    pub coalesce_max_merged_size: u64,
+    /// Hive-style partition columns parsed from each file's path rather than its contents,
+    /// e.g. `[("year", DataType::Int32)]` for files laid out as `table/year=2024/*.orc`.
+    /// Mirrors [`ListingOptions::with_table_partition_cols`].
+    pub table_partition_cols: Vec<(String, DataType)>,
+    /// When set, injects the file's object-store path as a `Utf8` column of this name into
+    /// every batch, alongside the file's own columns.
+    pub file_column_name: Option<&'a str>,
+}
+
+/// Mirrors `orc_rust`'s own default gap threshold for coalescing stream reads within a
+/// stripe; kept in sync by hand since the two crates don't share the constant.
+pub(crate) const DEFAULT_COALESCE_GAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// Mirrors `orc_rust`'s own default cap on a single coalesced stream read.
+pub(crate) const DEFAULT_COALESCE_MAX_MERGED_SIZE: u64 = 8 * 1024 * 1024;
+
+impl<'a> OrcReadOptions<'a> {
+    /// Sets [`Self::schema`].
+    pub fn with_schema(mut self, schema: &'a Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Sets [`Self::coalesce_gap_threshold`].
+    pub fn with_coalesce_gap_threshold(mut self, gap_threshold: u64) -> Self {
+        self.coalesce_gap_threshold = gap_threshold;
+        self
+    }
+
+    /// Sets [`Self::coalesce_max_merged_size`].
+    pub fn with_coalesce_max_merged_size(mut self, max_merged_size: u64) -> Self {
+        self.coalesce_max_merged_size = max_merged_size;
+        self
+    }
+
+    /// Sets [`Self::table_partition_cols`].
+    pub fn with_table_partition_cols(
+        mut self,
+        table_partition_cols: Vec<(String, DataType)>,
+    ) -> Self {
+        self.table_partition_cols = table_partition_cols;
+        self
+    }
+
+    /// Sets [`Self::file_column_name`].
+    pub fn with_file_column_name(mut self, file_column_name: &'a str) -> Self {
+        self.file_column_name = Some(file_column_name);
+        self
+    }
 }
 
 impl<'a> Default for OrcReadOptions<'a> {
     fn default() -> Self {
         Self {
             file_extension: "orc",
+            schema: None,
+            coalesce_gap_threshold: DEFAULT_COALESCE_GAP_THRESHOLD,
+            coalesce_max_merged_size: DEFAULT_COALESCE_MAX_MERGED_SIZE,
+            table_partition_cols: Vec::new(),
+            file_column_name: None,
         }
     }
 }
@@ -85,8 +168,13 @@ impl ReadOptions<'_> for OrcReadOptions<'_> {
         _config: &SessionConfig,
         _table_options: TableOptions,
     ) -> ListingOptions {
-        let file_format = OrcFormat::new();
-        ListingOptions::new(Arc::new(file_format)).with_file_extension(self.file_extension)
+        let file_format = OrcFormat::new()
+            .with_coalesce_gap_threshold(self.coalesce_gap_threshold)
+            .with_coalesce_max_merged_size(self.coalesce_max_merged_size)
+            .with_file_column_name(self.file_column_name);
+        ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(self.file_extension)
+            .with_table_partition_cols(self.table_partition_cols.clone())
     }
 
     async fn get_resolved_schema(
@@ -95,11 +183,29 @@ impl ReadOptions<'_> for OrcReadOptions<'_> {
         state: SessionState,
         table_path: ListingTableUrl,
     ) -> Result<SchemaRef> {
-        self._get_resolved_schema(config, state, table_path, None)
-            .await
+        let schema = self
+            ._get_resolved_schema(config, state, table_path, self.schema)
+            .await?;
+        with_file_column(schema, self.file_column_name)
     }
 }
 
+/// Appends `file_column_name` as a nullable `Utf8` field to `schema`, unless it's absent or
+/// already present -- [`OrcOpener`](crate::physical_exec::OrcOpener) fills this column in per
+/// batch, the same way it null-fills a [`OrcReadOptions::schema`]-requested column the file
+/// doesn't have.
+fn with_file_column(schema: SchemaRef, file_column_name: Option<&str>) -> Result<SchemaRef> {
+    let Some(file_column_name) = file_column_name else {
+        return Ok(schema);
+    };
+    if schema.field_with_name(file_column_name).is_ok() {
+        return Ok(schema);
+    }
+    let mut fields = schema.fields().to_vec();
+    fields.push(Arc::new(Field::new(file_column_name, DataType::Utf8, true)));
+    Ok(Arc::new(Schema::new(fields)))
+}
+
 /// Exposes new functions for registering ORC tables onto a DataFusion [`SessionContext`]
 /// to enable querying them using the SQL or DataFrame API.
 pub trait SessionContextOrcExt {
@@ -126,8 +232,13 @@ impl SessionContextOrcExt for SessionContext {
         // SessionContext::_read_type
         let table_paths = table_paths.to_urls()?;
         let session_config = self.copied_config();
-        let listing_options =
-            ListingOptions::new(Arc::new(OrcFormat::new())).with_file_extension(".orc");
+        let file_format = OrcFormat::new()
+            .with_coalesce_gap_threshold(options.coalesce_gap_threshold)
+            .with_coalesce_max_merged_size(options.coalesce_max_merged_size)
+            .with_file_column_name(options.file_column_name);
+        let listing_options = ListingOptions::new(Arc::new(file_format))
+            .with_file_extension(".orc")
+            .with_table_partition_cols(options.table_partition_cols.clone());
 
         let option_extension = listing_options.file_extension.clone();
 
@@ -208,4 +319,39 @@ mod tests {
 
         Ok(())
     }
+
+    /// A projected, filtered query should come back with only the requested column and rows
+    /// matching the predicate -- and should do so via [`OrcSource::try_pushdown_filters`]
+    /// pruning whole stripes by their min/max timestamp statistics, the same
+    /// [`StripeStatistics`](crate::pruning::StripeStatistics) mechanism
+    /// [`OrcOpener`](crate::physical_exec::OrcOpener) already uses for `LIMIT`-driven pruning.
+    #[tokio::test]
+    async fn dataframe_projection_and_predicate_pushdown() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.register_orc(
+            "timestamps",
+            "tests/integration/data/pyarrow_timestamps.orc",
+            OrcReadOptions::default(),
+        )
+        .await?;
+
+        let actual = ctx
+            .sql("select ts from timestamps where ts > timestamp '2015-01-01 00:00:00'")
+            .await?
+            .collect()
+            .await?;
+
+        assert_batches_sorted_eq!(
+            [
+                "+---------------------------+",
+                "| ts                         |",
+                "+---------------------------+",
+                "| 2023-01-01T00:00:00.123456 |",
+                "+---------------------------+",
+            ],
+            &actual
+        );
+
+        Ok(())
+    }
 }
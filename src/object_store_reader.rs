@@ -25,6 +25,15 @@ use orc_rust::reader::AsyncChunkReader;
 use object_store::{GetOptions, ObjectMeta, ObjectStore};
 
 /// Implements [`AsyncChunkReader`] to allow reading ORC files via `object_store` API.
+///
+/// This issues one `get_range` per `get_bytes` call and does no coalescing or caching of its
+/// own -- that happens one layer up, in `orc_rust`'s [`Stripe`](orc_rust::stripe::Stripe)
+/// loading, which already knows every stream's byte range from the stripe footer before any
+/// column is decoded and merges nearby ranges into a single [`AsyncChunkReader::get_bytes`]
+/// call per merged span (see `fetch_streams_coalesced`/`plan_coalesced_reads`, tuned via
+/// [`OrcFormat::with_coalesce_gap_threshold`](crate::file_format::OrcFormat::with_coalesce_gap_threshold)
+/// / `with_coalesce_max_merged_size`). `ObjectStoreReader` itself has no visibility into a
+/// stripe's full stream layout, so it isn't the right place to plan merges.
 pub struct ObjectStoreReader {
     store: Arc<dyn ObjectStore>,
     file: ObjectMeta,
@@ -0,0 +1,641 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Simple per-column predicates for pruning whole stripes via their column statistics,
+//! without decompressing any data streams.
+
+use crate::schema::RootDataType;
+use crate::statistics::{ColumnStatistics, TypeStatistics};
+
+/// A scalar a [`Predicate`] compares a column's statistics against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PredicateValue {
+    Integer(i64),
+    Float(f64),
+    String(String),
+    /// Days since epoch, matching [`TypeStatistics::Date`].
+    Date(i32),
+    /// Milliseconds since UNIX epoch, matching the UTC min/max recorded by
+    /// [`TypeStatistics::Timestamp`].
+    Timestamp(i64),
+    Boolean(bool),
+}
+
+impl From<i64> for PredicateValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for PredicateValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<String> for PredicateValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for PredicateValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<bool> for PredicateValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+/// A predicate on root-level columns, combinable with [`And`](Self::And)/[`Or`](Self::Or),
+/// evaluated against a stripe's column statistics (see
+/// [`StripeMetadata::column_statistics`](crate::stripe::StripeMetadata::column_statistics))
+/// to decide whether the whole stripe can be skipped without decoding it.
+///
+/// Pass these to [`ArrowReaderBuilder::with_predicate`](crate::arrow_reader::ArrowReaderBuilder::with_predicate).
+/// Every variant is conservative: a stripe is only dropped when its statistics *prove*
+/// no row in it could match, so a column with no statistics recorded for a stripe (or
+/// whose statistics aren't a variant the predicate knows how to compare against) always
+/// falls back to scanning that stripe, rather than risk dropping rows that do match.
+///
+/// ORC also records statistics, and optionally a [bloom filter](crate::bloom_filter), per
+/// `rowIndexStride`-sized row group within a stripe via the `RowIndex` and `BloomFilter`
+/// streams. [`ResolvedPredicate::stride_may_match_bloom_filter`] evaluates an `Eq`/`In`
+/// predicate against one such stride, which
+/// [`NaiveStripeDecoder`](crate::array_decoder::NaiveStripeDecoder) uses to build a
+/// [`RowSelection`](crate::row_selection::RowSelection) keeping only rows from surviving
+/// strides. A batch made up entirely of excluded strides skips decoding altogether (see
+/// [`crate::row_selection`]'s module doc); one straddling the boundary is still decoded in
+/// full and filtered down. Either way this never reduces how much is read off the
+/// underlying streams: actually skipping the I/O for unselected strides would additionally
+/// require mapping a stride back to the stream position the `RowIndex` stream recorded for
+/// it, which this crate doesn't parse yet.
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    /// Keep the stripe unless `column`'s stripe statistics prove it has no nulls.
+    IsNull { column: String },
+    /// Keep the stripe unless `column`'s stripe statistics prove no row equals `value`.
+    Eq {
+        column: String,
+        value: PredicateValue,
+    },
+    /// Keep the stripe unless `column`'s stripe statistics prove no row falls in the
+    /// inclusive range `min..=max`.
+    Between {
+        column: String,
+        min: PredicateValue,
+        max: PredicateValue,
+    },
+    /// Keep the stripe unless `column`'s stripe statistics prove no row equals any of
+    /// `values`.
+    In {
+        column: String,
+        values: Vec<PredicateValue>,
+    },
+    /// Keep the stripe unless `column`'s stripe statistics prove no row is `< value`.
+    Lt {
+        column: String,
+        value: PredicateValue,
+    },
+    /// Keep the stripe unless `column`'s stripe statistics prove no row is `<= value`.
+    Le {
+        column: String,
+        value: PredicateValue,
+    },
+    /// Keep the stripe unless `column`'s stripe statistics prove no row is `> value`.
+    Gt {
+        column: String,
+        value: PredicateValue,
+    },
+    /// Keep the stripe unless `column`'s stripe statistics prove no row is `>= value`.
+    Ge {
+        column: String,
+        value: PredicateValue,
+    },
+    /// Keep the stripe unless every one of `predicates` proves it can be skipped.
+    And(Vec<Predicate>),
+    /// Keep the stripe unless none of `predicates` can prove it can't be skipped, i.e.
+    /// drop it only once every one of `predicates` proves no row could satisfy it.
+    Or(Vec<Predicate>),
+}
+
+impl Predicate {
+    pub fn is_null(column: impl Into<String>) -> Self {
+        Self::IsNull {
+            column: column.into(),
+        }
+    }
+
+    pub fn eq(column: impl Into<String>, value: impl Into<PredicateValue>) -> Self {
+        Self::Eq {
+            column: column.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn between(
+        column: impl Into<String>,
+        min: impl Into<PredicateValue>,
+        max: impl Into<PredicateValue>,
+    ) -> Self {
+        Self::Between {
+            column: column.into(),
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+
+    pub fn in_list(
+        column: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<PredicateValue>>,
+    ) -> Self {
+        Self::In {
+            column: column.into(),
+            values: values.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn lt(column: impl Into<String>, value: impl Into<PredicateValue>) -> Self {
+        Self::Lt {
+            column: column.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn le(column: impl Into<String>, value: impl Into<PredicateValue>) -> Self {
+        Self::Le {
+            column: column.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn gt(column: impl Into<String>, value: impl Into<PredicateValue>) -> Self {
+        Self::Gt {
+            column: column.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn ge(column: impl Into<String>, value: impl Into<PredicateValue>) -> Self {
+        Self::Ge {
+            column: column.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn and(predicates: impl IntoIterator<Item = Predicate>) -> Self {
+        Self::And(predicates.into_iter().collect())
+    }
+
+    pub fn or(predicates: impl IntoIterator<Item = Predicate>) -> Self {
+        Self::Or(predicates.into_iter().collect())
+    }
+
+    /// Resolves every column name reachable from this predicate to its ORC column index,
+    /// i.e. the index used into
+    /// [`StripeMetadata::column_statistics`](crate::stripe::StripeMetadata::column_statistics).
+    ///
+    /// Returns `None` (rather than an error) for a column name that doesn't exist on
+    /// `root_data_type`, since [`ArrowReaderBuilder::build`](crate::arrow_reader::ArrowReaderBuilder::build)
+    /// isn't fallible: an unresolvable leaf predicate is simply dropped (treated as always
+    /// matching) inside an [`And`](Self::And), falling back to scanning on that leaf's
+    /// account rather than failing the whole read over what's likely a typo. Inside an
+    /// [`Or`](Self::Or) this instead drops the *whole* `Or`, since one unresolvable branch
+    /// means the combined predicate can no longer prove any stripe unsatisfiable.
+    ///
+    /// Public so callers that want to filter a [`FileMetadata`](crate::reader::metadata::FileMetadata)'s
+    /// stripes themselves (rather than going through
+    /// [`ArrowReaderBuilder::with_predicate`](crate::arrow_reader::ArrowReaderBuilder::with_predicate))
+    /// can resolve a predicate once and reuse it across every stripe via
+    /// [`StripeMetadata::can_match`](crate::stripe::StripeMetadata::can_match).
+    pub fn resolve(&self, root_data_type: &RootDataType) -> Option<ResolvedPredicate> {
+        let resolve_column = |column: &str| {
+            root_data_type
+                .children()
+                .iter()
+                .find(|col| col.name() == column)
+                .map(|col| col.data_type().column_index())
+        };
+        match self {
+            Self::IsNull { column } => resolve_column(column)
+                .map(|column_index| ResolvedPredicate::IsNull { column_index }),
+            Self::Eq { column, value } => {
+                resolve_column(column).map(|column_index| ResolvedPredicate::Eq {
+                    column_index,
+                    value: value.clone(),
+                })
+            }
+            Self::Between { column, min, max } => {
+                resolve_column(column).map(|column_index| ResolvedPredicate::Between {
+                    column_index,
+                    min: min.clone(),
+                    max: max.clone(),
+                })
+            }
+            Self::In { column, values } => {
+                resolve_column(column).map(|column_index| ResolvedPredicate::In {
+                    column_index,
+                    values: values.clone(),
+                })
+            }
+            Self::Lt { column, value } => {
+                resolve_column(column).map(|column_index| ResolvedPredicate::Lt {
+                    column_index,
+                    value: value.clone(),
+                })
+            }
+            Self::Le { column, value } => {
+                resolve_column(column).map(|column_index| ResolvedPredicate::Le {
+                    column_index,
+                    value: value.clone(),
+                })
+            }
+            Self::Gt { column, value } => {
+                resolve_column(column).map(|column_index| ResolvedPredicate::Gt {
+                    column_index,
+                    value: value.clone(),
+                })
+            }
+            Self::Ge { column, value } => {
+                resolve_column(column).map(|column_index| ResolvedPredicate::Ge {
+                    column_index,
+                    value: value.clone(),
+                })
+            }
+            Self::And(predicates) => Some(ResolvedPredicate::And(
+                predicates
+                    .iter()
+                    .filter_map(|predicate| predicate.resolve(root_data_type))
+                    .collect(),
+            )),
+            Self::Or(predicates) => {
+                let resolved = predicates
+                    .iter()
+                    .map(|predicate| predicate.resolve(root_data_type))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(ResolvedPredicate::Or(resolved))
+            }
+        }
+    }
+}
+
+/// A [`Predicate`] tree whose column names have already been resolved to their ORC column
+/// indices, ready to evaluate against a stripe's column statistics.
+#[derive(Debug, Clone)]
+pub enum ResolvedPredicate {
+    IsNull {
+        column_index: usize,
+    },
+    Eq {
+        column_index: usize,
+        value: PredicateValue,
+    },
+    Between {
+        column_index: usize,
+        min: PredicateValue,
+        max: PredicateValue,
+    },
+    In {
+        column_index: usize,
+        values: Vec<PredicateValue>,
+    },
+    Lt {
+        column_index: usize,
+        value: PredicateValue,
+    },
+    Le {
+        column_index: usize,
+        value: PredicateValue,
+    },
+    Gt {
+        column_index: usize,
+        value: PredicateValue,
+    },
+    Ge {
+        column_index: usize,
+        value: PredicateValue,
+    },
+    And(Vec<ResolvedPredicate>),
+    Or(Vec<ResolvedPredicate>),
+}
+
+impl ResolvedPredicate {
+    /// The single column this predicate leaf reads statistics for, or `None` for an
+    /// `And`/`Or`, which may span more than one. Used to look up a stripe's bloom filter
+    /// stream for a leaf predicate; see
+    /// [`crate::arrow_reader::Cursor::row_selection_for`].
+    pub(crate) fn column_index(&self) -> Option<usize> {
+        match self {
+            Self::IsNull { column_index }
+            | Self::Eq { column_index, .. }
+            | Self::Between { column_index, .. }
+            | Self::In { column_index, .. }
+            | Self::Lt { column_index, .. }
+            | Self::Le { column_index, .. }
+            | Self::Gt { column_index, .. }
+            | Self::Ge { column_index, .. } => Some(*column_index),
+            Self::And(_) | Self::Or(_) => None,
+        }
+    }
+
+    /// `false` only if `column_statistics` proves no row could satisfy this predicate;
+    /// `true` otherwise, including whenever a referenced column's statistics aren't present
+    /// or don't carry a comparable statistic. `column_statistics` is indexed the same way
+    /// [`StripeMetadata::column_statistics`](crate::stripe::StripeMetadata::column_statistics)
+    /// is, i.e. by ORC column index.
+    pub fn may_match(&self, column_statistics: &[ColumnStatistics]) -> bool {
+        match self {
+            Self::IsNull { column_index } => column_statistics
+                .get(*column_index)
+                .map_or(true, |stats| stats.has_null()),
+            Self::Eq {
+                column_index,
+                value,
+            } => column_statistics
+                .get(*column_index)
+                .map_or(true, |stats| Self::overlaps(stats, value, value)),
+            Self::Between {
+                column_index,
+                min,
+                max,
+            } => column_statistics
+                .get(*column_index)
+                .map_or(true, |stats| Self::overlaps(stats, min, max)),
+            Self::In {
+                column_index,
+                values,
+            } => column_statistics.get(*column_index).map_or(true, |stats| {
+                values
+                    .iter()
+                    .any(|value| Self::overlaps(stats, value, value))
+            }),
+            Self::Lt {
+                column_index,
+                value,
+            } => column_statistics
+                .get(*column_index)
+                .map_or(true, |stats| Self::less_than(stats, value, false)),
+            Self::Le {
+                column_index,
+                value,
+            } => column_statistics
+                .get(*column_index)
+                .map_or(true, |stats| Self::less_than(stats, value, true)),
+            Self::Gt {
+                column_index,
+                value,
+            } => column_statistics
+                .get(*column_index)
+                .map_or(true, |stats| Self::greater_than(stats, value, false)),
+            Self::Ge {
+                column_index,
+                value,
+            } => column_statistics
+                .get(*column_index)
+                .map_or(true, |stats| Self::greater_than(stats, value, true)),
+            Self::And(predicates) => predicates
+                .iter()
+                .all(|predicate| predicate.may_match(column_statistics)),
+            Self::Or(predicates) => predicates
+                .iter()
+                .any(|predicate| predicate.may_match(column_statistics)),
+        }
+    }
+
+    /// `false` only if `stride`'s bloom filter proves it can't contain a value this
+    /// predicate looks for; `true` for any predicate kind a bloom filter can't help with
+    /// (only equality-style predicates benefit, since a bloom filter has no notion of
+    /// ordering, and only when every leaf of the tree is one, since a single miss can't
+    /// rule out an `Or` and a single stray non-equality leaf can't be skipped inside an
+    /// `And` without losing soundness for the rest of the tree). Used to build a
+    /// [`RowSelection`](crate::row_selection::RowSelection) narrowing a kept stripe down
+    /// to just its surviving `rowIndexStride`-sized row groups; see
+    /// [`Self::may_match_bloom_filter`] for testing a whole stripe's strides at once.
+    pub(crate) fn stride_may_match_bloom_filter(
+        &self,
+        stride: &crate::bloom_filter::BloomFilter,
+    ) -> bool {
+        match self.bloom_filter_values() {
+            Some(values) => values.iter().any(|value| stride.may_contain(value)),
+            None => true,
+        }
+    }
+
+    /// `false` only if a single `rowIndexStride`-sized group's statistics prove no row in
+    /// it could satisfy this predicate; `true` otherwise, including for `And`/`Or`, since
+    /// narrowing those would need the same column-index bookkeeping
+    /// [`Self::bloom_filter_values`] mentions -- unlike stripe-level [`Self::may_match`],
+    /// which does handle them, because there the column statistics are already indexed by
+    /// column rather than being a single-column slice scoped to one predicate leaf. See
+    /// [`crate::stripe::Stripe::row_group_statistics`] for where `stats` comes from, and
+    /// [`crate::arrow_reader::Cursor::row_selection_for`] for how the per-stride result is
+    /// combined with bloom filter pruning.
+    pub(crate) fn stride_may_match_statistics(&self, stats: &ColumnStatistics) -> bool {
+        match self {
+            Self::IsNull { .. } => stats.has_null(),
+            Self::Eq { value, .. } => Self::overlaps(stats, value, value),
+            Self::Between { min, max, .. } => Self::overlaps(stats, min, max),
+            Self::In { values, .. } => values
+                .iter()
+                .any(|value| Self::overlaps(stats, value, value)),
+            Self::Lt { value, .. } => Self::less_than(stats, value, false),
+            Self::Le { value, .. } => Self::less_than(stats, value, true),
+            Self::Gt { value, .. } => Self::greater_than(stats, value, false),
+            Self::Ge { value, .. } => Self::greater_than(stats, value, true),
+            Self::And(_) | Self::Or(_) => true,
+        }
+    }
+
+    /// `false` only if none of `strides` could contain a value this predicate looks for;
+    /// `true` for any predicate kind a bloom filter can't help with.
+    pub(crate) fn may_match_bloom_filter(
+        &self,
+        strides: &[crate::bloom_filter::BloomFilter],
+    ) -> bool {
+        match self.bloom_filter_values() {
+            Some(values) => strides
+                .iter()
+                .any(|stride| values.iter().any(|value| stride.may_contain(value))),
+            None => true,
+        }
+    }
+
+    /// The values an equality-style leaf predicate would test a bloom filter against, or
+    /// `None` for any predicate a bloom filter can't help with -- which, beyond the
+    /// ordering-sensitive comparisons a bloom filter never helps with, also includes every
+    /// `And`/`Or` for now, since combining per-leaf bloom results into one verdict for a
+    /// whole stride would need the same column-index bookkeeping
+    /// [`crate::arrow_reader::Cursor::row_selection_for`] does for a single leaf today.
+    fn bloom_filter_values(&self) -> Option<&[PredicateValue]> {
+        match self {
+            Self::Eq { value, .. } => Some(std::slice::from_ref(value)),
+            Self::In { values, .. } => Some(values),
+            Self::IsNull { .. }
+            | Self::Between { .. }
+            | Self::Lt { .. }
+            | Self::Le { .. }
+            | Self::Gt { .. }
+            | Self::Ge { .. }
+            | Self::And(_)
+            | Self::Or(_) => None,
+        }
+    }
+
+    /// `false` only if `stats` proves every row is `>= value` (or `> value` when
+    /// `inclusive` is `false`), i.e. no row can be `< value` (or `<= value`).
+    fn less_than(stats: &ColumnStatistics, value: &PredicateValue, inclusive: bool) -> bool {
+        let Some(stats_min) = Self::stats_min(stats) else {
+            return true;
+        };
+        match Self::partial_cmp_values(&stats_min, value) {
+            Some(std::cmp::Ordering::Greater) => false,
+            Some(std::cmp::Ordering::Equal) => inclusive,
+            Some(std::cmp::Ordering::Less) => true,
+            None => true,
+        }
+    }
+
+    /// `false` only if `stats` proves every row is `<= value` (or `< value` when
+    /// `inclusive` is `false`), i.e. no row can be `> value` (or `>= value`).
+    fn greater_than(stats: &ColumnStatistics, value: &PredicateValue, inclusive: bool) -> bool {
+        let Some(stats_max) = Self::stats_max(stats) else {
+            return true;
+        };
+        match Self::partial_cmp_values(&stats_max, value) {
+            Some(std::cmp::Ordering::Less) => false,
+            Some(std::cmp::Ordering::Equal) => inclusive,
+            Some(std::cmp::Ordering::Greater) => true,
+            None => true,
+        }
+    }
+
+    /// `stats`' minimum, as a [`PredicateValue`] comparable against a predicate's own
+    /// values, or `None` for a statistics kind with no single-value minimum (e.g. a
+    /// boolean's bucketed true count).
+    fn stats_min(stats: &ColumnStatistics) -> Option<PredicateValue> {
+        match stats.type_statistics()? {
+            TypeStatistics::Integer { min, .. } => Some(PredicateValue::Integer(*min)),
+            TypeStatistics::Double { min, .. } => Some(PredicateValue::Float(*min)),
+            TypeStatistics::String { min, .. } => Some(PredicateValue::String(min.clone())),
+            TypeStatistics::Date { min, .. } => Some(PredicateValue::Date(*min)),
+            TypeStatistics::Timestamp { min_utc, .. } => Some(PredicateValue::Timestamp(*min_utc)),
+            _ => None,
+        }
+    }
+
+    /// `stats`' maximum; see [`Self::stats_min`].
+    fn stats_max(stats: &ColumnStatistics) -> Option<PredicateValue> {
+        match stats.type_statistics()? {
+            TypeStatistics::Integer { max, .. } => Some(PredicateValue::Integer(*max)),
+            TypeStatistics::Double { max, .. } => Some(PredicateValue::Float(*max)),
+            TypeStatistics::String { max, .. } => Some(PredicateValue::String(max.clone())),
+            TypeStatistics::Date { max, .. } => Some(PredicateValue::Date(*max)),
+            TypeStatistics::Timestamp { max_utc, .. } => Some(PredicateValue::Timestamp(*max_utc)),
+            _ => None,
+        }
+    }
+
+    /// Compares two [`PredicateValue`]s of the same variant, or `None` if they're
+    /// different variants (can't happen for a value built from the same stats kind, but
+    /// this also guards a predicate's own value not matching its column's actual type).
+    fn partial_cmp_values(a: &PredicateValue, b: &PredicateValue) -> Option<std::cmp::Ordering> {
+        match (a, b) {
+            (PredicateValue::Integer(a), PredicateValue::Integer(b)) => a.partial_cmp(b),
+            (PredicateValue::Float(a), PredicateValue::Float(b)) => a.partial_cmp(b),
+            (PredicateValue::String(a), PredicateValue::String(b)) => a.partial_cmp(b),
+            (PredicateValue::Date(a), PredicateValue::Date(b)) => a.partial_cmp(b),
+            (PredicateValue::Timestamp(a), PredicateValue::Timestamp(b)) => a.partial_cmp(b),
+            (PredicateValue::Boolean(a), PredicateValue::Boolean(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+
+    /// Whether `[min, max]` could overlap the range `stats` records, i.e. whether
+    /// `min <= stats_max && max >= stats_min`.
+    fn overlaps(stats: &ColumnStatistics, min: &PredicateValue, max: &PredicateValue) -> bool {
+        let Some(type_statistics) = stats.type_statistics() else {
+            // No min/max recorded for this column/stripe: can't prove anything.
+            return true;
+        };
+        match (type_statistics, min, max) {
+            (
+                TypeStatistics::Integer {
+                    min: stats_min,
+                    max: stats_max,
+                    ..
+                },
+                PredicateValue::Integer(min),
+                PredicateValue::Integer(max),
+            ) => *min <= *stats_max && *max >= *stats_min,
+            (
+                TypeStatistics::Double {
+                    min: stats_min,
+                    max: stats_max,
+                    ..
+                },
+                PredicateValue::Float(min),
+                PredicateValue::Float(max),
+            ) => *min <= *stats_max && *max >= *stats_min,
+            (
+                TypeStatistics::String {
+                    min: stats_min,
+                    max: stats_max,
+                    ..
+                },
+                PredicateValue::String(min),
+                PredicateValue::String(max),
+            ) => min.as_str() <= stats_max.as_str() && max.as_str() >= stats_min.as_str(),
+            (
+                TypeStatistics::Date {
+                    min: stats_min,
+                    max: stats_max,
+                },
+                PredicateValue::Date(min),
+                PredicateValue::Date(max),
+            ) => *min <= *stats_max && *max >= *stats_min,
+            (
+                TypeStatistics::Timestamp {
+                    min_utc: stats_min,
+                    max_utc: stats_max,
+                    ..
+                },
+                PredicateValue::Timestamp(min),
+                PredicateValue::Timestamp(max),
+            ) => *min <= *stats_max && *max >= *stats_min,
+            (
+                TypeStatistics::Bucket { true_count },
+                PredicateValue::Boolean(min),
+                PredicateValue::Boolean(max),
+            ) => {
+                // `[min, max]` using `false < true`: does it include false, true, or both?
+                let wants_false = !*min;
+                let wants_true = *max;
+                let has_false = *true_count < stats.number_of_values();
+                let has_true = *true_count > 0;
+                (wants_false && has_false) || (wants_true && has_true)
+            }
+            // Predicate value doesn't match the kind of statistics recorded for this
+            // column (e.g. comparing a string predicate against date statistics): can't
+            // evaluate, so don't prune.
+            _ => true,
+        }
+    }
+}
@@ -15,9 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::schema::RootDataType;
+use snafu::OptionExt;
 
-// TODO: be able to nest project (project columns within struct type)
+use crate::error::{OutOfSpecSnafu, Result};
+use crate::schema::RootDataType;
 
 /// Specifies which column indices to project from an ORC type.
 #[derive(Debug, Clone)]
@@ -68,6 +69,58 @@ impl ProjectionMask {
         }
     }
 
+    /// Project individual leaf fields from the root type by dotted path, e.g.
+    /// `"order.customer.id"` to select just the `id` field of the `customer` struct
+    /// nested under the root's `order` field, without pulling in `customer`'s other
+    /// fields the way [`ProjectionMask::roots`] selecting `order` would.
+    ///
+    /// A path segment matches a struct field by name; the reserved segment `item`
+    /// descends into a list's element type, and `key`/`value` descend into a map's key
+    /// or value type. Every ancestor struct/list/map on the path is always included (not
+    /// just the leaf) so the stripe reader can still navigate down to it; selecting a
+    /// compound type itself (e.g. `"order.items"`) includes its entire subtree, the same
+    /// as [`ProjectionMask::roots`] would for a root-level field.
+    ///
+    /// Returns an error (rather than silently ignoring it) if any path segment doesn't
+    /// resolve to an existing column.
+    pub fn paths<T: AsRef<str>>(root_data_type: &RootDataType, paths: &[T]) -> Result<Self> {
+        // By default always project root
+        let mut indices = vec![0];
+        for path in paths {
+            let path = path.as_ref();
+            let mut segments = path.split('.');
+            let first = segments
+                .next()
+                .filter(|s| !s.is_empty())
+                .context(OutOfSpecSnafu {
+                    msg: format!("empty column projection path: '{path}'"),
+                })?;
+            let named_column = root_data_type
+                .children()
+                .iter()
+                .find(|col| col.name() == first)
+                .context(OutOfSpecSnafu {
+                    msg: format!("no column named '{first}' in projection path '{path}'"),
+                })?;
+            let mut current = named_column.data_type();
+            indices.push(current.column_index());
+            for segment in segments {
+                current = current
+                    .child_by_path_segment(segment)
+                    .context(OutOfSpecSnafu {
+                        msg: format!("no child named '{segment}' in projection path '{path}'"),
+                    })?;
+                indices.push(current.column_index());
+            }
+            indices.extend(current.children_indices());
+        }
+        indices.sort_unstable();
+        indices.dedup();
+        Ok(Self {
+            indices: Some(indices),
+        })
+    }
+
     /// Check if ORC column should is projected or not, by index.
     pub fn is_index_projected(&self, index: usize) -> bool {
         match &self.indices {
@@ -75,4 +128,72 @@ impl ProjectionMask {
             None => true,
         }
     }
+
+    /// The full set of ORC column ids this mask selects, resolved against `root_data_type`
+    /// (needed since [`ProjectionMask::all`] doesn't itself know how many columns exist) --
+    /// what a query engine's projection pushdown needs to map selected Arrow fields back
+    /// onto ORC streams.
+    pub fn selected_column_ids(&self, root_data_type: &RootDataType) -> Vec<usize> {
+        match &self.indices {
+            Some(indices) => {
+                let mut ids = indices.clone();
+                ids.sort_unstable();
+                ids.dedup();
+                ids
+            }
+            None => {
+                let mut ids = vec![root_data_type.column_index()];
+                for col in root_data_type.children() {
+                    ids.extend(col.data_type().all_indices());
+                }
+                ids
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::datatypes::{DataType as ArrowDataType, Field, Schema};
+
+    use super::*;
+    use crate::schema::arrow_schema_to_orc;
+
+    /// `root=0, a=1, b=2, c=3`.
+    fn three_column_schema() -> RootDataType {
+        let schema = Schema::new(vec![
+            Field::new("a", ArrowDataType::Int64, true),
+            Field::new("b", ArrowDataType::Int64, true),
+            Field::new("c", ArrowDataType::Int64, true),
+        ]);
+        let types = arrow_schema_to_orc(&schema).unwrap();
+        RootDataType::from_proto(&types).unwrap()
+    }
+
+    #[test]
+    fn selected_column_ids_all_returns_every_column_sorted() {
+        let root = three_column_schema();
+        let mask = ProjectionMask::all();
+        assert_eq!(mask.selected_column_ids(&root), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn selected_column_ids_some_sorts_indices() {
+        let root = three_column_schema();
+        let mask = ProjectionMask::roots(&root, [3, 1]);
+        assert_eq!(mask.selected_column_ids(&root), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn selected_column_ids_some_dedups_indices() {
+        let root = three_column_schema();
+        // No public constructor currently produces a `ProjectionMask` with duplicate
+        // indices, but nothing prevents one from doing so in the future either; build one
+        // directly (this test module is a child of `projection`, so it can see the private
+        // field) to pin down that `selected_column_ids` itself dedups rather than just sorts.
+        let mask = ProjectionMask {
+            indices: Some(vec![0, 1, 1, 2]),
+        };
+        assert_eq!(mask.selected_column_ids(&root), vec![0, 1, 2]);
+    }
 }
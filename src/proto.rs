@@ -143,6 +143,9 @@ pub struct ColumnEncoding {
     pub kind: ::core::option::Option<i32>,
     #[prost(uint32, optional, tag = "2")]
     pub dictionary_size: ::core::option::Option<u32>,
+    /// The encoding version of any bloom filters stored for this column, if it has one.
+    #[prost(uint32, optional, tag = "3")]
+    pub bloom_encoding: ::core::option::Option<u32>,
 }
 /// Nested message and enum types in `ColumnEncoding`.
 pub mod column_encoding {
@@ -178,6 +181,15 @@ pub struct Type {
     pub precision: ::core::option::Option<u32>,
     #[prost(uint32, optional, tag = "6")]
     pub scale: ::core::option::Option<u32>,
+    #[prost(message, repeated, tag = "7")]
+    pub attributes: ::prost::alloc::vec::Vec<StringPair>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StringPair {
+    #[prost(string, optional, tag = "1")]
+    pub key: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub value: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// Nested message and enum types in `Type`.
 pub mod r#type {
@@ -202,6 +214,7 @@ pub mod r#type {
         Date = 15,
         Varchar = 16,
         Char = 17,
+        TimestampInstant = 18,
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -0,0 +1,163 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, UInt64Array};
+use datafusion::common::{Column, ScalarValue};
+use datafusion::physical_optimizer::pruning::PruningStatistics;
+use orc_rust::reader::metadata::FileMetadata;
+use orc_rust::statistics::TypeStatistics;
+use orc_rust::stripe::StripeMetadata;
+
+/// Adapts a file's per-stripe [`StripeMetadata`] to DataFusion's [`PruningStatistics`],
+/// so a pushed-down filter can be evaluated against ORC's stripe-level min/max statistics
+/// before any stripe bytes are read.
+///
+/// Only the statistics variants with a natural scalar representation (integer, double,
+/// string and date) are exposed; columns whose stripe statistics are some other variant
+/// (e.g. decimal, timestamp, or a nested collection) report no min/max for every stripe,
+/// which [`PruningPredicate`](datafusion::physical_optimizer::pruning::PruningPredicate)
+/// treats as "unknown" and never allows it to be the reason a stripe gets pruned.
+pub(crate) struct StripeStatistics<'a> {
+    stripes: &'a [StripeMetadata],
+    /// Maps a (possibly projected) column name to its ORC column index, i.e. the index
+    /// used into [`StripeMetadata::column_statistics`].
+    column_indices: HashMap<String, usize>,
+}
+
+impl<'a> StripeStatistics<'a> {
+    pub(crate) fn new(stripes: &'a [StripeMetadata], file_metadata: &FileMetadata) -> Self {
+        let column_indices = file_metadata
+            .root_data_type()
+            .children()
+            .map(|named_column| {
+                (
+                    named_column.name().to_owned(),
+                    named_column.data_type().column_index(),
+                )
+            })
+            .collect();
+        Self {
+            stripes,
+            column_indices,
+        }
+    }
+
+    fn type_statistics(&self, column: &Column, index: usize) -> Option<&TypeStatistics> {
+        self.stripes[index]
+            .column_statistics()
+            .get(*self.column_indices.get(&column.name)?)
+            .and_then(|stats| stats.type_statistics())
+    }
+
+    /// Builds an array of one [`ScalarValue`] per stripe by applying `f` to each stripe's
+    /// type statistics. Bails out (returning `None`) the moment any stripe is missing
+    /// statistics or has a variant `f` doesn't understand, rather than mixing in nulls,
+    /// since a single untyped gap would otherwise make the whole array's type ambiguous.
+    fn scalar_array(
+        &self,
+        column: &Column,
+        f: impl Fn(&TypeStatistics) -> Option<ScalarValue>,
+    ) -> Option<ArrayRef> {
+        let values = (0..self.stripes.len())
+            .map(|index| self.type_statistics(column, index).and_then(&f))
+            .collect::<Option<Vec<_>>>()?;
+        ScalarValue::iter_to_array(values).ok()
+    }
+}
+
+impl PruningStatistics for StripeStatistics<'_> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.scalar_array(column, |stats| min_max_scalars(stats).0)
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.scalar_array(column, |stats| min_max_scalars(stats).1)
+    }
+
+    fn num_containers(&self) -> usize {
+        self.stripes.len()
+    }
+
+    fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
+        let values = self
+            .stripes
+            .iter()
+            .map(|stripe| {
+                let column_stats = stripe
+                    .column_statistics()
+                    .get(*self.column_indices.get(&column.name)?)?;
+                Some(
+                    stripe
+                        .number_of_rows()
+                        .saturating_sub(column_stats.number_of_values()),
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Arc::new(UInt64Array::from_iter_values(values)))
+    }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        Some(Arc::new(UInt64Array::from_iter_values(
+            self.stripes.iter().map(StripeMetadata::number_of_rows),
+        )))
+    }
+
+    /// Always reports "unknown": proving containment would mean fetching and decoding a
+    /// stripe's `BloomFilter` stream, which is I/O this synchronous trait method has no way
+    /// to perform. [`physical_exec`](crate::physical_exec)'s bloom-filter pruning instead
+    /// runs as an explicit async step once candidate stripes are already known, rather than
+    /// through this hook.
+    fn contained(
+        &self,
+        _column: &Column,
+        _values: &std::collections::HashSet<ScalarValue>,
+    ) -> Option<arrow::array::BooleanArray> {
+        None
+    }
+}
+
+/// Converts an ORC stripe- or file-level [`TypeStatistics`] into the `(min, max)`
+/// [`ScalarValue`] pair DataFusion's pruning and table statistics both want, for the
+/// variants with a natural single-scalar representation. Other variants (decimal,
+/// timestamp, nested collections) have no natural single-scalar min/max and report
+/// `(None, None)`.
+pub(crate) fn min_max_scalars(
+    stats: &TypeStatistics,
+) -> (Option<ScalarValue>, Option<ScalarValue>) {
+    match stats {
+        TypeStatistics::Integer { min, max, .. } => (
+            Some(ScalarValue::Int64(Some(*min))),
+            Some(ScalarValue::Int64(Some(*max))),
+        ),
+        TypeStatistics::Double { min, max, .. } => (
+            Some(ScalarValue::Float64(Some(*min))),
+            Some(ScalarValue::Float64(Some(*max))),
+        ),
+        TypeStatistics::String { min, max, .. } => (
+            Some(ScalarValue::Utf8(Some(min.clone()))),
+            Some(ScalarValue::Utf8(Some(max.clone()))),
+        ),
+        TypeStatistics::Date { min, max } => (
+            Some(ScalarValue::Date32(Some(*min))),
+            Some(ScalarValue::Date32(Some(*max))),
+        ),
+        _ => (None, None),
+    }
+}
@@ -51,6 +51,21 @@ impl ChunkReader for File {
     }
 }
 
+impl ChunkReader for Bytes {
+    type T = std::io::Cursor<Bytes>;
+
+    fn len(&self) -> u64 {
+        Bytes::len(self) as u64
+    }
+
+    /// A cheap `Bytes` clone (a refcount bump, not a copy) positioned at `offset_from_start`.
+    fn get_read(&self, offset_from_start: u64) -> std::io::Result<Self::T> {
+        let mut cursor = std::io::Cursor::new(self.clone());
+        cursor.set_position(offset_from_start);
+        Ok(cursor)
+    }
+}
+
 #[allow(clippy::len_without_is_empty)]
 pub trait AsyncChunkReader: Send {
     // TODO: this is only used for file tail, so replace with load_metadata?
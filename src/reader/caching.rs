@@ -0,0 +1,296 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A range-coalescing, size-bounded caching [`AsyncChunkReader`] wrapper.
+//!
+//! Stripe reading already plans its own coalesced fetches once it knows every stream's
+//! range from the footer (see `plan_coalesced_reads` in the `stripe` module), so
+//! [`CachingChunkReader`] isn't meant to replace that -- it's for the two things that
+//! planning step can't do on its own: serve a range that's already been fetched (e.g. the
+//! same stripe's footer re-read, or two row-group scans over the same file) out of memory
+//! instead of re-hitting the underlying store, and coalesce a caller-supplied *batch* of
+//! ranges ([`CachingChunkReader::get_ranges`]) the same way `plan_coalesced_reads` does, for
+//! callers that aren't going through `Stripe` at all.
+
+use std::collections::{HashMap, VecDeque};
+
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+
+use super::AsyncChunkReader;
+
+/// Merge reads separated by no more than this many bytes into one fetch, by default.
+pub const DEFAULT_COALESCE_GAP: u64 = 1024 * 1024;
+/// Never merge reads into a single fetch larger than this, by default.
+pub const DEFAULT_MAX_FETCH: u64 = 8 * 1024 * 1024;
+/// Cap the cache's total retained bytes at this, by default.
+pub const DEFAULT_CACHE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Wraps an [`AsyncChunkReader`] with a size-bounded LRU cache of fetched byte ranges and a
+/// coalescing batch read ([`get_ranges`](Self::get_ranges)).
+///
+/// Cache entries are keyed by the exact `(offset, length)` of the underlying fetch that
+/// produced them (a merged span's range when the caller's request was folded into one via
+/// coalescing, or the caller's own range otherwise), evicted oldest-first once
+/// [`cache_bytes`](Self::with_cache_bytes)'s budget is exceeded.
+pub struct CachingChunkReader<R> {
+    inner: R,
+    coalesce_gap: u64,
+    max_fetch: u64,
+    cache_bytes: u64,
+    cache: HashMap<(u64, u64), Bytes>,
+    // Oldest entry first; touched entries move to the back, so eviction (front) is LRU.
+    order: VecDeque<(u64, u64)>,
+    cached_bytes: u64,
+}
+
+impl<R> CachingChunkReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            coalesce_gap: DEFAULT_COALESCE_GAP,
+            max_fetch: DEFAULT_MAX_FETCH,
+            cache_bytes: DEFAULT_CACHE_BYTES,
+            cache: HashMap::new(),
+            order: VecDeque::new(),
+            cached_bytes: 0,
+        }
+    }
+
+    /// Sets the max gap, in bytes, between two requested ranges that still get merged into
+    /// one underlying fetch.
+    pub fn with_coalesce_gap(mut self, coalesce_gap: u64) -> Self {
+        self.coalesce_gap = coalesce_gap;
+        self
+    }
+
+    /// Caps how large a single merged fetch [`with_coalesce_gap`](Self::with_coalesce_gap)
+    /// is allowed to grow.
+    pub fn with_max_fetch(mut self, max_fetch: u64) -> Self {
+        self.max_fetch = max_fetch;
+        self
+    }
+
+    /// Sets the total number of bytes the cache retains before evicting the
+    /// least-recently-used entry.
+    pub fn with_cache_bytes(mut self, cache_bytes: u64) -> Self {
+        self.cache_bytes = cache_bytes;
+        self
+    }
+
+    fn touch(&mut self, key: (u64, u64)) {
+        if let Some(pos) = self.order.iter().position(|k| *k == key) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(key);
+    }
+
+    fn insert(&mut self, key: (u64, u64), bytes: Bytes) {
+        self.cached_bytes += bytes.len() as u64;
+        self.cache.insert(key, bytes);
+        self.order.push_back(key);
+        while self.cached_bytes > self.cache_bytes {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = self.cache.remove(&oldest) {
+                self.cached_bytes -= evicted.len() as u64;
+            }
+        }
+    }
+}
+
+impl<R: AsyncChunkReader> CachingChunkReader<R> {
+    /// Fetches every range in `ranges`, coalescing nearby ones into fewer underlying reads
+    /// and serving previously-fetched spans from cache, returning one [`Bytes`] per input
+    /// range in the same order.
+    ///
+    /// Mirrors `plan_coalesced_reads`/`fetch_streams_coalesced` in the `stripe` module:
+    /// ranges are sorted by offset, adjacent ones within
+    /// [`coalesce_gap`](Self::with_coalesce_gap) are merged into a single fetch bounded by
+    /// [`max_fetch`](Self::with_max_fetch), and each merged fetch is cached under its own
+    /// `(offset, length)` so a later request landing inside it -- even a different caller's
+    /// sub-range -- is served from memory.
+    pub async fn get_ranges(&mut self, ranges: &[(u64, u64)]) -> std::io::Result<Vec<Bytes>> {
+        let mut order: Vec<usize> = (0..ranges.len()).collect();
+        order.sort_by_key(|&i| ranges[i].0);
+
+        let mut results: Vec<Option<Bytes>> = vec![None; ranges.len()];
+        let mut index = 0;
+        while index < order.len() {
+            let (group_start, first_length) = ranges[order[index]];
+            let mut group_end = group_start + first_length;
+            let mut group_len = index + 1;
+            while group_len < order.len() {
+                let (next_offset, next_length) = ranges[order[group_len]];
+                let merged_end = next_offset + next_length;
+                if next_offset.saturating_sub(group_end) > self.coalesce_gap
+                    || merged_end - group_start > self.max_fetch
+                {
+                    break;
+                }
+                group_end = merged_end.max(group_end);
+                group_len += 1;
+            }
+
+            let key = (group_start, group_end - group_start);
+            let merged = match self.cache.get(&key).cloned() {
+                Some(bytes) => {
+                    self.touch(key);
+                    bytes
+                }
+                None => {
+                    let bytes = self.inner.get_bytes(key.0, key.1).await?;
+                    self.insert(key, bytes.clone());
+                    bytes
+                }
+            };
+
+            for &i in &order[index..group_len] {
+                let (offset, length) = ranges[i];
+                let start = (offset - group_start) as usize;
+                results[i] = Some(merged.slice(start..start + length as usize));
+            }
+            index = group_len;
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|bytes| bytes.expect("every range is covered by exactly one merged group"))
+            .collect())
+    }
+}
+
+impl<R: AsyncChunkReader> AsyncChunkReader for CachingChunkReader<R> {
+    fn len(&mut self) -> BoxFuture<'_, std::io::Result<u64>> {
+        self.inner.len()
+    }
+
+    /// Single-range reads go through the same cache as [`get_ranges`](Self::get_ranges), so
+    /// a range that was already fetched (as part of an earlier batch, or a plain repeat
+    /// request) is served from memory. Without sibling ranges to coalesce against, a cache
+    /// miss here is just forwarded to the inner reader unmerged; batch multiple ranges via
+    /// [`get_ranges`] up front to get the coalescing benefit.
+    fn get_bytes(
+        &mut self,
+        offset_from_start: u64,
+        length: u64,
+    ) -> BoxFuture<'_, std::io::Result<Bytes>> {
+        async move {
+            let mut results = self.get_ranges(&[(offset_from_start, length)]).await?;
+            Ok(results.remove(0))
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+
+    struct CountingReader {
+        data: Bytes,
+        fetches: Arc<AtomicUsize>,
+    }
+
+    impl AsyncChunkReader for CountingReader {
+        fn len(&mut self) -> BoxFuture<'_, std::io::Result<u64>> {
+            let len = self.data.len() as u64;
+            async move { Ok(len) }.boxed()
+        }
+
+        fn get_bytes(
+            &mut self,
+            offset_from_start: u64,
+            length: u64,
+        ) -> BoxFuture<'_, std::io::Result<Bytes>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let data = self.data.clone();
+            async move {
+                let start = offset_from_start as usize;
+                Ok(data.slice(start..start + length as usize))
+            }
+            .boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn coalesces_nearby_ranges_into_one_fetch() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let inner = CountingReader {
+            data: Bytes::from(vec![0u8; 100]),
+            fetches: fetches.clone(),
+        };
+        let mut reader = CachingChunkReader::new(inner).with_coalesce_gap(10);
+
+        let results = reader
+            .get_ranges(&[(50, 5), (0, 5), (10, 5)])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_coalesce_across_large_gaps() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let inner = CountingReader {
+            data: Bytes::from(vec![0u8; 1000]),
+            fetches: fetches.clone(),
+        };
+        let mut reader = CachingChunkReader::new(inner).with_coalesce_gap(10);
+
+        reader.get_ranges(&[(0, 5), (900, 5)]).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn repeated_range_is_served_from_cache() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let inner = CountingReader {
+            data: Bytes::from(vec![0u8; 100]),
+            fetches: fetches.clone(),
+        };
+        let mut reader = CachingChunkReader::new(inner);
+
+        reader.get_bytes(0, 10).await.unwrap();
+        reader.get_bytes(0, 10).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn evicts_oldest_once_over_budget() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let inner = CountingReader {
+            data: Bytes::from(vec![0u8; 100]),
+            fetches: fetches.clone(),
+        };
+        let mut reader = CachingChunkReader::new(inner)
+            .with_coalesce_gap(0)
+            .with_cache_bytes(10);
+
+        reader.get_bytes(0, 10).await.unwrap();
+        reader.get_bytes(50, 10).await.unwrap();
+        // First entry should have been evicted to stay within the 10-byte budget.
+        reader.get_bytes(0, 10).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+}
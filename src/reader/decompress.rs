@@ -22,9 +22,10 @@ use std::io::Read;
 
 use bytes::{Bytes, BytesMut};
 use fallible_streaming_iterator::FallibleStreamingIterator;
+use rayon::prelude::*;
 use snafu::ResultExt;
 
-use crate::error::{self, OrcError, Result};
+use crate::error::{self, OrcError, OutOfSpecSnafu, Result, UnsupportedCompressionFeatureSnafu};
 use crate::proto::{self, CompressionKind};
 
 // Spec states default is 256K
@@ -36,6 +37,11 @@ pub struct Compression {
     /// No compression chunk will decompress to larger than this size.
     /// Use to size the scratch buffer appropriately.
     max_decompressed_block_size: usize,
+    /// Codec-specific compression level/quality, consulted only by the write path (a reader
+    /// must decode whatever level the writer already chose, so this has no effect here).
+    /// `Zstd` accepts 1-22, `Zlib` accepts 0-9; other codecs ignore it. `None` uses each
+    /// codec's own default.
+    level: Option<i32>,
 }
 
 impl std::fmt::Display for Compression {
@@ -49,48 +55,97 @@ impl std::fmt::Display for Compression {
 }
 
 impl Compression {
+    /// Builds a [`Compression`] for the write path, with no explicit level (each codec's
+    /// own default). Use [`Self::with_level`] to override it.
+    pub fn new(compression_type: CompressionType, max_decompressed_block_size: usize) -> Self {
+        Self {
+            compression_type,
+            max_decompressed_block_size,
+            level: None,
+        }
+    }
+
     pub fn compression_type(&self) -> CompressionType {
         self.compression_type
     }
 
+    pub fn level(&self) -> Option<i32> {
+        self.level
+    }
+
+    /// The maximum size of a decompressed block. On the write path this is the size each
+    /// stream is split into before a block is compressed.
+    pub fn block_size(&self) -> usize {
+        self.max_decompressed_block_size
+    }
+
+    /// Sets a codec-specific compression level, consulted only by the write path. `Zstd`
+    /// accepts 1-22, `Zlib` accepts 0-9; other codecs ignore it.
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Fails with [`OrcError::UnsupportedCompressionFeature`](crate::error::OrcError) if the
+    /// file declares a codec whose corresponding cargo feature was disabled at build time.
     pub(crate) fn from_proto(
         kind: proto::CompressionKind,
         compression_block_size: Option<u64>,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>> {
         let max_decompressed_block_size =
             compression_block_size.unwrap_or(DEFAULT_COMPRESSION_BLOCK_SIZE) as usize;
-        match kind {
-            CompressionKind::None => None,
-            CompressionKind::Zlib => Some(Self {
-                compression_type: CompressionType::Zlib,
-                max_decompressed_block_size,
-            }),
-            CompressionKind::Snappy => Some(Self {
-                compression_type: CompressionType::Snappy,
-                max_decompressed_block_size,
-            }),
-            CompressionKind::Lzo => Some(Self {
-                compression_type: CompressionType::Lzo,
-                max_decompressed_block_size,
-            }),
-            CompressionKind::Lz4 => Some(Self {
-                compression_type: CompressionType::Lz4,
-                max_decompressed_block_size,
-            }),
-            CompressionKind::Zstd => Some(Self {
-                compression_type: CompressionType::Zstd,
-                max_decompressed_block_size,
-            }),
-        }
+        let compression_type = match kind {
+            CompressionKind::None => return Ok(None),
+            #[cfg(feature = "zlib")]
+            CompressionKind::Zlib => CompressionType::Zlib,
+            #[cfg(not(feature = "zlib"))]
+            CompressionKind::Zlib => {
+                return UnsupportedCompressionFeatureSnafu { feature: "zlib" }.fail()
+            }
+            #[cfg(feature = "snappy")]
+            CompressionKind::Snappy => CompressionType::Snappy,
+            #[cfg(not(feature = "snappy"))]
+            CompressionKind::Snappy => {
+                return UnsupportedCompressionFeatureSnafu { feature: "snappy" }.fail()
+            }
+            #[cfg(feature = "lzo")]
+            CompressionKind::Lzo => CompressionType::Lzo,
+            #[cfg(not(feature = "lzo"))]
+            CompressionKind::Lzo => {
+                return UnsupportedCompressionFeatureSnafu { feature: "lzo" }.fail()
+            }
+            #[cfg(feature = "lz4")]
+            CompressionKind::Lz4 => CompressionType::Lz4,
+            #[cfg(not(feature = "lz4"))]
+            CompressionKind::Lz4 => {
+                return UnsupportedCompressionFeatureSnafu { feature: "lz4" }.fail()
+            }
+            #[cfg(feature = "zstd")]
+            CompressionKind::Zstd => CompressionType::Zstd,
+            #[cfg(not(feature = "zstd"))]
+            CompressionKind::Zstd => {
+                return UnsupportedCompressionFeatureSnafu { feature: "zstd" }.fail()
+            }
+        };
+        Ok(Some(Self {
+            compression_type,
+            max_decompressed_block_size,
+            level: None,
+        }))
     }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum CompressionType {
+    #[cfg(feature = "zlib")]
     Zlib,
+    #[cfg(feature = "snappy")]
     Snappy,
+    #[cfg(feature = "lzo")]
     Lzo,
+    #[cfg(feature = "lz4")]
     Lz4,
+    #[cfg(feature = "zstd")]
     Zstd,
 }
 
@@ -100,6 +155,23 @@ impl std::fmt::Display for CompressionType {
     }
 }
 
+impl From<CompressionType> for CompressionKind {
+    fn from(value: CompressionType) -> Self {
+        match value {
+            #[cfg(feature = "zlib")]
+            CompressionType::Zlib => CompressionKind::Zlib,
+            #[cfg(feature = "snappy")]
+            CompressionType::Snappy => CompressionKind::Snappy,
+            #[cfg(feature = "lzo")]
+            CompressionType::Lzo => CompressionKind::Lzo,
+            #[cfg(feature = "lz4")]
+            CompressionType::Lz4 => CompressionKind::Lz4,
+            #[cfg(feature = "zstd")]
+            CompressionType::Zstd => CompressionKind::Zstd,
+        }
+    }
+}
+
 /// Indicates length of block and whether it's compressed or not.
 #[derive(Debug, PartialEq, Eq)]
 enum CompressionHeader {
@@ -122,23 +194,84 @@ fn decode_header(bytes: [u8; 3]) -> CompressionHeader {
     }
 }
 
-pub(crate) trait DecompressorVariant: Send {
+/// Inverse of [`decode_header`], used by the write path to prefix each compression block
+/// with its length and original/compressed flag.
+pub(crate) fn encode_header(length: u32, is_original: bool) -> [u8; 3] {
+    let flag = is_original as u32;
+    let length_and_flag = (length << 1) | flag;
+    let bytes = length_and_flag.to_le_bytes();
+    [bytes[0], bytes[1], bytes[2]]
+}
+
+/// Location of one compression block within a compressed stream, as found by [`scan_blocks`]
+/// without decompressing anything.
+#[derive(Debug, Clone, Copy)]
+struct BlockDescriptor {
+    /// Offset of the block's payload, i.e. just after its 3 byte header.
+    offset: usize,
+    length: usize,
+    is_compressed: bool,
+}
+
+/// Walks `stream` reading only the 3 byte header of each compression block, recording where
+/// every block lives without decompressing it. Used to split the stream into independent
+/// units of work for [`Decompressor::new_parallel`]. Fails if a block's declared length would
+/// read past the end of `stream`.
+fn scan_blocks(stream: &[u8]) -> Result<Vec<BlockDescriptor>> {
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    while pos < stream.len() {
+        if pos + 3 > stream.len() {
+            return OutOfSpecSnafu {
+                msg: "compression block header overruns stream",
+            }
+            .fail();
+        }
+        let header = [stream[pos], stream[pos + 1], stream[pos + 2]];
+        let (length, is_compressed) = match decode_header(header) {
+            CompressionHeader::Original(length) => (length as usize, false),
+            CompressionHeader::Compressed(length) => (length as usize, true),
+        };
+        let offset = pos + 3;
+        if offset + length > stream.len() {
+            return OutOfSpecSnafu {
+                msg: "compression block length overruns stream",
+            }
+            .fail();
+        }
+        blocks.push(BlockDescriptor {
+            offset,
+            length,
+            is_compressed,
+        });
+        pos = offset + length;
+    }
+    Ok(blocks)
+}
+
+pub(crate) trait DecompressorVariant: Send + Sync {
     fn decompress_block(&self, compressed_bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()>;
 }
 
+#[cfg(feature = "zlib")]
 #[derive(Debug, Clone, Copy)]
 struct Zlib;
+#[cfg(feature = "zstd")]
 #[derive(Debug, Clone, Copy)]
 struct Zstd;
+#[cfg(feature = "snappy")]
 #[derive(Debug, Clone, Copy)]
 struct Snappy;
+#[cfg(feature = "lzo")]
 #[derive(Debug, Clone, Copy)]
 struct Lzo;
+#[cfg(feature = "lz4")]
 #[derive(Debug, Clone, Copy)]
 struct Lz4 {
     max_decompressed_block_size: usize,
 }
 
+#[cfg(feature = "zlib")]
 impl DecompressorVariant for Zlib {
     fn decompress_block(&self, compressed_bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
         let mut gz = flate2::read::DeflateDecoder::new(compressed_bytes);
@@ -148,16 +281,26 @@ impl DecompressorVariant for Zlib {
     }
 }
 
+/// Already wired up behind the `zstd` feature (see [`CompressionKind::try_from`]'s
+/// `CompressionKind::Zstd` arm and [`CompressionType::Zstd`]). Each ORC zstd chunk is exactly
+/// one complete zstd frame (the same per-chunk framing `decode_header`/`scan_blocks` already
+/// handle for every other codec), so decoding it is just handing the chunk to a frame decoder
+/// and reading it to completion. Uses [`ruzstd`]'s pure-Rust streaming decoder rather than the
+/// `zstd` crate's C bindings, so this feature pulls in no C dependency -- useful for consumers
+/// cross-compiling to targets (e.g. WASM) where linking `zstd-sys` is awkward or impossible.
+#[cfg(feature = "zstd")]
 impl DecompressorVariant for Zstd {
     fn decompress_block(&self, compressed_bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
-        let mut reader =
-            zstd::Decoder::new(compressed_bytes).context(error::BuildZstdDecoderSnafu)?;
+        let mut reader = ruzstd::StreamingDecoder::new(compressed_bytes)
+            .map_err(std::io::Error::other)
+            .context(error::BuildZstdDecoderSnafu)?;
         scratch.clear();
         reader.read_to_end(scratch).context(error::IoSnafu)?;
         Ok(())
     }
 }
 
+#[cfg(feature = "snappy")]
 impl DecompressorVariant for Snappy {
     fn decompress_block(&self, compressed_bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
         let len =
@@ -171,25 +314,24 @@ impl DecompressorVariant for Snappy {
     }
 }
 
+#[cfg(feature = "lzo")]
 impl DecompressorVariant for Lzo {
     fn decompress_block(&self, compressed_bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
         let decompressed = lzokay_native::decompress_all(compressed_bytes, None)
             .context(error::BuildLzoDecoderSnafu)?;
-        // TODO: better way to utilize scratch here
-        scratch.clear();
-        scratch.extend(decompressed);
+        *scratch = decompressed;
         Ok(())
     }
 }
 
+#[cfg(feature = "lz4")]
 impl DecompressorVariant for Lz4 {
     fn decompress_block(&self, compressed_bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
-        let decompressed =
-            lz4_flex::block::decompress(compressed_bytes, self.max_decompressed_block_size)
-                .context(error::BuildLz4DecoderSnafu)?;
-        // TODO: better way to utilize scratch here
         scratch.clear();
-        scratch.extend(decompressed);
+        scratch.resize(self.max_decompressed_block_size, 0);
+        let len = lz4_flex::block::decompress_into(compressed_bytes, scratch)
+            .context(error::BuildLz4DecoderSnafu)?;
+        scratch.truncate(len);
         Ok(())
     }
 }
@@ -199,29 +341,38 @@ fn get_decompressor_variant(
     Compression {
         compression_type,
         max_decompressed_block_size,
+        ..
     }: Compression,
 ) -> Box<dyn DecompressorVariant> {
     match compression_type {
+        #[cfg(feature = "zlib")]
         CompressionType::Zlib => Box::new(Zlib),
+        #[cfg(feature = "snappy")]
         CompressionType::Snappy => Box::new(Snappy),
+        #[cfg(feature = "lzo")]
         CompressionType::Lzo => Box::new(Lzo),
+        #[cfg(feature = "lz4")]
         CompressionType::Lz4 => Box::new(Lz4 {
             max_decompressed_block_size,
         }),
+        #[cfg(feature = "zstd")]
         CompressionType::Zstd => Box::new(Zstd),
     }
 }
 
 enum State {
     Original(Bytes),
-    Compressed(Vec<u8>),
+    Compressed(Bytes),
 }
 
 struct DecompressorIter {
     stream: BytesMut,
     current: Option<State>, // when we have compression but the value is original
     compression: Option<Box<dyn DecompressorVariant>>,
-    scratch: Vec<u8>,
+    /// Buffers reclaimed from past `State::Compressed` blocks once nothing else references
+    /// them, so `advance` can ping-pong between two allocations instead of allocating fresh
+    /// scratch space for every compressed block.
+    scratch_pool: Vec<Vec<u8>>,
 }
 
 impl DecompressorIter {
@@ -230,7 +381,18 @@ impl DecompressorIter {
             stream: BytesMut::from(stream.as_ref()),
             current: None,
             compression: compression.map(get_decompressor_variant),
-            scratch,
+            scratch_pool: vec![scratch, Vec::new()],
+        }
+    }
+
+    /// Reclaims the buffer behind the current `State::Compressed` block, if any, back into
+    /// the scratch pool, provided `get()` is no longer holding on to it elsewhere.
+    fn reclaim_current(&mut self) {
+        if let Some(State::Compressed(bytes)) = self.current.take() {
+            if let Ok(mut buf) = bytes.try_into_mut() {
+                buf.clear();
+                self.scratch_pool.push(buf.into());
+            }
         }
     }
 }
@@ -242,14 +404,14 @@ impl FallibleStreamingIterator for DecompressorIter {
 
     #[inline]
     fn advance(&mut self) -> Result<(), Self::Error> {
+        self.reclaim_current();
+
         if self.stream.is_empty() {
-            self.current = None;
             return Ok(());
         }
 
         match &self.compression {
             Some(compression) => {
-                // TODO: take stratch from current State::Compressed for re-use
                 let header = self.stream.split_to(3);
                 let header = [header[0], header[1], header[2]];
                 match decode_header(header) {
@@ -259,14 +421,14 @@ impl FallibleStreamingIterator for DecompressorIter {
                     }
                     CompressionHeader::Compressed(length) => {
                         let compressed = self.stream.split_to(length as usize);
-                        compression.decompress_block(&compressed, &mut self.scratch)?;
-                        self.current = Some(State::Compressed(std::mem::take(&mut self.scratch)));
+                        let mut scratch = self.scratch_pool.pop().unwrap_or_default();
+                        compression.decompress_block(&compressed, &mut scratch)?;
+                        self.current = Some(State::Compressed(Bytes::from(scratch)));
                     }
                 };
                 Ok(())
             }
             None => {
-                // TODO: take stratch from current State::Compressed for re-use
                 self.current = Some(State::Original(self.stream.clone().into()));
                 self.stream.clear();
                 Ok(())
@@ -283,6 +445,18 @@ impl FallibleStreamingIterator for DecompressorIter {
     }
 }
 
+impl DecompressorIter {
+    /// Returns the current block in its zero-copy [`Bytes`] form, regardless of whether it
+    /// came from an uncompressed region of the stream or was just decompressed into `scratch`.
+    #[inline]
+    fn current_bytes(&self) -> Option<&Bytes> {
+        self.current.as_ref().map(|x| match x {
+            State::Original(x) => x,
+            State::Compressed(x) => x,
+        })
+    }
+}
+
 /// A [`Read`]er fulfilling the ORC specification of reading compressed data.
 pub struct Decompressor {
     decompressor: DecompressorIter,
@@ -308,6 +482,64 @@ impl Decompressor {
             is_first: true,
         }
     }
+
+    /// Like [`Self::new`], but decompresses every block of `stream` up front across a rayon
+    /// thread pool instead of lazily as the caller reads, since each ORC compression block
+    /// carries its own header and decodes independently of its neighbours. Blocks are
+    /// reassembled in their original stream order regardless of which finishes decompressing
+    /// first. Prefer this over [`Self::new`] when `stream` spans many blocks and the caller
+    /// will consume all of it anyway, e.g. when materializing a whole column stream rather
+    /// than reading it incrementally.
+    pub fn new_parallel(stream: Bytes, compression: Option<Compression>) -> Result<Self> {
+        let Some(compression) = compression else {
+            return Ok(Self::new(stream, None, vec![]));
+        };
+        let blocks = scan_blocks(&stream)?;
+        let variant = get_decompressor_variant(compression);
+        let decompressed_blocks = blocks
+            .par_iter()
+            .map(|block| {
+                let compressed_bytes = &stream[block.offset..block.offset + block.length];
+                let mut scratch = Vec::with_capacity(compression.max_decompressed_block_size);
+                if block.is_compressed {
+                    variant.decompress_block(compressed_bytes, &mut scratch)?;
+                } else {
+                    scratch.extend_from_slice(compressed_bytes);
+                }
+                Ok(scratch)
+            })
+            .collect::<Result<Vec<Vec<u8>>>>()?;
+        let mut buffer = Vec::with_capacity(decompressed_blocks.iter().map(Vec::len).sum());
+        decompressed_blocks
+            .into_iter()
+            .for_each(|block| buffer.extend(block));
+        Ok(Self::new(Bytes::from(buffer), None, vec![]))
+    }
+
+    /// Returns a zero-copy view of the next `len` bytes, provided they lie entirely within
+    /// the current decompressed (or originally uncompressed) block, advancing past them.
+    ///
+    /// Returns `None` when `len` would span a block boundary (including when nothing has
+    /// been buffered yet), in which case the caller should fall back to [`Read::read`]/
+    /// [`Read::read_to_end`], which copy but can stitch multiple blocks together.
+    pub(crate) fn next_contiguous(&mut self, len: usize) -> Option<Bytes> {
+        if self.is_first {
+            self.is_first = false;
+            self.decompressor.advance().ok()?;
+        }
+        let mut current = self.decompressor.current_bytes()?;
+        if self.offset == current.len() {
+            self.decompressor.advance().ok()?;
+            self.offset = 0;
+            current = self.decompressor.current_bytes()?;
+        }
+        if self.offset + len > current.len() {
+            return None;
+        }
+        let slice = current.slice(self.offset..self.offset + len);
+        self.offset += len;
+        Some(slice)
+    }
 }
 
 impl std::io::Read for Decompressor {
@@ -360,6 +592,19 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn encode_roundtrips_through_decode_header() {
+        for (length, is_original) in [(5, true), (100_000, false), (0, false)] {
+            let header = encode_header(length, is_original);
+            let expected = if is_original {
+                CompressionHeader::Original(length)
+            } else {
+                CompressionHeader::Compressed(length)
+            };
+            assert_eq!(expected, decode_header(header));
+        }
+    }
+
     #[test]
     fn decode_compressed() {
         // 100_000 compressed = [0x40, 0x0d, 0x03] = [0b01000000, 0b00001101, 0b00000011]
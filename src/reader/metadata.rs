@@ -44,12 +44,13 @@
 use std::collections::HashMap;
 use std::io::Read;
 
-use bytes::{Bytes, BytesMut};
+use bytes::Bytes;
 use prost::Message;
 use snafu::{ensure, OptionExt, ResultExt};
 
 use crate::compression::{Compression, Decompressor};
 use crate::error::{self, EmptyFileSnafu, OutOfSpecSnafu, Result};
+use crate::predicate::Predicate;
 use crate::proto::{self, Footer, Metadata, PostScript};
 use crate::schema::RootDataType;
 use crate::statistics::ColumnStatistics;
@@ -59,6 +60,44 @@ use crate::reader::ChunkReader;
 
 const DEFAULT_FOOTER_SIZE: u64 = 16 * 1024;
 
+/// Check that `postscript_len`, `footer_length`, and `metadata_length` -- all taken straight
+/// from an untrusted file tail -- are consistent with `file_len` before anything derived from
+/// them is used to size an allocation or as a subtraction offset. A crafted file can set these
+/// arbitrarily large, which would otherwise underflow the `file_len - 1 - postscript_len -
+/// footer_length - metadata_length` offset computation (panicking in debug, wrapping in
+/// release) or drive an oversized allocation before any validation has run.
+///
+/// Returns the validated offset of the start of the Metadata section on success.
+fn validate_tail_lengths(
+    file_len: u64,
+    postscript_len: u64,
+    footer_length: u64,
+    metadata_length: u64,
+) -> Result<u64> {
+    postscript_len
+        .checked_add(footer_length)
+        .and_then(|sum| sum.checked_add(metadata_length))
+        .and_then(|sum| sum.checked_add(1))
+        .filter(|&total| total <= file_len)
+        .map(|total| file_len - total)
+        .context(OutOfSpecSnafu {
+            msg: "postscript, footer, and metadata lengths are inconsistent with the file's length",
+        })
+}
+
+/// Grow `buffer` to hold `additional` more bytes, surfacing an allocation failure as
+/// `OutOfSpec` instead of aborting the process -- `footer_length`/`metadata_length` come
+/// straight from the file tail, so a corrupt or malicious file must not be able to force an
+/// unbounded/OOM-inducing allocation before the lengths have been used for anything else.
+fn try_reserve(buffer: &mut Vec<u8>, additional: usize) -> Result<()> {
+    buffer.try_reserve_exact(additional).map_err(|source| {
+        error::OutOfSpecSnafu {
+            msg: format!("failed to allocate {additional} bytes for file tail buffer: {source}"),
+        }
+        .build()
+    })
+}
+
 /// The file's metadata.
 #[derive(Debug, Clone)]
 pub struct FileMetadata {
@@ -70,6 +109,9 @@ pub struct FileMetadata {
     column_statistics: Vec<ColumnStatistics>,
     stripes: Vec<StripeMetadata>,
     user_custom_metadata: HashMap<String, Vec<u8>>,
+    /// Number of rows per entry in each stripe's `RowIndex` stream, i.e. the row range a
+    /// single index/bloom filter entry covers. `None` if the writer didn't build row indexes.
+    row_index_stride: Option<u32>,
 }
 
 impl FileMetadata {
@@ -79,7 +121,7 @@ impl FileMetadata {
         metadata: &proto::Metadata,
     ) -> Result<Self> {
         let compression =
-            Compression::from_proto(postscript.compression(), postscript.compression_block_size);
+            Compression::from_proto(postscript.compression(), postscript.compression_block_size)?;
         let root_data_type = RootDataType::from_proto(&footer.types)?;
         let number_of_rows = footer.number_of_rows();
         let column_statistics = footer
@@ -132,6 +174,7 @@ impl FileMetadata {
             column_statistics,
             stripes,
             user_custom_metadata,
+            row_index_stride: footer.row_index_stride,
         })
     }
 
@@ -155,6 +198,29 @@ impl FileMetadata {
         &self.stripes
     }
 
+    /// Like [`stripe_metadatas`](Self::stripe_metadatas), but filtered down to the stripes
+    /// `predicate` can't rule out via [`StripeMetadata::can_match`], so a caller that wants
+    /// to stream in stripes manually (rather than through
+    /// [`ArrowReaderBuilder::with_predicate`](crate::arrow_reader::ArrowReaderBuilder::with_predicate))
+    /// can skip [`Stripe::new`](crate::stripe::Stripe::new)/[`Stripe::new_async`](crate::stripe::Stripe::new_async)
+    /// -- and the stream reads they'd otherwise issue -- for stripes no row of which could
+    /// satisfy it.
+    ///
+    /// `predicate` is resolved against [`root_data_type`](Self::root_data_type) once up
+    /// front; a column name `predicate` references that doesn't exist on this file falls
+    /// back to keeping every stripe, the same as [`Predicate::resolve`] documents.
+    pub fn stripe_metadatas_matching<'a>(
+        &'a self,
+        predicate: &Predicate,
+    ) -> impl Iterator<Item = &'a StripeMetadata> + 'a {
+        let resolved = predicate.resolve(&self.root_data_type);
+        self.stripes.iter().filter(move |stripe| {
+            resolved
+                .as_ref()
+                .map_or(true, |resolved| stripe.can_match(resolved))
+        })
+    }
+
     pub fn user_custom_metadata(&self) -> &HashMap<String, Vec<u8>> {
         &self.user_custom_metadata
     }
@@ -162,6 +228,13 @@ impl FileMetadata {
     pub fn file_format_version(&self) -> &str {
         &self.file_format_version
     }
+
+    /// Number of rows covered by each entry of a stripe's `RowIndex`/`BloomFilter` streams,
+    /// i.e. the granularity [`crate::bloom_filter::BloomFilter`] pruning operates at.
+    /// `None` if the writer didn't record row indexes for this file.
+    pub fn row_index_stride(&self) -> Option<u32> {
+        self.row_index_stride
+    }
 }
 
 pub fn read_metadata<R: ChunkReader>(reader: &mut R) -> Result<FileMetadata> {
@@ -180,7 +253,9 @@ pub fn read_metadata<R: ChunkReader>(reader: &mut R) -> Result<FileMetadata> {
 
     // The final byte of the file contains the serialized length of the Postscript,
     // which must be less than 256 bytes.
-    let postscript_len = tail_bytes[tail_bytes.len() - 1] as u64;
+    let postscript_len = *tail_bytes.last().context(OutOfSpecSnafu {
+        msg: "File tail is empty",
+    })? as u64;
     tail_bytes.truncate(tail_bytes.len() - 1);
 
     if tail_bytes.len() < postscript_len as usize {
@@ -192,7 +267,7 @@ pub fn read_metadata<R: ChunkReader>(reader: &mut R) -> Result<FileMetadata> {
     let postscript = PostScript::decode(&tail_bytes[tail_bytes.len() - postscript_len as usize..])
         .context(error::DecodeProtoSnafu)?;
     let compression =
-        Compression::from_proto(postscript.compression(), postscript.compression_block_size);
+        Compression::from_proto(postscript.compression(), postscript.compression_block_size)?;
     tail_bytes.truncate(tail_bytes.len() - postscript_len as usize);
 
     let footer_length = postscript.footer_length.context(error::OutOfSpecSnafu {
@@ -202,16 +277,20 @@ pub fn read_metadata<R: ChunkReader>(reader: &mut R) -> Result<FileMetadata> {
         msg: "Metadata length is empty",
     })?;
 
+    let combined_length = footer_length.checked_add(metadata_length).context(OutOfSpecSnafu {
+        msg: "footer and metadata lengths overflow when combined",
+    })?;
+
     // Ensure we have enough bytes for Footer and Metadata
-    let mut tail_bytes = if footer_length + metadata_length > tail_bytes.len() as u64 {
+    let mut tail_bytes = if combined_length > tail_bytes.len() as u64 {
         // Need second read
-        // -1 is the postscript length byte
-        let offset = file_len - 1 - postscript_len - footer_length - metadata_length;
-        let bytes_to_read = (footer_length + metadata_length) - tail_bytes.len() as u64;
+        let offset = validate_tail_lengths(file_len, postscript_len, footer_length, metadata_length)?;
+        let bytes_to_read = combined_length - tail_bytes.len() as u64;
         let prepend_bytes = reader
             .get_bytes(offset, bytes_to_read)
             .context(error::IoSnafu)?;
-        let mut all_bytes = BytesMut::with_capacity(prepend_bytes.len() + tail_bytes.len());
+        let mut all_bytes = Vec::new();
+        try_reserve(&mut all_bytes, prepend_bytes.len() + tail_bytes.len())?;
         all_bytes.extend_from_slice(&prepend_bytes);
         all_bytes.extend_from_slice(&tail_bytes);
         all_bytes.into()
@@ -219,12 +298,24 @@ pub fn read_metadata<R: ChunkReader>(reader: &mut R) -> Result<FileMetadata> {
         tail_bytes
     };
 
+    ensure!(
+        tail_bytes.len() >= footer_length as usize,
+        OutOfSpecSnafu {
+            msg: "File too small for given footer length",
+        }
+    );
     let footer = deserialize_footer(
         tail_bytes.slice(tail_bytes.len() - footer_length as usize..),
         compression,
     )?;
     tail_bytes.truncate(tail_bytes.len() - footer_length as usize);
 
+    ensure!(
+        tail_bytes.len() >= metadata_length as usize,
+        OutOfSpecSnafu {
+            msg: "File too small for given metadata length",
+        }
+    );
     let metadata = deserialize_footer_metadata(
         tail_bytes.slice(tail_bytes.len() - metadata_length as usize..),
         compression,
@@ -253,7 +344,9 @@ pub async fn read_metadata_async<R: super::AsyncChunkReader>(
 
     // The final byte of the file contains the serialized length of the Postscript,
     // which must be less than 256 bytes.
-    let postscript_len = tail_bytes[tail_bytes.len() - 1] as u64;
+    let postscript_len = *tail_bytes.last().context(OutOfSpecSnafu {
+        msg: "File tail is empty",
+    })? as u64;
     tail_bytes.truncate(tail_bytes.len() - 1);
 
     if tail_bytes.len() < postscript_len as usize {
@@ -265,7 +358,7 @@ pub async fn read_metadata_async<R: super::AsyncChunkReader>(
     let postscript = PostScript::decode(&tail_bytes[tail_bytes.len() - postscript_len as usize..])
         .context(error::DecodeProtoSnafu)?;
     let compression =
-        Compression::from_proto(postscript.compression(), postscript.compression_block_size);
+        Compression::from_proto(postscript.compression(), postscript.compression_block_size)?;
     tail_bytes.truncate(tail_bytes.len() - postscript_len as usize);
 
     let footer_length = postscript.footer_length.context(error::OutOfSpecSnafu {
@@ -275,17 +368,21 @@ pub async fn read_metadata_async<R: super::AsyncChunkReader>(
         msg: "Metadata length is empty",
     })?;
 
+    let combined_length = footer_length.checked_add(metadata_length).context(OutOfSpecSnafu {
+        msg: "footer and metadata lengths overflow when combined",
+    })?;
+
     // Ensure we have enough bytes for Footer and Metadata
-    let mut tail_bytes = if footer_length + metadata_length > tail_bytes.len() as u64 {
+    let mut tail_bytes = if combined_length > tail_bytes.len() as u64 {
         // Need second read
-        // -1 is the postscript length byte
-        let offset = file_len - 1 - postscript_len - footer_length - metadata_length;
-        let bytes_to_read = (footer_length + metadata_length) - tail_bytes.len() as u64;
+        let offset = validate_tail_lengths(file_len, postscript_len, footer_length, metadata_length)?;
+        let bytes_to_read = combined_length - tail_bytes.len() as u64;
         let prepend_bytes = reader
             .get_bytes(offset, bytes_to_read)
             .await
             .context(error::IoSnafu)?;
-        let mut all_bytes = BytesMut::with_capacity(prepend_bytes.len() + tail_bytes.len());
+        let mut all_bytes = Vec::new();
+        try_reserve(&mut all_bytes, prepend_bytes.len() + tail_bytes.len())?;
         all_bytes.extend_from_slice(&prepend_bytes);
         all_bytes.extend_from_slice(&tail_bytes);
         all_bytes.into()
@@ -293,12 +390,24 @@ pub async fn read_metadata_async<R: super::AsyncChunkReader>(
         tail_bytes
     };
 
+    ensure!(
+        tail_bytes.len() >= footer_length as usize,
+        OutOfSpecSnafu {
+            msg: "File too small for given footer length",
+        }
+    );
     let footer = deserialize_footer(
         tail_bytes.slice(tail_bytes.len() - footer_length as usize..),
         compression,
     )?;
     tail_bytes.truncate(tail_bytes.len() - footer_length as usize);
 
+    ensure!(
+        tail_bytes.len() >= metadata_length as usize,
+        OutOfSpecSnafu {
+            msg: "File too small for given metadata length",
+        }
+    );
     let metadata = deserialize_footer_metadata(
         tail_bytes.slice(tail_bytes.len() - metadata_length as usize..),
         compression,
@@ -307,6 +416,10 @@ pub async fn read_metadata_async<R: super::AsyncChunkReader>(
     FileMetadata::from_proto(&postscript, &footer, &metadata)
 }
 
+/// `Decompressor` dispatches on `compression.compression_type()` the same way for every
+/// stream in the file, so the footer gets Snappy/LZO/LZ4/Zstd support for free from
+/// [`crate::reader::decompress`]'s `DecompressorVariant` impls -- there's nothing
+/// footer-specific to wire up here.
 fn deserialize_footer(bytes: Bytes, compression: Option<Compression>) -> Result<Footer> {
     let mut buffer = vec![];
     Decompressor::new(bytes, compression, vec![])
@@ -322,3 +435,62 @@ fn deserialize_footer_metadata(bytes: Bytes, compression: Option<Compression>) -
         .context(error::IoSnafu)?;
     Metadata::decode(buffer.as_slice()).context(error::DecodeProtoSnafu)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow_writer::serialize_postscript;
+
+    /// Build a file tail consisting of `prefix_len` zero bytes followed by a PostScript
+    /// claiming the given footer/metadata lengths, and the trailing length byte.
+    fn build_tail(footer_length: u64, metadata_length: u64, prefix_len: usize) -> Bytes {
+        let postscript = serialize_postscript(footer_length, metadata_length, None);
+        let mut postscript_bytes = Vec::new();
+        postscript.encode(&mut postscript_bytes).unwrap();
+
+        let mut bytes = vec![0u8; prefix_len];
+        bytes.extend_from_slice(&postscript_bytes);
+        bytes.push(postscript_bytes.len() as u8);
+        bytes.into()
+    }
+
+    #[test]
+    fn read_metadata_rejects_empty_file() {
+        let mut reader = Bytes::new();
+        assert!(read_metadata(&mut reader).is_err());
+    }
+
+    #[test]
+    fn read_metadata_rejects_postscript_length_exceeding_file() {
+        // A single byte claiming a 255-byte postscript, in a file that isn't nearly that big.
+        let mut reader = Bytes::from_static(&[255]);
+        assert!(read_metadata(&mut reader).is_err());
+    }
+
+    #[test]
+    fn read_metadata_rejects_oversized_footer_and_metadata_lengths() {
+        // footer_length/metadata_length claim far more data than the file actually has, which
+        // would previously underflow the `file_len - 1 - postscript_len - footer_length -
+        // metadata_length` offset computation instead of returning a clean error.
+        let mut reader = build_tail(u64::MAX / 2, u64::MAX / 2, 8);
+        let err = read_metadata(&mut reader).unwrap_err();
+        assert!(matches!(err, error::OrcError::OutOfSpec { .. }));
+    }
+
+    #[test]
+    fn read_metadata_rejects_lengths_that_overflow_when_combined() {
+        // footer_length + metadata_length overflows u64 outright; must error, not panic.
+        let mut reader = build_tail(u64::MAX, u64::MAX, 8);
+        let err = read_metadata(&mut reader).unwrap_err();
+        assert!(matches!(err, error::OrcError::OutOfSpec { .. }));
+    }
+
+    #[test]
+    fn read_metadata_rejects_truncated_tail() {
+        // footer_length/metadata_length are individually plausible but the file is too small
+        // to actually contain that much data once the prefix is accounted for.
+        let mut reader = build_tail(1_000_000, 1_000_000, 8);
+        let err = read_metadata(&mut reader).unwrap_err();
+        assert!(matches!(err, error::OrcError::OutOfSpec { .. }));
+    }
+}
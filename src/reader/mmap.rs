@@ -0,0 +1,150 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A memory-mapped [`ChunkReader`] for local files, gated behind the `mmap` feature.
+//!
+//! [`ChunkReader for File`](super::ChunkReader) re-`try_clone`s the file descriptor and
+//! seeks on every [`get_read`](super::ChunkReader::get_read)/[`get_bytes`](super::ChunkReader::get_bytes),
+//! which costs a syscall (and a `read_exact` copy into a freshly allocated buffer) per
+//! range. [`MmapChunkReader`] instead maps the file once up front and serves every
+//! `get_bytes` call by slicing the mapping -- the OS faults pages in lazily as they're
+//! touched, and [`Bytes::from_owner`] lets the returned [`Bytes`] share the mapping instead
+//! of copying out of it.
+
+use std::fs::File;
+use std::io;
+use std::sync::Arc;
+
+use bytes::{Buf, Bytes};
+use memmap2::Mmap;
+
+use super::ChunkReader;
+
+/// A [`ChunkReader`] backed by a whole-file memory mapping, shared (via [`Arc`]) across
+/// every [`Bytes`] handed out by [`get_bytes`](ChunkReader::get_bytes) so the mapping stays
+/// alive for as long as any slice of it is still in use.
+#[derive(Clone)]
+pub struct MmapChunkReader {
+    mmap: Arc<Mmap>,
+}
+
+impl MmapChunkReader {
+    /// Map the whole of `file` into memory.
+    ///
+    /// # Safety
+    ///
+    /// This inherits the same safety caveat as [`Mmap::map`]: mutating or truncating the
+    /// underlying file while the mapping is alive (including from another process) is
+    /// undefined behaviour, since the kernel may hand back a page in either its old or new
+    /// state rather than erroring. Callers must ensure `file` isn't written to concurrently.
+    pub unsafe fn new(file: &File) -> io::Result<Self> {
+        let mmap = Mmap::map(file)?;
+        Ok(Self {
+            mmap: Arc::new(mmap),
+        })
+    }
+
+    fn slice(&self, offset_from_start: u64, length: u64) -> io::Result<Bytes> {
+        let start = offset_from_start as usize;
+        let end = start
+            .checked_add(length as usize)
+            .filter(|&end| end <= self.mmap.len())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "requested range is out of bounds of the memory-mapped file",
+                )
+            })?;
+        // Shares the mapping via the `Arc` instead of copying out of it; `end - start`
+        // bytes of file-backed memory become reachable through this `Bytes`, but nothing is
+        // read off disk until (and unless) those pages are actually touched.
+        Ok(Bytes::from_owner(self.mmap.clone()).slice(start..end))
+    }
+}
+
+impl ChunkReader for MmapChunkReader {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn len(&self) -> u64 {
+        self.mmap.len() as u64
+    }
+
+    fn get_read(&self, offset_from_start: u64) -> io::Result<Self::T> {
+        let length = self.len().saturating_sub(offset_from_start);
+        Ok(self.slice(offset_from_start, length)?.reader())
+    }
+
+    fn get_bytes(&self, offset_from_start: u64, length: u64) -> io::Result<Bytes> {
+        self.slice(offset_from_start, length)
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_mmap_chunk_reader {
+    use std::io;
+
+    use bytes::Bytes;
+    use futures_util::future::BoxFuture;
+    use futures_util::FutureExt;
+
+    use super::MmapChunkReader;
+    use crate::reader::{AsyncChunkReader, ChunkReader};
+
+    /// Async counterpart to [`MmapChunkReader`], for call sites that only have an
+    /// [`AsyncChunkReader`] to hand (e.g. generic stripe-reading code shared with the
+    /// object-store path). Matches the pattern used by `fmmap`'s `AsyncMmapFile`: the
+    /// mapping itself is never actually asynchronous (a page fault just blocks the calling
+    /// thread like any other memory access), so every method here resolves immediately --
+    /// this exists purely so local files can be dropped into async call sites without an
+    /// extra `spawn_blocking` or a real `tokio::fs::File` read.
+    #[derive(Clone)]
+    pub struct AsyncMmapChunkReader {
+        inner: MmapChunkReader,
+    }
+
+    impl AsyncMmapChunkReader {
+        /// See [`MmapChunkReader::new`] for the safety requirements this inherits.
+        ///
+        /// # Safety
+        ///
+        /// Same as [`MmapChunkReader::new`].
+        pub unsafe fn new(file: &std::fs::File) -> io::Result<Self> {
+            Ok(Self {
+                inner: MmapChunkReader::new(file)?,
+            })
+        }
+    }
+
+    impl AsyncChunkReader for AsyncMmapChunkReader {
+        fn len(&mut self) -> BoxFuture<'_, io::Result<u64>> {
+            let len = ChunkReader::len(&self.inner);
+            async move { Ok(len) }.boxed()
+        }
+
+        fn get_bytes(
+            &mut self,
+            offset_from_start: u64,
+            length: u64,
+        ) -> BoxFuture<'_, io::Result<Bytes>> {
+            let result = self.inner.slice(offset_from_start, length);
+            async move { result }.boxed()
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub use async_mmap_chunk_reader::AsyncMmapChunkReader;
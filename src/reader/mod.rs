@@ -15,7 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
+#[cfg(feature = "async")]
+pub mod caching;
 pub mod metadata;
+#[cfg(feature = "mmap")]
+pub mod mmap;
 
 use std::fs::File;
 use std::io::{BufReader, Read, Seek, SeekFrom};
@@ -37,7 +41,7 @@ pub trait ChunkReader {
 
     /// Read bytes from an offset with specific length.
     fn get_bytes(&self, offset_from_start: u64, length: u64) -> std::io::Result<Bytes> {
-        let mut bytes = vec![0; length as usize];
+        let mut bytes = uninit_buffer(length as usize);
         self.get_read(offset_from_start)?
             .take(length)
             .read_exact(&mut bytes)?;
@@ -45,6 +49,24 @@ pub trait ChunkReader {
     }
 }
 
+/// A `Vec<u8>` of `len` bytes, skipping the zero-fill `vec![0; len]` does: every byte is
+/// about to be overwritten by a `read_exact` the caller makes right after, so there's no
+/// need to initialize them first.
+///
+/// # Safety invariant on the caller
+///
+/// The full `len` bytes must be written (e.g. via a successful `read_exact`) before the
+/// buffer is read from or handed out (as a `Bytes`, a `&[u8]`, etc.) -- `u8` has no invalid
+/// bit patterns, so `Vec::set_len` here is sound, but only because nothing observes the
+/// bytes in between.
+fn uninit_buffer(len: usize) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(len);
+    // SAFETY: see the safety invariant documented above; every caller in this module
+    // fills the whole buffer via `read_exact` immediately afterwards.
+    unsafe { buffer.set_len(len) };
+    buffer
+}
+
 impl ChunkReader for File {
     type T = BufReader<File>;
 
@@ -107,7 +129,7 @@ mod async_chunk_reader {
         ) -> BoxFuture<'_, std::io::Result<Bytes>> {
             async move {
                 self.seek(SeekFrom::Start(offset_from_start)).await?;
-                let mut buffer = vec![0; length as usize];
+                let mut buffer = uninit_buffer(length as usize);
                 self.read_exact(&mut buffer).await?;
                 Ok(buffer.into())
             }
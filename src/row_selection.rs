@@ -0,0 +1,126 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Maps a per-stride "may match" mask (e.g. from [`bloom_filter`](crate::bloom_filter)
+//! pruning) to the row ranges of a stripe worth materializing.
+//!
+//! [`NaiveStripeDecoder`](crate::array_decoder::NaiveStripeDecoder) uses this two ways: a
+//! batch that falls entirely outside the selection is skipped via
+//! [`ArrayBatchDecoder::skip`](crate::array_decoder::ArrayBatchDecoder::skip) instead of
+//! decoded, while a batch straddling the selection's boundary is still decoded in full and
+//! then filtered down. Either way this only changes which rows get decoded/kept, not how
+//! much is read off the underlying streams: actually skipping the *I/O* (or seeking an
+//! RLE/byte decoder mid-stream instead of decoding-and-discarding it) for unselected strides
+//! would additionally require seeking each column's stream to the position the `RowIndex`
+//! stream recorded for the first surviving stride, which this crate doesn't parse yet.
+
+use std::ops::Range;
+
+/// A set of increasing, non-overlapping row ranges selected for keeping out of a stripe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RowSelection {
+    ranges: Vec<Range<usize>>,
+}
+
+impl RowSelection {
+    /// Builds a selection from a per-stride mask: `mask[i]` is whether stride `i` (rows
+    /// `[i * row_index_stride, (i + 1) * row_index_stride)`, clipped to `number_of_rows`)
+    /// may contain a matching row. Adjacent surviving strides are merged into one range.
+    pub(crate) fn from_stride_mask(
+        mask: &[bool],
+        row_index_stride: usize,
+        number_of_rows: usize,
+    ) -> Self {
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for (i, &may_match) in mask.iter().enumerate() {
+            if !may_match {
+                continue;
+            }
+            let start = i * row_index_stride;
+            if start >= number_of_rows {
+                break;
+            }
+            let end = ((i + 1) * row_index_stride).min(number_of_rows);
+            match ranges.last_mut() {
+                Some(last) if last.end == start => last.end = end,
+                _ => ranges.push(start..end),
+            }
+        }
+        Self { ranges }
+    }
+
+    /// A selection that keeps every row, used when no stride mask narrows anything down.
+    pub(crate) fn all(number_of_rows: usize) -> Self {
+        Self {
+            ranges: if number_of_rows == 0 {
+                Vec::new()
+            } else {
+                vec![0..number_of_rows]
+            },
+        }
+    }
+
+    /// A boolean mask covering just `range`, one entry per row in it, true where selected.
+    pub(crate) fn mask_for_range(&self, range: Range<usize>) -> Vec<bool> {
+        let mut mask = vec![false; range.len()];
+        for selected in &self.ranges {
+            let start = selected.start.max(range.start);
+            let end = selected.end.min(range.end);
+            if start < end {
+                mask[start - range.start..end - range.start].fill(true);
+            }
+        }
+        mask
+    }
+
+    #[cfg(test)]
+    pub(crate) fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_adjacent_surviving_strides() {
+        let selection = RowSelection::from_stride_mask(&[true, true, false, true], 10, 35);
+        assert_eq!(selection.ranges(), &[0..20, 30..35]);
+    }
+
+    #[test]
+    fn empty_mask_selects_nothing() {
+        let selection = RowSelection::from_stride_mask(&[false, false], 10, 20);
+        assert!(selection.ranges().is_empty());
+    }
+
+    #[test]
+    fn all_keeps_every_row() {
+        assert_eq!(RowSelection::all(5).ranges(), &[0..5]);
+        assert_eq!(RowSelection::all(0).ranges(), &[]);
+    }
+
+    #[test]
+    fn mask_for_range_clips_to_requested_window() {
+        let selection = RowSelection::from_stride_mask(&[false, true], 10, 20);
+        assert_eq!(
+            selection.mask_for_range(5..15),
+            vec![false, false, false, false, false, true, true, true, true, true]
+        );
+    }
+}
@@ -21,11 +21,14 @@ use std::sync::Arc;
 
 use snafu::{ensure, OptionExt};
 
-use crate::error::{NoTypesSnafu, Result, UnexpectedSnafu};
+use crate::error::{NoTypesSnafu, OutOfSpecSnafu, Result, UnexpectedSnafu};
 use crate::projection::ProjectionMask;
 use crate::proto;
 
-use arrow::datatypes::{DataType as ArrowDataType, Field, Schema, TimeUnit, UnionMode};
+use arrow::datatypes::{
+    DataType as ArrowDataType, Decimal128Type, DecimalType, Field, Fields, Schema, TimeUnit,
+    UnionMode,
+};
 
 /// Represents the root data type of the ORC file. Contains multiple named child types
 /// which map to the columns available. Allows projecting only specific columns from
@@ -41,6 +44,12 @@ use arrow::datatypes::{DataType as ArrowDataType, Field, Schema, TimeUnit, Union
 #[derive(Debug, Clone)]
 pub struct RootDataType {
     children: Vec<NamedColumn>,
+    /// Column-index-keyed ORC `Type` attributes (arbitrary protobuf key/value pairs a
+    /// writer attached to a type, e.g. `iceberg.field-id`), captured by [`Self::from_proto`]
+    /// and copied into the corresponding Arrow `Field`'s metadata by
+    /// [`Self::create_arrow_schema`]/[`DataType::to_arrow_data_type`]. Indices are preserved
+    /// by [`Self::project`], so wrapped in an `Arc` to keep that cheap.
+    attributes: Arc<ColumnAttributes>,
 }
 
 impl RootDataType {
@@ -54,36 +63,85 @@ impl RootDataType {
         &self.children
     }
 
-    /// Convert into an Arrow schema.
-    pub fn create_arrow_schema(&self, user_metadata: &HashMap<String, String>) -> Schema {
+    /// Convert into an Arrow schema, encoding timestamp columns with `timestamp_unit` and
+    /// `output_timestamp_tz`, string-like columns with `dictionary_key_type` or
+    /// `use_utf8_view`, and map columns' generated fields with `map_field_names` (see
+    /// [`DataType::to_arrow_data_type`]). Each field's metadata is populated from the source
+    /// ORC type's attributes, if any (see [`ColumnAttributes`]).
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_arrow_schema(
+        &self,
+        user_metadata: &HashMap<String, String>,
+        timestamp_unit: TimeUnit,
+        dictionary_key_type: Option<&ArrowDataType>,
+        map_field_names: &MapFieldNames,
+        output_timestamp_tz: Option<&Arc<str>>,
+        use_utf8_view: bool,
+    ) -> Schema {
         let fields = self
             .children
             .iter()
             .map(|col| {
-                let dt = col.data_type().to_arrow_data_type();
+                let dt = col.data_type().to_arrow_data_type(
+                    timestamp_unit,
+                    dictionary_key_type,
+                    map_field_names,
+                    output_timestamp_tz,
+                    use_utf8_view,
+                    &self.attributes,
+                );
                 Field::new(col.name(), dt, true)
+                    .with_metadata(field_metadata_for(col.data_type().column_index(), &self.attributes))
             })
             .collect::<Vec<_>>();
         Schema::new_with_metadata(fields, user_metadata.clone())
     }
 
     /// Create new root data type based on mask of columns to project.
+    ///
+    /// Unlike a plain top-level filter, this recurses into [`DataType::Struct`] fields so
+    /// a mask built by [`ProjectionMask::paths`] to select a single nested field (e.g.
+    /// `order.customer.id`) actually drops its unprojected struct siblings, rather than
+    /// pulling in the whole `order.customer` subtree just because `order` was selected.
+    ///
+    /// This is what makes pruning work at the decoder level, not just in the output
+    /// `Schema`: [`Stripe::new`](crate::stripe::Stripe::new) projects the root type before
+    /// `array_decoder_factory` ever walks it, so a dropped struct/list/map field has no
+    /// [`NamedColumn`] left for a child decoder to be built from, and its streams are never
+    /// fetched in the first place.
     pub fn project(&self, mask: &ProjectionMask) -> Self {
-        // TODO: fix logic here to account for nested projection
         let children = self
             .children
             .iter()
-            .filter(|col| mask.is_index_projected(col.data_type().column_index()))
-            .map(|col| col.to_owned())
+            .filter_map(|col| project_named_column(col, mask))
             .collect::<Vec<_>>();
-        Self { children }
+        Self {
+            children,
+            attributes: self.attributes.clone(),
+        }
+    }
+
+    /// Column index of every `Timestamp`/`TimestampWithLocalTimezone` column reachable from
+    /// the root, recursing into nested types the same way [`Self::project`] does. Used by
+    /// [`ArrowReaderBuilder::validate_timestamp_range`](crate::arrow_reader::ArrowReaderBuilder::validate_timestamp_range)
+    /// to check each one's statistics against a requested output [`TimeUnit`].
+    pub(crate) fn timestamp_column_indices(&self) -> Vec<usize> {
+        let mut indices = Vec::new();
+        for child in &self.children {
+            child.data_type().collect_timestamp_column_indices(&mut indices);
+        }
+        indices
     }
 
     /// Construct from protobuf types.
     pub(crate) fn from_proto(types: &[proto::Type]) -> Result<Self> {
         ensure!(!types.is_empty(), NoTypesSnafu {});
         let children = parse_struct_children_from_proto(types, 0)?;
-        Ok(Self { children })
+        let attributes = Arc::new(column_attributes_from_proto(types));
+        Ok(Self {
+            children,
+            attributes,
+        })
     }
 }
 
@@ -119,6 +177,23 @@ impl Display for NamedColumn {
     }
 }
 
+/// Projects a single named column, recursing into struct fields, or returns `None` if
+/// `mask` doesn't project this column (or any of its descendants) at all.
+fn project_named_column(col: &NamedColumn, mask: &ProjectionMask) -> Option<NamedColumn> {
+    col.data_type().project(mask).map(|data_type| NamedColumn {
+        name: col.name().to_owned(),
+        data_type,
+    })
+}
+
+/// Like [`DataType::project`], but for a required child (a list's element, a map's key or
+/// value, a union variant) that can't simply be dropped: falls back to an unpruned clone
+/// of `data_type` if `mask` doesn't project its column index at all, rather than losing the
+/// type entirely.
+fn project_or_keep(data_type: &DataType, mask: &ProjectionMask) -> DataType {
+    data_type.project(mask).unwrap_or_else(|| data_type.clone())
+}
+
 /// Helper function since this is duplicated for [`RootDataType`] and [`DataType::Struct`]
 /// parsing from proto.
 fn parse_struct_children_from_proto(
@@ -152,6 +227,101 @@ fn parse_struct_children_from_proto(
     Ok(children)
 }
 
+/// Column-index-keyed ORC `Type` attributes: a `Type`'s own arbitrary protobuf key/value
+/// pairs (e.g. `iceberg.field-id`, a semantic tag), keyed by that type's column index. See
+/// [`RootDataType::from_proto`]/[`field_metadata_for`].
+pub type ColumnAttributes = HashMap<usize, HashMap<String, String>>;
+
+/// The Arrow `Field` metadata key its extension-type mechanism looks for to recognize a
+/// logical type layered on top of the field's storage type (e.g. tagging a `Utf8` column as
+/// a UUID or JSON extension type). A writer that wants a column tagged this way on read can
+/// attach an ORC `Type` attribute under this exact key -- [`field_metadata_for`] copies every
+/// attribute through to Field metadata unchanged, so this name (and its companion
+/// [`ARROW_EXTENSION_METADATA_ATTRIBUTE`]) round-trip as-is rather than needing translation.
+pub const ARROW_EXTENSION_NAME_ATTRIBUTE: &str = "ARROW:extension:name";
+
+/// Companion to [`ARROW_EXTENSION_NAME_ATTRIBUTE`]: the canonical Arrow Field metadata key
+/// for an extension type's serialized metadata.
+pub const ARROW_EXTENSION_METADATA_ATTRIBUTE: &str = "ARROW:extension:metadata";
+
+/// Extracts every [`proto::Type`]'s attributes into a [`ColumnAttributes`] map keyed by its
+/// index in `types` (which is also its column index, see [`DataType::from_proto`]). A type
+/// with no attributes is left out of the map entirely.
+fn column_attributes_from_proto(types: &[proto::Type]) -> ColumnAttributes {
+    types
+        .iter()
+        .enumerate()
+        .filter_map(|(column_index, ty)| {
+            let attributes = ty
+                .attributes
+                .iter()
+                .filter_map(|pair| Some((pair.key.clone()?, pair.value.clone().unwrap_or_default())))
+                .collect::<HashMap<_, _>>();
+            (!attributes.is_empty()).then_some((column_index, attributes))
+        })
+        .collect()
+}
+
+/// Arrow `Field` metadata for the column at `column_index`, copied from `column_attributes`
+/// (empty if that column carried no ORC attributes or none at all were captured). Recognizes
+/// [`ARROW_EXTENSION_NAME_ATTRIBUTE`]/[`ARROW_EXTENSION_METADATA_ATTRIBUTE`] as already being
+/// the canonical Arrow extension-type keys, so those pass through unchanged; any other ORC
+/// attribute is preserved under its own key rather than being dropped.
+fn field_metadata_for(
+    column_index: usize,
+    column_attributes: &ColumnAttributes,
+) -> HashMap<String, String> {
+    column_attributes
+        .get(&column_index)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Names to give the generated Arrow fields of a [`DataType::Map`] column when computing
+/// its Arrow type (see [`DataType::to_arrow_data_type`]).
+///
+/// Arrow's `Map` type always wraps its key/value pair in an intermediate non-nullable
+/// `entries` struct field, which ORC's own schema has no equivalent name for -- this
+/// crate defaults to `"entries"`/`"keys"`/`"values"`, but other ORC readers (e.g.
+/// PyArrow) use `"key"`/`"value"` instead. Arrow compares `Map` types (and so considers
+/// batches mergeable) by field name as well as type, so reading files written by
+/// differently-named ORC readers into one schema requires making these configurable;
+/// see [`ArrowReaderBuilder::with_map_field_names`](crate::arrow_reader::ArrowReaderBuilder::with_map_field_names).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MapFieldNames {
+    pub entries: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl Default for MapFieldNames {
+    fn default() -> Self {
+        Self {
+            entries: "entries".to_owned(),
+            key: "keys".to_owned(),
+            value: "values".to_owned(),
+        }
+    }
+}
+
+/// Controls how a `Map` column's entries are handled relative to their key order, since ORC
+/// itself makes no promise that a map's keys are written in any particular order, let alone
+/// deduplicated, but some consumers rely on one or both of those properties; see
+/// [`ArrowReaderBuilder::with_map_key_mode`](crate::arrow_reader::ArrowReaderBuilder::with_map_key_mode).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapKeyMode {
+    /// Decode entries in the order the file stores them, performing no validation. The
+    /// historical default.
+    #[default]
+    Unordered,
+    /// Error out (with [`crate::error::Error::DuplicateMapKey`]) if any map value contains
+    /// two entries with the same key.
+    ValidateUnique,
+    /// Sort each map value's entries by key, producing the Arrow `Map` type's `sorted: true`
+    /// layout. Does not itself check for or reject duplicate keys.
+    SortKeys,
+}
+
 /// Represents the exact data types supported by ORC.
 ///
 /// Each variant holds the column index in order to associate the type
@@ -186,12 +356,13 @@ pub enum DataType {
     },
     /// Arbitrary byte array values.
     Binary { column_index: usize },
-    /// Decimal numbers with a fixed precision and scale.
+    /// Decimal numbers with a fixed precision and scale. `precision` is at most 76 (see
+    /// [`RootDataType::from_proto`]), so `Decimal128`/`Decimal256`'s own `u8` precision
+    /// never needs a narrowing cast (see [`DataType::to_arrow_data_type`]).
     Decimal {
         column_index: usize,
-        // TODO: narrow to u8
-        precision: u32,
-        scale: u32,
+        precision: u8,
+        scale: u8,
     },
     /// Represents specific date and time, down to the nanosecond, as offset
     /// since 1st January 2015, with no timezone.
@@ -202,8 +373,10 @@ pub enum DataType {
     /// Represents specific date and time, down to the nanosecond, as offset
     /// since 1st January 2015, with timezone.
     ///
-    /// The date and time represented by values of this column changes based
-    /// on the reader's timezone (is a fixed instant in time).
+    /// Unlike [`DataType::Timestamp`], values of this column are stored as an absolute
+    /// UTC instant (the stripe footer's writer timezone does not apply), so the date and
+    /// time they represent is a fixed instant that reads the same regardless of the
+    /// reader's requested timezone -- only its wall-clock rendering changes.
     TimestampWithLocalTimezone { column_index: usize },
     /// Represents specific date (without time) as days since the UNIX epoch
     /// (1st January 1970 UTC).
@@ -307,6 +480,108 @@ impl DataType {
         indices
     }
 
+    /// Appends the column index of this type (and any nested `Timestamp`/
+    /// `TimestampWithLocalTimezone` columns below it) to `indices`. See
+    /// [`RootDataType::timestamp_column_indices`].
+    fn collect_timestamp_column_indices(&self, indices: &mut Vec<usize>) {
+        match self {
+            DataType::Timestamp { column_index }
+            | DataType::TimestampWithLocalTimezone { column_index } => {
+                indices.push(*column_index);
+            }
+            DataType::Struct { children, .. } => {
+                for child in children {
+                    child.data_type().collect_timestamp_column_indices(indices);
+                }
+            }
+            DataType::List { child, .. } => child.collect_timestamp_column_indices(indices),
+            DataType::Map { key, value, .. } => {
+                key.collect_timestamp_column_indices(indices);
+                value.collect_timestamp_column_indices(indices);
+            }
+            DataType::Union { variants, .. } => {
+                for variant in variants {
+                    variant.collect_timestamp_column_indices(indices);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolves one path segment of a [`ProjectionMask::paths`] projection below this
+    /// type: a struct field by name, the reserved `item` segment for a list's element
+    /// type, or the reserved `key`/`value` segments for a map's key/value types. Returns
+    /// `None` if this type has no such child, either because it's not a compound type or
+    /// because the name doesn't match any field.
+    pub(crate) fn child_by_path_segment(&self, segment: &str) -> Option<&DataType> {
+        match self {
+            DataType::Struct { children, .. } => children
+                .iter()
+                .find(|col| col.name() == segment)
+                .map(NamedColumn::data_type),
+            DataType::List { child, .. } if segment == "item" => Some(child),
+            DataType::Map { key, .. } if segment == "key" => Some(key),
+            DataType::Map { value, .. } if segment == "value" => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of this type with any nested [`DataType::Struct`] field whose own
+    /// column index isn't projected by `mask` dropped, recursing through
+    /// [`DataType::List`]/[`DataType::Map`]/[`DataType::Union`] children to find them.
+    /// Returns `None` if `mask` doesn't project this type's own column index at all, so
+    /// an ancestor struct can drop this field entirely rather than keep an empty shell.
+    ///
+    /// A list's element, a map's key/value, and a union's variants are never dropped by
+    /// this alone -- every value in the column needs all of them to be decodable (a
+    /// union's tag can still point at any variant; a list/map can't have an
+    /// element/key/value type at all otherwise) -- but if one of those is itself a
+    /// struct, its own unprojected fields are still pruned, so e.g. a mask built by
+    /// [`ProjectionMask::paths`] for `"my_list.item.a"` drops `b` from a
+    /// `my_list: list<struct<a, b>>` column.
+    fn project(&self, mask: &ProjectionMask) -> Option<DataType> {
+        if !mask.is_index_projected(self.column_index()) {
+            return None;
+        }
+        let projected = match self {
+            DataType::Struct {
+                column_index,
+                children,
+            } => DataType::Struct {
+                column_index: *column_index,
+                children: children
+                    .iter()
+                    .filter_map(|col| project_named_column(col, mask))
+                    .collect(),
+            },
+            DataType::List { column_index, child } => DataType::List {
+                column_index: *column_index,
+                child: Box::new(project_or_keep(child, mask)),
+            },
+            DataType::Map {
+                column_index,
+                key,
+                value,
+            } => DataType::Map {
+                column_index: *column_index,
+                key: Box::new(project_or_keep(key, mask)),
+                value: Box::new(project_or_keep(value, mask)),
+            },
+            DataType::Union {
+                column_index,
+                variants,
+            } => DataType::Union {
+                column_index: *column_index,
+                variants: variants
+                    .iter()
+                    .map(|variant| project_or_keep(variant, mask))
+                    .collect(),
+            },
+            other => other.clone(),
+        };
+        Some(projected)
+    }
+
     fn from_proto(types: &[proto::Type], column_index: usize) -> Result<Self> {
         use proto::r#type::Kind;
 
@@ -371,12 +646,15 @@ impl DataType {
                 }
             }
             Kind::Union => {
-                // TODO: bump this limit up to 256
+                // Each variant gets assigned an Arrow union type id in
+                // `to_arrow_data_type` by casting its position `as u8 as i8`, so 256
+                // positions (0..=255) is the most this can support without two
+                // variants colliding on the same type id.
                 ensure!(
-                    ty.subtypes.len() <= 127,
+                    ty.subtypes.len() <= 256,
                     UnexpectedSnafu {
                         msg: format!(
-                            "Union type for column index {} cannot exceed 127 variants, found {}",
+                            "Union type for column index {} cannot exceed 256 variants, found {}",
                             column_index,
                             ty.subtypes.len()
                         )
@@ -395,11 +673,33 @@ impl DataType {
                     variants,
                 }
             }
-            Kind::Decimal => Self::Decimal {
-                column_index,
-                precision: ty.precision(),
-                scale: ty.scale(),
-            },
+            Kind::Decimal => {
+                let precision = ty.precision();
+                let scale = ty.scale();
+                ensure!(
+                    precision >= 1 && precision <= 76,
+                    OutOfSpecSnafu {
+                        msg: format!(
+                            "Decimal column {column_index} has precision {precision}, expected \
+                             1..=76 (the combined range Decimal128/Decimal256 can represent)"
+                        ),
+                    }
+                );
+                ensure!(
+                    scale <= precision,
+                    OutOfSpecSnafu {
+                        msg: format!(
+                            "Decimal column {column_index} has scale {scale} greater than its \
+                             precision {precision}"
+                        ),
+                    }
+                );
+                Self::Decimal {
+                    column_index,
+                    precision: precision as u8,
+                    scale: scale as u8,
+                }
+            }
             Kind::Date => Self::Date { column_index },
             Kind::Varchar => Self::Varchar {
                 column_index,
@@ -414,7 +714,44 @@ impl DataType {
         Ok(dt)
     }
 
-    pub fn to_arrow_data_type(&self) -> ArrowDataType {
+    /// Converts to the equivalent Arrow type, encoding [`DataType::Timestamp`] and
+    /// [`DataType::TimestampWithLocalTimezone`] columns with `timestamp_unit` rather than
+    /// always `Nanosecond`, since ORC's timestamp range exceeds what an i64 count of
+    /// nanoseconds since the epoch can represent (roughly years 1677-2262); a coarser
+    /// unit trades precision for range.
+    ///
+    /// `dictionary_key_type`, when set, wraps `String`/`Varchar`/`Char` columns as
+    /// `Dictionary(dictionary_key_type, Utf8)` instead of plain `Utf8`, opting every such
+    /// column into dictionary-preserving decoding (see
+    /// [`ArrowReaderBuilder::with_dictionary_key_type`](crate::ArrowReaderBuilder::with_dictionary_key_type)).
+    ///
+    /// `map_field_names` names the `entries`/key/value Arrow fields generated for a
+    /// [`DataType::Map`] column (see [`MapFieldNames`]).
+    ///
+    /// `output_timestamp_tz`, when set, labels both [`DataType::Timestamp`] and
+    /// [`DataType::TimestampWithLocalTimezone`] columns with that timezone instead of the
+    /// defaults (no timezone, and `"UTC"` respectively); the decoder converts every value
+    /// into that timezone's wall clock rather than just relabeling it (see
+    /// [`ArrowReaderBuilder::with_timestamp_timezone`](crate::arrow_reader::ArrowReaderBuilder::with_timestamp_timezone)).
+    ///
+    /// `use_utf8_view`, when `true` and `dictionary_key_type` is unset, reports
+    /// `String`/`Varchar`/`Char` columns as `Utf8View` instead of plain `Utf8` (see
+    /// [`ArrowReaderBuilder::with_utf8_view`](crate::arrow_reader::ArrowReaderBuilder::with_utf8_view)).
+    ///
+    /// `column_attributes` populates the metadata of every `Field` this produces for a
+    /// nested column (a struct field, a list's `item`, a map's key/value) from that
+    /// column's own ORC attributes, the same way [`RootDataType::create_arrow_schema`]
+    /// does for the root-level fields (see [`field_metadata_for`]).
+    #[allow(clippy::too_many_arguments)]
+    pub fn to_arrow_data_type(
+        &self,
+        timestamp_unit: TimeUnit,
+        dictionary_key_type: Option<&ArrowDataType>,
+        map_field_names: &MapFieldNames,
+        output_timestamp_tz: Option<&Arc<str>>,
+        use_utf8_view: bool,
+        column_attributes: &ColumnAttributes,
+    ) -> ArrowDataType {
         match self {
             DataType::Boolean { .. } => ArrowDataType::Boolean,
             DataType::Byte { .. } => ArrowDataType::Int8,
@@ -424,43 +761,94 @@ impl DataType {
             DataType::Float { .. } => ArrowDataType::Float32,
             DataType::Double { .. } => ArrowDataType::Float64,
             DataType::String { .. } | DataType::Varchar { .. } | DataType::Char { .. } => {
-                ArrowDataType::Utf8
+                match dictionary_key_type {
+                    Some(key_type) => ArrowDataType::Dictionary(
+                        Box::new(key_type.clone()),
+                        Box::new(ArrowDataType::Utf8),
+                    ),
+                    None if use_utf8_view => ArrowDataType::Utf8View,
+                    None => ArrowDataType::Utf8,
+                }
             }
             DataType::Binary { .. } => ArrowDataType::Binary,
+            // Beyond `Decimal128`'s 38 digits of precision, the unscaled value no
+            // longer fits in an `i128` and needs the `i256`-backed `Decimal256` instead
+            // (see `new_decimal_decoder`).
+            DataType::Decimal {
+                precision, scale, ..
+            } if *precision > Decimal128Type::MAX_PRECISION => {
+                ArrowDataType::Decimal256(*precision, *scale as i8)
+            }
             DataType::Decimal {
                 precision, scale, ..
-            } => ArrowDataType::Decimal128(*precision as u8, *scale as i8), // TODO: safety of cast?
-            DataType::Timestamp { .. } => ArrowDataType::Timestamp(TimeUnit::Nanosecond, None),
-            DataType::TimestampWithLocalTimezone { .. } => {
-                ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into()))
+            } => ArrowDataType::Decimal128(*precision, *scale as i8),
+            DataType::Timestamp { .. } => {
+                ArrowDataType::Timestamp(timestamp_unit, output_timestamp_tz.cloned())
             }
+            DataType::TimestampWithLocalTimezone { .. } => ArrowDataType::Timestamp(
+                timestamp_unit,
+                Some(output_timestamp_tz.cloned().unwrap_or_else(|| "UTC".into())),
+            ),
             DataType::Date { .. } => ArrowDataType::Date32,
             DataType::Struct { children, .. } => {
                 let children = children
                     .iter()
                     .map(|col| {
-                        let dt = col.data_type().to_arrow_data_type();
-                        Field::new(col.name(), dt, true)
+                        let dt = col.data_type().to_arrow_data_type(
+                            timestamp_unit,
+                            dictionary_key_type,
+                            map_field_names,
+                            output_timestamp_tz,
+                            use_utf8_view,
+                            column_attributes,
+                        );
+                        Field::new(col.name(), dt, true).with_metadata(field_metadata_for(
+                            col.data_type().column_index(),
+                            column_attributes,
+                        ))
                     })
                     .collect();
                 ArrowDataType::Struct(children)
             }
             DataType::List { child, .. } => {
-                let child = child.to_arrow_data_type();
-                ArrowDataType::new_list(child, true)
+                let child_dt = child.to_arrow_data_type(
+                    timestamp_unit,
+                    dictionary_key_type,
+                    map_field_names,
+                    output_timestamp_tz,
+                    use_utf8_view,
+                    column_attributes,
+                );
+                let child_field = Field::new("item", child_dt, true)
+                    .with_metadata(field_metadata_for(child.column_index(), column_attributes));
+                ArrowDataType::List(Arc::new(child_field))
             }
             DataType::Map { key, value, .. } => {
-                // TODO: this needs to be kept in sync with MapArrayDecoder
-                //       move to common location?
-                // TODO: should it be "keys" and "values" (like arrow-rs)
-                //       or "key" and "value" like PyArrow and in Schema.fbs?
-                let key = key.to_arrow_data_type();
-                let key = Field::new("keys", key, false);
-                let value = value.to_arrow_data_type();
-                let value = Field::new("values", value, true);
-
-                let dt = ArrowDataType::Struct(vec![key, value].into());
-                let dt = Arc::new(Field::new("entries", dt, false));
+                // Kept in sync with MapArrayDecoder, which mirrors these same field
+                // names (other than the nullability) when assembling its output array.
+                let key_dt = key.to_arrow_data_type(
+                    timestamp_unit,
+                    dictionary_key_type,
+                    map_field_names,
+                    output_timestamp_tz,
+                    use_utf8_view,
+                    column_attributes,
+                );
+                let key_dt = Field::new(&map_field_names.key, key_dt, false)
+                    .with_metadata(field_metadata_for(key.column_index(), column_attributes));
+                let value_dt = value.to_arrow_data_type(
+                    timestamp_unit,
+                    dictionary_key_type,
+                    map_field_names,
+                    output_timestamp_tz,
+                    use_utf8_view,
+                    column_attributes,
+                );
+                let value_dt = Field::new(&map_field_names.value, value_dt, true)
+                    .with_metadata(field_metadata_for(value.column_index(), column_attributes));
+
+                let dt = ArrowDataType::Struct(vec![key_dt, value_dt].into());
+                let dt = Arc::new(Field::new(&map_field_names.entries, dt, false));
                 ArrowDataType::Map(dt, false)
             }
             DataType::Union { variants, .. } => {
@@ -468,18 +856,29 @@ impl DataType {
                     .iter()
                     .enumerate()
                     .map(|(index, variant)| {
-                        // Limited to 127 variants max (in from_proto)
-                        // TODO: Support up to including 256
-                        //       Need to do Union within Union
+                        // Limited to 256 variants max (in from_proto); positions 128..=255
+                        // wrap into the negative half of the i8 type id space, which
+                        // `UnionArrayDecoder` un-wraps back via `tag as u8` before using it
+                        // as a variant index.
                         let index = index as u8 as i8;
-                        let arrow_dt = variant.to_arrow_data_type();
+                        let arrow_dt = variant.to_arrow_data_type(
+                            timestamp_unit,
+                            dictionary_key_type,
+                            map_field_names,
+                            output_timestamp_tz,
+                            use_utf8_view,
+                            column_attributes,
+                        );
                         // Name shouldn't matter here (only ORC struct types give names to subtypes anyway)
                         // Using naming convention following PyArrow for easier comparison
-                        let field = Arc::new(Field::new(format!("_union_{index}"), arrow_dt, true));
-                        (index, field)
+                        let field = Field::new(format!("_union_{index}"), arrow_dt, true)
+                            .with_metadata(field_metadata_for(variant.column_index(), column_attributes));
+                        (index, Arc::new(field))
                     })
                     .collect();
-                ArrowDataType::Union(fields, UnionMode::Sparse)
+                // Dense avoids every variant array being padded out to the parent's
+                // full length: see `UnionArrayDecoder`'s Sparse/Dense split.
+                ArrowDataType::Union(fields, UnionMode::Dense)
             }
         }
     }
@@ -547,3 +946,379 @@ impl Display for DataType {
         }
     }
 }
+
+/// The inverse of [`RootDataType::from_proto`]: flattens an Arrow [`Schema`] into ORC's
+/// pre-order `Type` array, the representation `Footer.types` uses on disk. Column ids are
+/// assigned depth-first in the same order the reader walks them back (root struct = 0, then
+/// each child visited before any of its siblings).
+///
+/// Returns an error rather than panicking for any Arrow [`ArrowDataType`] this crate's writer
+/// doesn't have an ORC equivalent for (e.g. `Float16`, `Utf8View`, nested `List`/`Map` column
+/// encoding isn't implemented by [`crate::arrow_writer`] yet either, but the type mapping
+/// itself is exercised independently here so it's ready once that support lands).
+pub fn arrow_schema_to_orc(schema: &Schema) -> Result<Vec<proto::Type>> {
+    use proto::r#type::Kind;
+
+    let mut types = vec![proto::Type::default()];
+    let (subtypes, field_names) = convert_struct_fields(&mut types, schema.fields())?;
+    types[0] = proto::Type {
+        kind: Some(Kind::Struct.into()),
+        subtypes,
+        field_names,
+        ..Default::default()
+    };
+    Ok(types)
+}
+
+/// Converts one Arrow [`Field`], appending it (and, depth-first, all of its descendants) to
+/// `types`, and returns the column id it was assigned. The field's metadata is copied onto
+/// the resulting `Type`'s attributes -- the inverse of [`field_metadata_for`] -- so an
+/// `ARROW:extension:name`/`ARROW:extension:metadata` tagged field, or any other custom
+/// metadata, round-trips through a file written by this crate.
+fn convert_arrow_type(types: &mut Vec<proto::Type>, field: &Field) -> Result<u32> {
+    use proto::r#type::Kind;
+
+    let index = types.len() as u32;
+    types.push(proto::Type::default());
+
+    let mut ty = match field.data_type() {
+        ArrowDataType::Boolean => leaf(Kind::Boolean),
+        ArrowDataType::Int8 => leaf(Kind::Byte),
+        ArrowDataType::Int16 => leaf(Kind::Short),
+        ArrowDataType::Int32 => leaf(Kind::Int),
+        ArrowDataType::Int64 => leaf(Kind::Long),
+        ArrowDataType::Float32 => leaf(Kind::Float),
+        ArrowDataType::Float64 => leaf(Kind::Double),
+        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 => leaf(Kind::String),
+        ArrowDataType::Binary | ArrowDataType::LargeBinary => leaf(Kind::Binary),
+        ArrowDataType::Date32 => leaf(Kind::Date),
+        ArrowDataType::Timestamp(TimeUnit::Nanosecond, None) => leaf(Kind::Timestamp),
+        ArrowDataType::Decimal128(precision, scale) => proto::Type {
+            kind: Some(Kind::Decimal.into()),
+            precision: Some(*precision as u32),
+            scale: Some(*scale as u32),
+            ..Default::default()
+        },
+        ArrowDataType::Struct(fields) => {
+            let (subtypes, field_names) = convert_struct_fields(types, fields)?;
+            proto::Type {
+                kind: Some(Kind::Struct.into()),
+                subtypes,
+                field_names,
+                ..Default::default()
+            }
+        }
+        ArrowDataType::List(item) | ArrowDataType::LargeList(item) => {
+            let subtype = convert_arrow_type(types, item)?;
+            proto::Type {
+                kind: Some(Kind::List.into()),
+                subtypes: vec![subtype],
+                field_names: vec![item.name().clone()],
+                ..Default::default()
+            }
+        }
+        ArrowDataType::Map(entries, _sorted) => {
+            let ArrowDataType::Struct(entry_fields) = entries.data_type() else {
+                return UnexpectedSnafu {
+                    msg: format!(
+                        "map entries field must be a struct of (key, value), got {:?}",
+                        entries.data_type()
+                    ),
+                }
+                .fail();
+            };
+            ensure!(
+                entry_fields.len() == 2,
+                UnexpectedSnafu {
+                    msg: format!(
+                        "map entries struct must have exactly 2 fields, got {}",
+                        entry_fields.len()
+                    )
+                }
+            );
+            let key_field = &entry_fields[0];
+            let value_field = &entry_fields[1];
+            let key_id = convert_arrow_type(types, key_field)?;
+            let value_id = convert_arrow_type(types, value_field)?;
+            proto::Type {
+                kind: Some(Kind::Map.into()),
+                subtypes: vec![key_id, value_id],
+                field_names: vec![key_field.name().clone(), value_field.name().clone()],
+                ..Default::default()
+            }
+        }
+        ArrowDataType::Union(fields, UnionMode::Sparse) => {
+            let mut subtypes = Vec::with_capacity(fields.len());
+            let mut field_names = Vec::with_capacity(fields.len());
+            for (_type_id, field) in fields.iter() {
+                field_names.push(field.name().clone());
+                subtypes.push(convert_arrow_type(types, field)?);
+            }
+            proto::Type {
+                kind: Some(Kind::Union.into()),
+                subtypes,
+                field_names,
+                ..Default::default()
+            }
+        }
+        other => {
+            return UnexpectedSnafu {
+                msg: format!("arrow datatype {other:?} has no ORC equivalent"),
+            }
+            .fail();
+        }
+    };
+    ty.attributes = attributes_to_proto(field.metadata());
+    types[index as usize] = ty;
+    Ok(index)
+}
+
+/// Converts every field of a `Struct`/the root `Schema` and returns the `(subtypes,
+/// field_names)` pair a `Kind::Struct` `Type` stores them as.
+fn convert_struct_fields(
+    types: &mut Vec<proto::Type>,
+    fields: &Fields,
+) -> Result<(Vec<u32>, Vec<String>)> {
+    let mut subtypes = Vec::with_capacity(fields.len());
+    let mut field_names = Vec::with_capacity(fields.len());
+    for field in fields {
+        field_names.push(field.name().clone());
+        subtypes.push(convert_arrow_type(types, field)?);
+    }
+    Ok((subtypes, field_names))
+}
+
+fn leaf(kind: proto::r#type::Kind) -> proto::Type {
+    proto::Type {
+        kind: Some(kind.into()),
+        ..Default::default()
+    }
+}
+
+/// The inverse of [`field_metadata_for`]: turns Arrow [`Field`] metadata into the protobuf
+/// `StringPair` list a `Type`'s `attributes` are stored as.
+fn attributes_to_proto(metadata: &HashMap<String, String>) -> Vec<proto::StringPair> {
+    metadata
+        .iter()
+        .map(|(key, value)| proto::StringPair {
+            key: Some(key.clone()),
+            value: Some(value.clone()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrow_schema_to_orc_round_trips_through_from_proto() {
+        let list_item = Field::new("item", ArrowDataType::Int32, true);
+        let map_entries = Field::new(
+            "entries",
+            ArrowDataType::Struct(Fields::from(vec![
+                Field::new("key", ArrowDataType::Utf8, false),
+                Field::new("value", ArrowDataType::Int64, true),
+            ])),
+            false,
+        );
+        let nested = Field::new(
+            "nested",
+            ArrowDataType::Struct(Fields::from(vec![
+                Field::new("a", ArrowDataType::Boolean, true),
+                Field::new("b", ArrowDataType::Float64, true),
+            ])),
+            true,
+        );
+
+        let schema = Schema::new(vec![
+            Field::new("bool_col", ArrowDataType::Boolean, true),
+            Field::new("int_col", ArrowDataType::Int32, true),
+            Field::new("long_col", ArrowDataType::Int64, true),
+            Field::new("string_col", ArrowDataType::Utf8, true),
+            Field::new("decimal_col", ArrowDataType::Decimal128(10, 2), true),
+            Field::new(
+                "timestamp_col",
+                ArrowDataType::Timestamp(TimeUnit::Nanosecond, None),
+                true,
+            ),
+            Field::new("list_col", ArrowDataType::List(Arc::new(list_item)), true),
+            Field::new(
+                "map_col",
+                ArrowDataType::Map(Arc::new(map_entries), false),
+                true,
+            ),
+            nested,
+        ]);
+
+        let types = arrow_schema_to_orc(&schema).unwrap();
+        let root = RootDataType::from_proto(&types).unwrap();
+
+        assert_eq!(root.children().len(), schema.fields().len());
+        for col in root.children() {
+            assert!(schema.field_with_name(col.name()).is_ok());
+        }
+
+        let decimal_col = root
+            .children()
+            .iter()
+            .find(|col| col.name() == "decimal_col")
+            .unwrap();
+        assert!(matches!(decimal_col.data_type(), DataType::Decimal { .. }));
+
+        let list_col = root
+            .children()
+            .iter()
+            .find(|col| col.name() == "list_col")
+            .unwrap();
+        assert!(matches!(list_col.data_type(), DataType::List { .. }));
+
+        let map_col = root
+            .children()
+            .iter()
+            .find(|col| col.name() == "map_col")
+            .unwrap();
+        assert!(matches!(map_col.data_type(), DataType::Map { .. }));
+    }
+
+    #[test]
+    fn arrow_schema_to_orc_rejects_unsupported_type() {
+        let schema = Schema::new(vec![Field::new("f", ArrowDataType::Float16, true)]);
+        assert!(arrow_schema_to_orc(&schema).is_err());
+    }
+
+    /// `m: map<long, list<struct<a: long, b: long>>>`, column indices assigned depth-first
+    /// the same way [`DataType::from_proto`] would: root=0, m=1, key=2, value(list)=3,
+    /// list's element struct=4, a=5, b=6.
+    fn nested_map_schema() -> RootDataType {
+        let a = DataType::Long { column_index: 5 };
+        let b = DataType::Long { column_index: 6 };
+        let list_element = DataType::Struct {
+            column_index: 4,
+            children: vec![
+                NamedColumn {
+                    name: "a".to_owned(),
+                    data_type: a,
+                },
+                NamedColumn {
+                    name: "b".to_owned(),
+                    data_type: b,
+                },
+            ],
+        };
+        let value = DataType::List {
+            column_index: 3,
+            child: Box::new(list_element),
+        };
+        let key = DataType::Long { column_index: 2 };
+        let m = DataType::Map {
+            column_index: 1,
+            key: Box::new(key),
+            value: Box::new(value),
+        };
+        RootDataType {
+            children: vec![NamedColumn {
+                name: "m".to_owned(),
+                data_type: m,
+            }],
+            attributes: Arc::new(HashMap::new()),
+        }
+    }
+
+    fn struct_field_names(data_type: &DataType) -> Vec<&str> {
+        match data_type {
+            DataType::Struct { children, .. } => {
+                children.iter().map(|col| col.name()).collect()
+            }
+            other => panic!("expected a Struct, got {other}"),
+        }
+    }
+
+    #[test]
+    fn project_recurses_into_map_value_list_struct() {
+        let root = nested_map_schema();
+        let mask = ProjectionMask::paths(&root, &["m.value.item.a"]).unwrap();
+
+        let projected = root.project(&mask);
+        assert_eq!(projected.children().len(), 1);
+
+        let m = projected.children()[0].data_type();
+        let DataType::Map { key, value, .. } = m else {
+            panic!("expected a Map, got {m}");
+        };
+
+        // The key isn't on the projected path at all, but a map can't drop its key type
+        // while keeping the map, so it comes back unpruned rather than disappearing.
+        assert!(matches!(**key, DataType::Long { .. }));
+
+        let DataType::List { child, .. } = value.as_ref() else {
+            panic!("expected a List, got {value}");
+        };
+        // Only `a` was on the projected path, so `b` is pruned from the list's element
+        // struct even though the list and map around it are kept.
+        assert_eq!(struct_field_names(child), vec!["a"]);
+    }
+
+    #[test]
+    fn project_drops_unselected_sibling_entirely() {
+        let a = DataType::Long { column_index: 1 };
+        let b = DataType::Long { column_index: 2 };
+        let root = RootDataType {
+            children: vec![
+                NamedColumn {
+                    name: "a".to_owned(),
+                    data_type: a,
+                },
+                NamedColumn {
+                    name: "b".to_owned(),
+                    data_type: b,
+                },
+            ],
+            attributes: Arc::new(HashMap::new()),
+        };
+        let mask = ProjectionMask::paths(&root, &["a"]).unwrap();
+
+        let projected = root.project(&mask);
+        let names = projected
+            .children()
+            .iter()
+            .map(|col| col.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a"]);
+    }
+
+    #[test]
+    fn union_assigns_all_256_variants_distinct_round_tripping_type_ids() {
+        let variants = (0..256)
+            .map(|i| DataType::Long {
+                column_index: i + 1,
+            })
+            .collect();
+        let union = DataType::Union {
+            column_index: 0,
+            variants,
+        };
+
+        let arrow_dt = union.to_arrow_data_type(
+            TimeUnit::Nanosecond,
+            None,
+            &MapFieldNames::default(),
+            None,
+            false,
+            &ColumnAttributes::new(),
+        );
+        let ArrowDataType::Union(fields, mode) = arrow_dt else {
+            panic!("expected a Union, got {arrow_dt:?}");
+        };
+        assert_eq!(mode, UnionMode::Dense);
+        assert_eq!(fields.iter().count(), 256);
+
+        // `UnionArrayDecoder` recovers a tag's variant position with `tag as u8 as usize`;
+        // every type id `to_arrow_data_type` hands out here -- including positions 128..=255,
+        // which wrap into the negative half of `i8` -- must round-trip through that back to
+        // the position it came from.
+        for (position, (type_id, _field)) in fields.iter().enumerate() {
+            assert_eq!(type_id as u8 as usize, position);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::fmt;
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion::datasource::physical_plan::FileSinkConfig;
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::TaskContext;
+use datafusion::physical_plan::metrics::MetricsSet;
+use datafusion::physical_plan::{DisplayAs, DisplayFormatType, SendableRecordBatchStream};
+use datafusion_datasource::sink::DataSink;
+use futures_util::StreamExt;
+use object_store::path::Path;
+use orc_rust::ArrowWriterBuilder;
+use std::sync::Arc;
+
+/// Writes a [`SendableRecordBatchStream`] out to a single ORC file per
+/// [`DataSink::write_all`] invocation, mirroring how [`OrcOpener`](crate::physical_exec::OrcOpener)
+/// drives the read path stripe-by-stripe.
+///
+/// Whole files are currently buffered in memory before being `put` to the
+/// [`ObjectStore`](object_store::ObjectStore), since [`orc_rust::ArrowWriter`] only writes to a
+/// synchronous [`std::io::Write`]. For very large outputs this should eventually be replaced
+/// with a multipart upload driven from a background thread.
+pub struct OrcSink {
+    config: FileSinkConfig,
+}
+
+impl OrcSink {
+    pub fn new(config: FileSinkConfig) -> Self {
+        Self { config }
+    }
+
+    fn output_path(&self) -> Path {
+        self.config
+            .table_paths
+            .first()
+            .map(|url| url.prefix().clone())
+            .unwrap_or_else(|| Path::from(""))
+            // TODO: derive a per-partition file name from `self.config.file_group` once
+            // multiple output files per sink are supported.
+            .child("part-0.orc")
+    }
+}
+
+impl fmt::Debug for OrcSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrcSink")
+            .field("output_schema", self.config.output_schema())
+            .finish()
+    }
+}
+
+impl DisplayAs for OrcSink {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OrcSink(file={})", self.output_path())
+    }
+}
+
+#[async_trait]
+impl DataSink for OrcSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> &SchemaRef {
+        self.config.output_schema()
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    async fn write_all(
+        &self,
+        mut data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let object_store = context
+            .runtime_env()
+            .object_store(&self.config.object_store_url)?;
+
+        let mut buffer = Vec::new();
+        let mut writer =
+            ArrowWriterBuilder::new(&mut buffer, self.config.output_schema().clone())
+                .try_build()
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        let mut row_count = 0u64;
+        while let Some(batch) = data.next().await {
+            let batch = batch?;
+            row_count += batch.num_rows() as u64;
+            writer
+                .write(&batch)
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        }
+        writer
+            .close()
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        object_store.put(&self.output_path(), buffer.into()).await?;
+
+        Ok(row_count)
+    }
+}
@@ -16,16 +16,18 @@
 // under the License.
 
 use std::collections::HashSet;
+use std::ops::Range;
 use std::{collections::HashMap, io::Read, sync::Arc};
 
 use bytes::Bytes;
 use prost::Message;
-use snafu::ResultExt;
+use snafu::{OptionExt, ResultExt};
 
 use crate::{
     column::Column,
     compression::{Compression, Decompressor},
-    error::{self, IoSnafu, Result},
+    error::{self, IoSnafu, OutOfSpecSnafu, Result},
+    predicate::ResolvedPredicate,
     proto::{self, stream::Kind, StripeFooter},
     reader::{metadata::FileMetadata, ChunkReader},
     schema::RootDataType,
@@ -79,6 +81,18 @@ impl StripeMetadata {
     pub fn footer_offset(&self) -> u64 {
         self.offset + self.index_length + self.data_length
     }
+
+    /// Whether this stripe's column statistics fail to rule out `predicate`, i.e. whether
+    /// some row in this stripe could still satisfy it. `false` means the stripe can be
+    /// skipped -- and its streams never read -- without missing any matching rows.
+    ///
+    /// `predicate` must already be resolved via [`Predicate::resolve`](crate::predicate::Predicate::resolve),
+    /// since [`column_statistics`](Self::column_statistics) is indexed by ORC column index
+    /// rather than name; [`FileMetadata::stripe_metadatas_matching`](crate::reader::metadata::FileMetadata::stripe_metadatas_matching)
+    /// does that resolution once and filters every stripe with it.
+    pub fn can_match(&self, predicate: &ResolvedPredicate) -> bool {
+        predicate.may_match(&self.column_statistics)
+    }
 }
 
 impl TryFrom<(&proto::StripeInformation, &proto::StripeStatistics)> for StripeMetadata {
@@ -124,14 +138,22 @@ pub struct Stripe {
     stream_map: Arc<StreamMap>,
     number_of_rows: usize,
     tz: Option<chrono_tz::Tz>,
+    /// The writer timezone exactly as recorded in the stripe footer, before being parsed into
+    /// [`Self::tz`]; exposed via [`Self::writer_timezone_name`] for callers that want to
+    /// inspect it without going through the parsed [`chrono_tz::Tz`].
+    raw_tz: Option<String>,
 }
 
 impl Stripe {
+    #[allow(clippy::too_many_arguments)]
     pub fn new<R: ChunkReader>(
         reader: &mut R,
         file_metadata: &Arc<FileMetadata>,
         projected_data_type: &RootDataType,
         info: &StripeMetadata,
+        coalesce_gap_threshold: u64,
+        coalesce_max_merged_size: u64,
+        ignore_writer_tz: bool,
     ) -> Result<Self> {
         let compression = file_metadata.compression();
 
@@ -147,24 +169,31 @@ impl Stripe {
             .collect();
         let column_ids = collect_required_column_ids(&columns);
 
-        let mut stream_map = HashMap::new();
+        let mut needed_streams = Vec::new();
         let mut stream_offset = info.offset();
         for stream in &footer.streams {
             let length = stream.length();
             let column_id = stream.column();
             if column_ids.contains(&column_id) {
-                let kind = stream.kind();
-                let data = Column::read_stream(reader, stream_offset, length)?;
-                stream_map.insert((column_id, kind), data);
+                needed_streams.push(((column_id, stream.kind()), stream_offset, length));
             }
             stream_offset += length;
         }
-
-        let tz: Option<chrono_tz::Tz> = footer
-            .writer_timezone
-            .as_ref()
-            // TODO: make this return error
-            .map(|a| a.parse::<chrono_tz::Tz>().unwrap());
+        let stream_map = fetch_streams_coalesced_sync(
+            reader,
+            &needed_streams,
+            coalesce_gap_threshold,
+            coalesce_max_merged_size,
+        )?;
+
+        // `ignore_writer_tz` keeps `raw_tz` populated either way -- it's still useful for a
+        // caller that opted out of the automatic conversion to see what was skipped.
+        let tz = if ignore_writer_tz {
+            None
+        } else {
+            parse_writer_tz(footer.writer_timezone.as_deref())?
+        };
+        let raw_tz = footer.writer_timezone.clone();
 
         Ok(Self {
             columns,
@@ -174,16 +203,21 @@ impl Stripe {
             }),
             number_of_rows: info.number_of_rows() as usize,
             tz,
+            raw_tz,
         })
     }
 
     // TODO: reduce duplication with above
     #[cfg(feature = "async")]
+    #[allow(clippy::too_many_arguments)]
     pub async fn new_async<R: crate::reader::AsyncChunkReader>(
         reader: &mut R,
         file_metadata: &Arc<FileMetadata>,
         projected_data_type: &RootDataType,
         info: &StripeMetadata,
+        coalesce_gap_threshold: u64,
+        coalesce_max_merged_size: u64,
+        ignore_writer_tz: bool,
     ) -> Result<Self> {
         let compression = file_metadata.compression();
 
@@ -200,25 +234,30 @@ impl Stripe {
             .collect();
         let column_ids = collect_required_column_ids(&columns);
 
-        let mut stream_map = HashMap::new();
+        let mut needed_streams = Vec::new();
         let mut stream_offset = info.offset();
         for stream in &footer.streams {
             let length = stream.length();
             let column_id = stream.column();
             if column_ids.contains(&column_id) {
-                let kind = stream.kind();
-                let data = Column::read_stream_async(reader, stream_offset, length).await?;
-                stream_map.insert((column_id, kind), data);
+                needed_streams.push(((column_id, stream.kind()), stream_offset, length));
             }
-
             stream_offset += length;
         }
-
-        let tz: Option<chrono_tz::Tz> = footer
-            .writer_timezone
-            .as_ref()
-            // TODO: make this return error
-            .map(|a| a.parse::<chrono_tz::Tz>().unwrap());
+        let stream_map = fetch_streams_coalesced(
+            reader,
+            &needed_streams,
+            coalesce_gap_threshold,
+            coalesce_max_merged_size,
+        )
+        .await?;
+
+        let tz = if ignore_writer_tz {
+            None
+        } else {
+            parse_writer_tz(footer.writer_timezone.as_deref())?
+        };
+        let raw_tz = footer.writer_timezone.clone();
 
         Ok(Self {
             columns,
@@ -228,6 +267,7 @@ impl Stripe {
             }),
             number_of_rows: info.number_of_rows() as usize,
             tz,
+            raw_tz,
         })
     }
 
@@ -246,6 +286,204 @@ impl Stripe {
     pub fn writer_tz(&self) -> Option<chrono_tz::Tz> {
         self.tz
     }
+
+    /// The writer timezone exactly as recorded in this stripe's footer (e.g.
+    /// `"America/New_York"`), before being parsed into [`Self::writer_tz`]. `None` means the
+    /// writer didn't record one, in which case UTC is assumed.
+    pub fn writer_timezone_name(&self) -> Option<&str> {
+        self.raw_tz.as_deref()
+    }
+
+    /// Decodes `column`'s `BloomFilter` stream, if this stripe recorded one, into one
+    /// [`BloomFilter`](crate::bloom_filter::BloomFilter) per `rowIndexStride`-sized group
+    /// of rows. `None` means the column has no bloom filter in this stripe, either because
+    /// the writer didn't build one for it or because `column` wasn't projected.
+    pub(crate) fn bloom_filters(
+        &self,
+        column: &Column,
+    ) -> Result<Option<Vec<crate::bloom_filter::BloomFilter>>> {
+        let Some(mut stream) = self.stream_map.get_opt(column, Kind::BloomFilter) else {
+            return Ok(None);
+        };
+        let mut buffer = vec![];
+        stream.read_to_end(&mut buffer).context(error::IoSnafu)?;
+        let index = proto::BloomFilterIndex::decode(buffer.as_slice())
+            .context(error::DecodeProtoSnafu)?;
+        Ok(Some(crate::bloom_filter::decode_bloom_filters(&index)))
+    }
+
+    /// Decodes `column`'s `RowIndex` stream, if this stripe recorded one, into one
+    /// [`ColumnStatistics`] per `rowIndexStride`-sized group of rows. `None` means the
+    /// column has no row index in this stripe.
+    ///
+    /// This only exposes the per-stride statistics `RowIndexEntry` carries; it doesn't yet
+    /// expose `RowIndexEntry::positions`, the byte offsets a decoder would need to seek past
+    /// a pruned stride's run instead of decoding it -- see [`crate::encoding::rle::GenericRle`]
+    /// for why that isn't wired up yet. Callers can still use the statistics returned here to
+    /// narrow a [`RowSelection`](crate::row_selection::RowSelection) the same way
+    /// [`Self::bloom_filters`] does, just without skipping the decode itself.
+    pub(crate) fn row_group_statistics(
+        &self,
+        column: &Column,
+    ) -> Result<Option<Vec<ColumnStatistics>>> {
+        let Some(mut stream) = self.stream_map.get_opt(column, Kind::RowIndex) else {
+            return Ok(None);
+        };
+        let mut buffer = vec![];
+        stream.read_to_end(&mut buffer).context(error::IoSnafu)?;
+        let row_index =
+            proto::RowIndex::decode(buffer.as_slice()).context(error::DecodeProtoSnafu)?;
+        row_index
+            .entry
+            .iter()
+            .map(|entry| {
+                entry
+                    .statistics
+                    .as_ref()
+                    .context(OutOfSpecSnafu {
+                        msg: "RowIndexEntry is missing statistics",
+                    })
+                    .and_then(|stats| ColumnStatistics::try_from(stats))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+}
+
+/// Fetches and decodes `column_id`'s `BloomFilter` stream for one stripe, reading only the
+/// stripe footer plus that single stream's bytes rather than every stream a full [`Stripe`]
+/// would need. Meant for callers deciding whether a stripe is worth reading at all (e.g.
+/// [`ArrowReaderBuilder`](crate::arrow_reader::ArrowReaderBuilder)'s
+/// `read_stripe_bloom_filter`), as opposed to [`Stripe::bloom_filters`], which assumes the
+/// stripe's streams have already been fetched as part of scanning it.
+///
+/// Returns `None` if the stripe has no `BloomFilter` stream for `column_id`, either because
+/// the writer didn't build one or because the column has no values in this stripe.
+#[cfg(feature = "async")]
+pub(crate) async fn read_bloom_filter_async<R: crate::reader::AsyncChunkReader>(
+    reader: &mut R,
+    file_metadata: &FileMetadata,
+    info: &StripeMetadata,
+    column_id: u32,
+) -> Result<Option<Vec<crate::bloom_filter::BloomFilter>>> {
+    let compression = file_metadata.compression();
+    let footer = reader
+        .get_bytes(info.footer_offset(), info.footer_length())
+        .await
+        .context(IoSnafu)?;
+    let footer = deserialize_stripe_footer(footer, compression)?;
+
+    let mut stream_offset = info.offset();
+    for stream in &footer.streams {
+        let length = stream.length();
+        if stream.column() == column_id && stream.kind() == Kind::BloomFilter {
+            let data = Column::read_stream_async(reader, stream_offset, length).await?;
+            let mut buffer = vec![];
+            Decompressor::new(data, compression, vec![])
+                .read_to_end(&mut buffer)
+                .context(IoSnafu)?;
+            let index = proto::BloomFilterIndex::decode(buffer.as_slice())
+                .context(error::DecodeProtoSnafu)?;
+            return Ok(Some(crate::bloom_filter::decode_bloom_filters(&index)));
+        }
+        stream_offset += length;
+    }
+    Ok(None)
+}
+
+/// Plans a minimal set of merged read spans covering every stream in `needed`: adjacent
+/// entries merge into the same span whenever the gap between them is no more than
+/// `gap_threshold` and the merged span would still be no larger than `max_merged_size`.
+/// `needed` must already be sorted by offset, which walking a stripe footer's streams in
+/// order guarantees. Returns each span's `(offset, length)` alongside the range of `needed`
+/// it covers, for a caller to fetch and then re-slice back into per-stream entries.
+///
+/// Shared by [`fetch_streams_coalesced`]/[`fetch_streams_coalesced_sync`] so the sync and
+/// async stripe-reading paths can't drift on the merging strategy itself.
+fn plan_coalesced_reads(
+    needed: &[((u32, Kind), u64, u64)],
+    gap_threshold: u64,
+    max_merged_size: u64,
+) -> Vec<(u64, u64, Range<usize>)> {
+    let mut groups = Vec::new();
+    let mut index = 0;
+    while index < needed.len() {
+        let (_, group_start, first_length) = needed[index];
+        let mut group_end = group_start + first_length;
+        let mut group_len = index + 1;
+        while group_len < needed.len() {
+            let (_, next_offset, next_length) = needed[group_len];
+            let merged_end = next_offset + next_length;
+            if next_offset.saturating_sub(group_end) > gap_threshold
+                || merged_end - group_start > max_merged_size
+            {
+                break;
+            }
+            group_end = merged_end;
+            group_len += 1;
+        }
+        groups.push((group_start, group_end - group_start, index..group_len));
+        index = group_len;
+    }
+    groups
+}
+
+/// Fetches every needed stream's bytes asynchronously, issuing one read per merged span from
+/// [`plan_coalesced_reads`] instead of one per stream. Cuts round-trips against a remote
+/// object store down from one per stream to one per merged span, at the cost of occasionally
+/// reading (and discarding) a few unwanted bytes from streams that fell in between.
+#[cfg(feature = "async")]
+async fn fetch_streams_coalesced<R: crate::reader::AsyncChunkReader>(
+    reader: &mut R,
+    needed: &[((u32, Kind), u64, u64)],
+    gap_threshold: u64,
+    max_merged_size: u64,
+) -> Result<HashMap<(u32, Kind), Bytes>> {
+    let mut stream_map = HashMap::with_capacity(needed.len());
+    for (group_start, group_len, range) in plan_coalesced_reads(needed, gap_threshold, max_merged_size) {
+        let merged = Column::read_stream_async(reader, group_start, group_len).await?;
+        for &(key, offset, length) in &needed[range] {
+            let start = (offset - group_start) as usize;
+            stream_map.insert(key, merged.slice(start..start + length as usize));
+        }
+    }
+    Ok(stream_map)
+}
+
+/// Sync counterpart to [`fetch_streams_coalesced`], for [`Stripe::new`]'s `R: ChunkReader`
+/// (rather than `AsyncChunkReader`) reader.
+fn fetch_streams_coalesced_sync<R: ChunkReader>(
+    reader: &mut R,
+    needed: &[((u32, Kind), u64, u64)],
+    gap_threshold: u64,
+    max_merged_size: u64,
+) -> Result<HashMap<(u32, Kind), Bytes>> {
+    let mut stream_map = HashMap::with_capacity(needed.len());
+    for (group_start, group_len, range) in plan_coalesced_reads(needed, gap_threshold, max_merged_size) {
+        let merged = Column::read_stream(reader, group_start, group_len)?;
+        for &(key, offset, length) in &needed[range] {
+            let start = (offset - group_start) as usize;
+            stream_map.insert(key, merged.slice(start..start + length as usize));
+        }
+    }
+    Ok(stream_map)
+}
+
+/// Parses the writer timezone recorded in a stripe footer, if any. A present but
+/// unrecognized timezone name is a malformed file rather than something to silently
+/// ignore, so it's reported as [`OrcError::OutOfSpec`](error::OrcError::OutOfSpec)
+/// instead of panicking.
+fn parse_writer_tz(writer_timezone: Option<&str>) -> Result<Option<chrono_tz::Tz>> {
+    writer_timezone
+        .map(|tz| {
+            tz.parse::<chrono_tz::Tz>().map_err(|_| {
+                OutOfSpecSnafu {
+                    msg: format!("unknown writer timezone in stripe footer: '{tz}'"),
+                }
+                .build()
+            })
+        })
+        .transpose()
 }
 
 #[derive(Debug)]
@@ -0,0 +1,505 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! An async counterpart to [`ArrowWriter`](crate::arrow_writer::ArrowWriter) for writing
+//! to an [`AsyncChunkWriter`] (e.g. an object store streaming upload) instead of a blocking
+//! [`std::io::Write`]. Stripes are flushed the same way the sync writer decides to (based on
+//! [`EstimateMemory`]), but each stripe's streams and footer are handed to the
+//! [`AsyncChunkWriter`] as soon as [`ColumnStripeEncoder::finish`](super::column::ColumnStripeEncoder::finish)
+//! produces them, so the whole file never needs to be buffered in memory to write it out.
+
+use std::io;
+
+use arrow::array::RecordBatch;
+use arrow::datatypes::SchemaRef;
+use bytes::Bytes;
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use prost::Message;
+use snafu::{ensure, ResultExt};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::arrow_writer::{serialize_footer, serialize_postscript, WriterMetadata};
+use crate::bloom_filter::BloomFilterConfig;
+use crate::error::{IoSnafu, Result, UnexpectedSnafu};
+use crate::memory::EstimateMemory;
+use crate::proto;
+use crate::reader::decompress::Compression;
+
+use super::column::ColumnStripeEncoder;
+use super::compress::compress_stream;
+use super::stripe::{create_encoder, StripeInformation, WrittenStream};
+use super::{statistics, ColumnEncoding, StreamType};
+
+/// Async counterpart to [`ChunkReader`](crate::reader::ChunkReader)/[`AsyncChunkReader`](crate::reader::AsyncChunkReader),
+/// for destinations that only expose an async write path (e.g. object store multipart
+/// uploads). `put_bytes` is called once per ORC stream/footer/postscript chunk, in the order
+/// they need to land in the file; `finish` is called exactly once after the last `put_bytes`
+/// to let the sink flush/commit (e.g. complete a multipart upload).
+pub trait AsyncChunkWriter: Send {
+    fn put_bytes(&mut self, bytes: Bytes) -> BoxFuture<'_, io::Result<()>>;
+
+    fn finish(&mut self) -> BoxFuture<'_, io::Result<()>>;
+}
+
+impl<T: AsyncWrite + Unpin + Send> AsyncChunkWriter for T {
+    fn put_bytes(&mut self, bytes: Bytes) -> BoxFuture<'_, io::Result<()>> {
+        async move { self.write_all(&bytes).await }.boxed()
+    }
+
+    fn finish(&mut self) -> BoxFuture<'_, io::Result<()>> {
+        async move { self.flush().await }.boxed()
+    }
+}
+
+impl AsyncChunkWriter for Box<dyn AsyncChunkWriter> {
+    fn put_bytes(&mut self, bytes: Bytes) -> BoxFuture<'_, io::Result<()>> {
+        self.as_mut().put_bytes(bytes)
+    }
+
+    fn finish(&mut self) -> BoxFuture<'_, io::Result<()>> {
+        self.as_mut().finish()
+    }
+}
+
+#[cfg(feature = "opendal")]
+mod async_opendal_writer {
+    use super::AsyncChunkWriter;
+    use bytes::Bytes;
+    use futures_util::future::BoxFuture;
+    use opendal::Operator;
+    use std::io;
+    use std::sync::Arc;
+
+    /// AsyncOpendalWriter provides native streaming upload support for [`opendal`], writing
+    /// each chunk handed to it via `put_bytes` as soon as it arrives rather than buffering
+    /// the whole file first.
+    ///
+    /// ```no_run
+    /// use opendal::Operator;
+    /// use opendal::services::MemoryConfig;
+    /// use orc_rust::writer::async_writer::AsyncOpendalWriter;
+    /// use orc_rust::writer::async_writer::AsyncChunkWriter;
+    ///
+    /// # async fn test() -> std::io::Result<()> {
+    /// let op = Operator::from_config(MemoryConfig::default())?.finish();
+    /// let mut writer = AsyncOpendalWriter::new(&op, "test").await?;
+    /// writer.put_bytes(Bytes::from_static(b"Hello, world!")).await?;
+    /// writer.finish().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub struct AsyncOpendalWriter {
+        inner: opendal::Writer,
+        path: Arc<String>,
+    }
+
+    impl AsyncOpendalWriter {
+        /// Open a streaming write to `path` under `op`.
+        pub async fn new(op: &Operator, path: &str) -> io::Result<Self> {
+            let inner = op.writer_with(path).await?;
+            Ok(Self {
+                inner,
+                path: Arc::new(path.to_string()),
+            })
+        }
+
+        /// The path this writer was opened against, mainly useful for error messages.
+        pub fn path(&self) -> &str {
+            &self.path
+        }
+    }
+
+    impl AsyncChunkWriter for AsyncOpendalWriter {
+        fn put_bytes(&mut self, bytes: Bytes) -> BoxFuture<'_, io::Result<()>> {
+            Box::pin(async move { self.inner.write(bytes).await.map_err(Into::into) })
+        }
+
+        fn finish(&mut self) -> BoxFuture<'_, io::Result<()>> {
+            Box::pin(async move {
+                self.inner.close().await?;
+                Ok(())
+            })
+        }
+    }
+}
+
+#[cfg(feature = "opendal")]
+pub use async_opendal_writer::AsyncOpendalWriter;
+
+/// Async counterpart to [`StripeWriter`](super::stripe::StripeWriter) -- see the module docs.
+pub struct AsyncStripeWriter<W> {
+    writer: W,
+    /// Flattened columns, in order of their column ID.
+    columns: Vec<Box<dyn ColumnStripeEncoder>>,
+    compression: Option<Compression>,
+    pub row_count: usize,
+}
+
+impl<W> EstimateMemory for AsyncStripeWriter<W> {
+    fn estimate_memory_size(&self) -> usize {
+        self.columns.iter().map(|c| c.estimate_memory_size()).sum()
+    }
+}
+
+impl<W: AsyncChunkWriter> AsyncStripeWriter<W> {
+    pub fn new(
+        writer: W,
+        schema: &SchemaRef,
+        compression: Option<Compression>,
+        bloom_filters: Option<&BloomFilterConfig>,
+    ) -> Self {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| create_encoder(field, bloom_filters))
+            .collect();
+        Self {
+            writer,
+            columns,
+            compression,
+            row_count: 0,
+        }
+    }
+
+    /// Attempt to encode entire [`RecordBatch`]. Relies on caller slicing the batch
+    /// to required batch size. Purely CPU-bound, so unlike `finish_stripe` this doesn't
+    /// need to be async.
+    pub fn encode_batch(&mut self, batch: &RecordBatch) -> Result<()> {
+        for (array, encoder) in batch.columns().iter().zip(self.columns.iter_mut()) {
+            encoder.encode_array(array)?;
+        }
+        self.row_count += batch.num_rows();
+        Ok(())
+    }
+
+    /// Same behaviour as [`StripeWriter::finish_stripe`](super::stripe::StripeWriter::finish_stripe),
+    /// except each stream's bytes are handed to the [`AsyncChunkWriter`] as soon as they're
+    /// produced instead of written to a buffered sync [`std::io::Write`].
+    pub async fn finish_stripe(
+        &mut self,
+        start_offset: u64,
+    ) -> Result<(StripeInformation, Vec<proto::ColumnStatistics>)> {
+        let mut column_encodings = vec![ColumnEncoding::Direct];
+        let child_column_encodings = self
+            .columns
+            .iter()
+            .map(|c| c.column_encoding())
+            .collect::<Vec<_>>();
+        column_encodings.extend(child_column_encodings);
+        let column_encodings = column_encodings.iter().map(From::from).collect();
+
+        let mut written_streams = vec![];
+
+        // A stripe's index section (bloom filters, and eventually row indexes) is laid out
+        // before its data section, so it's written first even though `finish()` -- which
+        // produces the data streams -- is called per column afterwards.
+        let mut index_length = 0;
+        for (index, c) in self.columns.iter_mut().enumerate() {
+            let column = index + 1;
+            if let Some(bloom_filter) = c.bloom_filter() {
+                let bloom_filter_index = proto::BloomFilterIndex {
+                    bloom_filter: vec![bloom_filter],
+                };
+                let bytes = bloom_filter_index.encode_to_vec();
+                let bytes = match self.compression {
+                    Some(compression) => compress_stream(&bytes, compression)?,
+                    None => bytes.into(),
+                };
+                let length = bytes.len();
+                self.writer.put_bytes(bytes).await.context(IoSnafu)?;
+                index_length += length as u64;
+                written_streams.push(WrittenStream {
+                    kind: StreamType::BloomFilter,
+                    column,
+                    length,
+                });
+            }
+        }
+
+        let mut data_length = 0;
+        for (index, c) in self.columns.iter_mut().enumerate() {
+            let column = index + 1;
+            let streams = c.finish();
+            for s in streams {
+                let (kind, bytes) = s.into_parts();
+                let bytes = match self.compression {
+                    Some(compression) => compress_stream(&bytes, compression)?,
+                    None => bytes,
+                };
+                let length = bytes.len();
+                self.writer.put_bytes(bytes).await.context(IoSnafu)?;
+                data_length += length as u64;
+                written_streams.push(WrittenStream {
+                    kind,
+                    column,
+                    length,
+                });
+            }
+        }
+        let streams = written_streams.iter().map(From::from).collect();
+        let stripe_footer = proto::StripeFooter {
+            streams,
+            columns: column_encodings,
+            writer_timezone: None,
+            encryption: vec![],
+        };
+
+        let footer_bytes = stripe_footer.encode_to_vec();
+        let footer_bytes = match self.compression {
+            Some(compression) => compress_stream(&footer_bytes, compression)?,
+            None => footer_bytes.into(),
+        };
+        let footer_length = footer_bytes.len() as u64;
+        let row_count = self.row_count;
+        self.writer
+            .put_bytes(footer_bytes)
+            .await
+            .context(IoSnafu)?;
+
+        let mut column_statistics = vec![proto::ColumnStatistics {
+            number_of_values: Some(row_count as u64),
+            has_null: Some(false),
+            ..Default::default()
+        }];
+        column_statistics.extend(self.columns.iter_mut().map(|c| c.statistics()));
+
+        self.row_count = 0;
+
+        Ok((
+            StripeInformation {
+                start_offset,
+                index_length,
+                data_length,
+                footer_length,
+                row_count,
+            },
+            column_statistics,
+        ))
+    }
+
+    /// When finished writing all stripes, flush/commit the sink and return the inner writer.
+    pub async fn finish(mut self) -> Result<W> {
+        self.writer.finish().await.context(IoSnafu)?;
+        Ok(self.writer)
+    }
+
+    /// Each column's [`ColumnEncoding`], root struct first then its children -- see
+    /// [`StripeWriter::column_encodings`](super::stripe::StripeWriter::column_encodings).
+    pub fn column_encodings(&self) -> Vec<ColumnEncoding> {
+        let mut encodings = vec![ColumnEncoding::Direct];
+        encodings.extend(self.columns.iter().map(|c| c.column_encoding()));
+        encodings
+    }
+}
+
+/// Construct an [`AsyncArrowWriter`] to encode [`RecordBatch`]es into a single ORC file,
+/// streamed out over an [`AsyncChunkWriter`]. Mirrors [`ArrowWriterBuilder`](crate::arrow_writer::ArrowWriterBuilder).
+pub struct AsyncArrowWriterBuilder<W> {
+    writer: W,
+    schema: SchemaRef,
+    batch_size: usize,
+    stripe_byte_size: usize,
+    compression: Option<Compression>,
+    bloom_filters: Option<BloomFilterConfig>,
+}
+
+impl<W: AsyncChunkWriter> AsyncArrowWriterBuilder<W> {
+    /// Create a new [`AsyncArrowWriterBuilder`], which will write an ORC file to
+    /// the provided async sink, with the expected Arrow schema.
+    pub fn new(writer: W, schema: SchemaRef) -> Self {
+        Self {
+            writer,
+            schema,
+            batch_size: 1024,
+            // 64 MiB
+            stripe_byte_size: 64 * 1024 * 1024,
+            compression: None,
+            bloom_filters: None,
+        }
+    }
+
+    /// Batch size controls the encoding behaviour, where `batch_size` values
+    /// are encoded at a time. Default is `1024`.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// The approximate size of stripes. Default is `64MiB`.
+    pub fn with_stripe_byte_size(mut self, stripe_byte_size: usize) -> Self {
+        self.stripe_byte_size = stripe_byte_size;
+        self
+    }
+
+    /// Block-compress every stream (and the stripe/file footers) with the given codec.
+    /// Default is no compression.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Write a bloom filter stream for each of [`BloomFilterConfig`]'s configured columns,
+    /// one per stripe (see the caveat on [`BloomFilterConfig`] about row-index-stride
+    /// granularity). Default is no bloom filters.
+    pub fn with_bloom_filters(mut self, bloom_filters: BloomFilterConfig) -> Self {
+        self.bloom_filters = Some(bloom_filters);
+        self
+    }
+
+    /// Construct an [`AsyncArrowWriter`] ready to encode [`RecordBatch`]es into an ORC file.
+    pub async fn try_build(mut self) -> Result<AsyncArrowWriter<W>> {
+        // Required magic "ORC" bytes at start of file
+        self.writer
+            .put_bytes(Bytes::from_static(b"ORC"))
+            .await
+            .context(IoSnafu)?;
+        let writer = AsyncStripeWriter::new(
+            self.writer,
+            &self.schema,
+            self.compression,
+            self.bloom_filters.as_ref(),
+        );
+        Ok(AsyncArrowWriter {
+            writer,
+            schema: self.schema,
+            batch_size: self.batch_size,
+            stripe_byte_size: self.stripe_byte_size,
+            compression: self.compression,
+            written_stripes: vec![],
+            stripe_statistics: vec![],
+            file_statistics: vec![],
+            // Accounting for the 3 magic bytes above
+            total_bytes_written: 3,
+        })
+    }
+}
+
+/// Encodes [`RecordBatch`]es into an ORC file over an [`AsyncChunkWriter`], flushing each
+/// stripe's streams as soon as they're encoded instead of buffering the whole file in memory.
+/// Mirrors [`ArrowWriter`](crate::arrow_writer::ArrowWriter).
+pub struct AsyncArrowWriter<W> {
+    writer: AsyncStripeWriter<W>,
+    schema: SchemaRef,
+    batch_size: usize,
+    stripe_byte_size: usize,
+    compression: Option<Compression>,
+    written_stripes: Vec<StripeInformation>,
+    stripe_statistics: Vec<proto::StripeStatistics>,
+    file_statistics: Vec<proto::ColumnStatistics>,
+    total_bytes_written: u64,
+}
+
+impl<W: AsyncChunkWriter> AsyncArrowWriter<W> {
+    /// Encode the provided batch at `batch_size` rows at a time, flushing any
+    /// stripes that exceed the configured stripe size.
+    pub async fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        ensure!(
+            batch.schema() == self.schema,
+            UnexpectedSnafu {
+                msg: "RecordBatch doesn't match expected schema"
+            }
+        );
+
+        for offset in (0..batch.num_rows()).step_by(self.batch_size) {
+            let length = self.batch_size.min(batch.num_rows() - offset);
+            let batch = batch.slice(offset, length);
+            self.writer.encode_batch(&batch)?;
+
+            if self.writer.estimate_memory_size() > self.stripe_byte_size {
+                self.flush_stripe().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush any buffered data that hasn't been written, and write the stripe
+    /// footer metadata.
+    pub async fn flush_stripe(&mut self) -> Result<()> {
+        let (info, column_statistics) =
+            self.writer.finish_stripe(self.total_bytes_written).await?;
+        self.total_bytes_written += info.total_byte_size();
+        self.written_stripes.push(info);
+
+        if self.file_statistics.is_empty() {
+            self.file_statistics = column_statistics.clone();
+        } else {
+            for (total, stripe) in self.file_statistics.iter_mut().zip(&column_statistics) {
+                statistics::merge_into(total, stripe);
+            }
+        }
+        self.stripe_statistics.push(proto::StripeStatistics {
+            col_stats: column_statistics,
+        });
+
+        Ok(())
+    }
+
+    /// Flush the current stripe if it is still in progress, and write the tail
+    /// metadata and close the sink.
+    pub async fn close(mut self) -> Result<WriterMetadata> {
+        if self.writer.row_count > 0 {
+            self.flush_stripe().await?;
+        }
+
+        let column_encodings = self.writer.column_encodings();
+        let number_of_rows = self.written_stripes.iter().map(|s| s.row_count as u64).sum();
+
+        let metadata = proto::Metadata {
+            stripe_stats: self.stripe_statistics,
+        };
+        let metadata = metadata.encode_to_vec();
+        let metadata = match self.compression {
+            Some(compression) => compress_stream(&metadata, compression)?,
+            None => metadata.into(),
+        };
+        let metadata_length = metadata.len() as u64;
+
+        let footer = serialize_footer(
+            &self.written_stripes,
+            &self.schema,
+            self.file_statistics.clone(),
+        )?;
+        let footer = footer.encode_to_vec();
+        let footer = match self.compression {
+            Some(compression) => compress_stream(&footer, compression)?,
+            None => footer.into(),
+        };
+        let postscript =
+            serialize_postscript(footer.len() as u64, metadata_length, self.compression);
+        let postscript = postscript.encode_to_vec();
+        let postscript_len = postscript.len() as u8;
+
+        let mut writer = self.writer.finish().await?;
+        writer.put_bytes(metadata).await.context(IoSnafu)?;
+        writer.put_bytes(footer).await.context(IoSnafu)?;
+        writer.put_bytes(postscript.into()).await.context(IoSnafu)?;
+        // Postscript length as last byte
+        writer
+            .put_bytes(Bytes::from(vec![postscript_len]))
+            .await
+            .context(IoSnafu)?;
+        writer.finish().await.context(IoSnafu)?;
+
+        Ok(WriterMetadata {
+            stripes: self.written_stripes,
+            number_of_rows,
+            column_encodings,
+            statistics: self.file_statistics,
+        })
+    }
+}
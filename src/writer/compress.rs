@@ -0,0 +1,167 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Compression of a finished stream into the blocked format
+//! [`Decompressor`](crate::reader::decompress::Decompressor) expects to read back.
+
+use bytes::{Bytes, BytesMut};
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+use crate::reader::decompress::{encode_header, Compression, CompressionType};
+
+trait CompressorVariant {
+    /// Compresses `bytes` into `scratch`, overwriting whatever was there before.
+    fn compress_block(&self, bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()>;
+}
+
+#[cfg(feature = "zlib")]
+struct Zlib {
+    level: flate2::Compression,
+}
+
+#[cfg(feature = "zlib")]
+impl CompressorVariant for Zlib {
+    fn compress_block(&self, bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
+        use std::io::Write;
+
+        scratch.clear();
+        let mut encoder = flate2::write::DeflateEncoder::new(scratch, self.level);
+        encoder.write_all(bytes).context(error::IoSnafu)?;
+        encoder.finish().context(error::IoSnafu)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "snappy")]
+struct Snappy;
+
+#[cfg(feature = "snappy")]
+impl CompressorVariant for Snappy {
+    fn compress_block(&self, bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
+        scratch.clear();
+        let mut encoder = snap::raw::Encoder::new();
+        let compressed = encoder
+            .compress_vec(bytes)
+            .context(error::BuildSnappyEncoderSnafu)?;
+        *scratch = compressed;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "lz4")]
+struct Lz4;
+
+#[cfg(feature = "lz4")]
+impl CompressorVariant for Lz4 {
+    fn compress_block(&self, bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
+        *scratch = lz4_flex::block::compress(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zstd")]
+struct Zstd {
+    level: i32,
+}
+
+#[cfg(feature = "zstd")]
+impl CompressorVariant for Zstd {
+    fn compress_block(&self, bytes: &[u8], scratch: &mut Vec<u8>) -> Result<()> {
+        *scratch = zstd::encode_all(bytes, self.level).context(error::IoSnafu)?;
+        Ok(())
+    }
+}
+
+fn get_compressor_variant(
+    compression_type: CompressionType,
+    level: Option<i32>,
+) -> Box<dyn CompressorVariant> {
+    match compression_type {
+        #[cfg(feature = "zlib")]
+        CompressionType::Zlib => Box::new(Zlib {
+            level: level
+                .map(|l| flate2::Compression::new(l as u32))
+                .unwrap_or_default(),
+        }),
+        #[cfg(feature = "snappy")]
+        CompressionType::Snappy => Box::new(Snappy),
+        #[cfg(feature = "lz4")]
+        CompressionType::Lz4 => Box::new(Lz4),
+        #[cfg(feature = "zstd")]
+        CompressionType::Zstd => Box::new(Zstd {
+            level: level.unwrap_or(0),
+        }),
+    }
+}
+
+/// Splits `bytes` into blocks of at most `compression`'s configured block size, compressing
+/// each one independently and prefixing it with the 3 byte header
+/// [`Decompressor`](crate::reader::decompress::Decompressor) expects. A block that doesn't
+/// shrink from compressing (e.g. already-compressed or high-entropy data) is stored as-is,
+/// matching what other ORC writers do to avoid wasting cycles expanding incompressible data.
+pub fn compress_stream(bytes: &[u8], compression: Compression) -> Result<Bytes> {
+    let variant = get_compressor_variant(compression.compression_type(), compression.level());
+    let block_size = compression.block_size();
+
+    let mut out = BytesMut::with_capacity(bytes.len());
+    let mut scratch = Vec::new();
+    for chunk in bytes.chunks(block_size.max(1)) {
+        variant.compress_block(chunk, &mut scratch)?;
+        if scratch.len() < chunk.len() {
+            out.extend_from_slice(&encode_header(scratch.len() as u32, false));
+            out.extend_from_slice(&scratch);
+        } else {
+            out.extend_from_slice(&encode_header(chunk.len() as u32, true));
+            out.extend_from_slice(chunk);
+        }
+    }
+    Ok(out.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn compress_stream_roundtrips_through_decompressor() {
+        use crate::reader::decompress::Decompressor;
+        use std::io::Read;
+
+        let compression = Compression::new(CompressionType::Zstd, 256);
+        let data = b"hello hello hello hello hello hello hello hello".repeat(10);
+
+        let compressed = compress_stream(&data, compression).unwrap();
+
+        let mut decompressor = Decompressor::new(compressed, Some(compression), vec![]);
+        let mut decompressed = Vec::new();
+        decompressor.read_to_end(&mut decompressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn compress_stream_stores_incompressible_block_as_original() {
+        // A single random-ish byte below any meaningful compression ratio still round-trips.
+        let compression = Compression::new(CompressionType::Zstd, 256);
+        let data = vec![0, 1, 2, 3];
+
+        let compressed = compress_stream(&data, compression).unwrap();
+        assert!(!compressed.is_empty());
+    }
+}
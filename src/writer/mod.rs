@@ -21,7 +21,11 @@ use bytes::Bytes;
 
 use crate::proto;
 
+#[cfg(feature = "async")]
+pub mod async_writer;
+pub(crate) mod compress;
 pub mod column;
+pub(crate) mod statistics;
 pub mod stripe;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -31,6 +35,7 @@ pub enum StreamType {
     Length,
     DictionaryData,
     Secondary,
+    BloomFilter,
 }
 
 impl From<StreamType> for proto::stream::Kind {
@@ -41,6 +46,7 @@ impl From<StreamType> for proto::stream::Kind {
             StreamType::Length => proto::stream::Kind::Length,
             StreamType::DictionaryData => proto::stream::Kind::DictionaryData,
             StreamType::Secondary => proto::stream::Kind::Secondary,
+            StreamType::BloomFilter => proto::stream::Kind::BloomFilter,
         }
     }
 }
@@ -0,0 +1,498 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Accumulates per-column statistics as [`ColumnStripeEncoder`](super::column::ColumnStripeEncoder)s
+//! encode values, so they can be emitted into the stripe footer, file footer and `Metadata`
+//! message as [`proto::ColumnStatistics`].
+
+use arrow::datatypes::{GenericBinaryType, GenericStringType, OffsetSizeTrait};
+
+use crate::proto;
+
+#[derive(Debug, Clone)]
+enum StatisticsKind {
+    Integer {
+        min: Option<i64>,
+        max: Option<i64>,
+        /// `None` once a running sum has overflowed, matching how the reader already
+        /// treats a missing sum (see [`crate::statistics::TypeStatistics::Integer`]).
+        sum: Option<i64>,
+    },
+    Double {
+        min: Option<f64>,
+        max: Option<f64>,
+        sum: f64,
+    },
+    String {
+        min: Option<String>,
+        max: Option<String>,
+        sum: i64,
+    },
+    Binary {
+        sum: i64,
+    },
+    Boolean {
+        true_count: u64,
+    },
+    /// Row/null count only. Used for types whose dedicated stats shape (e.g.
+    /// [`proto::DateStatistics`], [`proto::DecimalStatistics`], [`proto::TimestampStatistics`])
+    /// this builder doesn't populate yet -- the `number_of_values`/`has_null` counters
+    /// [`ColumnStatisticsBuilder`] already tracks outside of `kind` are still correct and
+    /// useful on their own, so they're emitted with no type-specific statistics attached
+    /// rather than not at all.
+    None,
+}
+
+impl StatisticsKind {
+    fn empty_like(&self) -> Self {
+        match self {
+            Self::Integer { .. } => Self::Integer {
+                min: None,
+                max: None,
+                sum: Some(0),
+            },
+            Self::Double { .. } => Self::Double {
+                min: None,
+                max: None,
+                sum: 0.0,
+            },
+            Self::String { .. } => Self::String {
+                min: None,
+                max: None,
+                sum: 0,
+            },
+            Self::Binary { .. } => Self::Binary { sum: 0 },
+            Self::Boolean { .. } => Self::Boolean { true_count: 0 },
+            Self::None => Self::None,
+        }
+    }
+
+    fn into_proto(self) -> proto::ColumnStatistics {
+        match self {
+            Self::Integer { min, max, sum } => proto::ColumnStatistics {
+                int_statistics: Some(proto::IntegerStatistics {
+                    minimum: min,
+                    maximum: max,
+                    sum,
+                }),
+                ..Default::default()
+            },
+            Self::Double { min, max, sum } => proto::ColumnStatistics {
+                double_statistics: Some(proto::DoubleStatistics {
+                    minimum: min,
+                    maximum: max,
+                    sum: Some(sum),
+                }),
+                ..Default::default()
+            },
+            Self::String { min, max, sum } => proto::ColumnStatistics {
+                string_statistics: Some(proto::StringStatistics {
+                    minimum: min,
+                    maximum: max,
+                    sum: Some(sum),
+                }),
+                ..Default::default()
+            },
+            Self::Binary { sum } => proto::ColumnStatistics {
+                binary_statistics: Some(proto::BinaryStatistics { sum: Some(sum) }),
+                ..Default::default()
+            },
+            Self::Boolean { true_count } => proto::ColumnStatistics {
+                bucket_statistics: Some(proto::BucketStatistics {
+                    count: vec![true_count],
+                }),
+                ..Default::default()
+            },
+            Self::None => proto::ColumnStatistics::default(),
+        }
+    }
+}
+
+/// Accumulates a single column's statistics across the values encoded into a stripe so far.
+/// [`Self::finish`] snapshots the accumulated totals into a [`proto::ColumnStatistics`] and
+/// resets back to empty, the same "take the buffered state, leave it ready for the next
+/// stripe" pattern [`BooleanEncoder::finish`](crate::encoding::boolean::BooleanEncoder::finish)
+/// already uses.
+#[derive(Debug, Clone)]
+pub struct ColumnStatisticsBuilder {
+    number_of_values: u64,
+    has_null: bool,
+    kind: StatisticsKind,
+}
+
+impl ColumnStatisticsBuilder {
+    pub fn new_integer() -> Self {
+        Self {
+            number_of_values: 0,
+            has_null: false,
+            kind: StatisticsKind::Integer {
+                min: None,
+                max: None,
+                sum: Some(0),
+            },
+        }
+    }
+
+    pub fn new_double() -> Self {
+        Self {
+            number_of_values: 0,
+            has_null: false,
+            kind: StatisticsKind::Double {
+                min: None,
+                max: None,
+                sum: 0.0,
+            },
+        }
+    }
+
+    pub fn new_string() -> Self {
+        Self {
+            number_of_values: 0,
+            has_null: false,
+            kind: StatisticsKind::String {
+                min: None,
+                max: None,
+                sum: 0,
+            },
+        }
+    }
+
+    pub fn new_binary() -> Self {
+        Self {
+            number_of_values: 0,
+            has_null: false,
+            kind: StatisticsKind::Binary { sum: 0 },
+        }
+    }
+
+    pub fn new_boolean() -> Self {
+        Self {
+            number_of_values: 0,
+            has_null: false,
+            kind: StatisticsKind::Boolean { true_count: 0 },
+        }
+    }
+
+    /// For column types (Date, Decimal, Timestamp) whose dedicated statistics shape isn't
+    /// tracked yet -- see [`StatisticsKind::None`]. [`Self::add_row`] still keeps
+    /// `number_of_values`/`has_null` accurate for these.
+    pub fn new_none() -> Self {
+        Self {
+            number_of_values: 0,
+            has_null: false,
+            kind: StatisticsKind::None,
+        }
+    }
+
+    /// Counts one encoded, non-null value without recording any type-specific statistic.
+    /// Used by column types built on [`ColumnStatisticsBuilder::new_none`].
+    pub fn add_row(&mut self) {
+        self.number_of_values += 1;
+    }
+
+    pub fn add_null(&mut self) {
+        self.has_null = true;
+    }
+
+    pub fn add_integer(&mut self, value: i64) {
+        self.number_of_values += 1;
+        if let StatisticsKind::Integer { min, max, sum } = &mut self.kind {
+            *min = Some(min.map_or(value, |m| m.min(value)));
+            *max = Some(max.map_or(value, |m| m.max(value)));
+            *sum = sum.and_then(|s| s.checked_add(value));
+        }
+    }
+
+    pub fn add_double(&mut self, value: f64) {
+        self.number_of_values += 1;
+        if let StatisticsKind::Double { min, max, sum } = &mut self.kind {
+            *min = Some(min.map_or(value, |m| m.min(value)));
+            *max = Some(max.map_or(value, |m| m.max(value)));
+            *sum += value;
+        }
+    }
+
+    pub fn add_string(&mut self, value: &str) {
+        self.number_of_values += 1;
+        if let StatisticsKind::String { min, max, sum } = &mut self.kind {
+            *sum += value.len() as i64;
+            match min {
+                Some(m) if value >= m.as_str() => {}
+                _ => *min = Some(value.to_owned()),
+            }
+            match max {
+                Some(m) if value <= m.as_str() => {}
+                _ => *max = Some(value.to_owned()),
+            }
+        }
+    }
+
+    pub fn add_binary(&mut self, length: i64) {
+        self.number_of_values += 1;
+        if let StatisticsKind::Binary { sum } = &mut self.kind {
+            *sum += length;
+        }
+    }
+
+    pub fn add_boolean(&mut self, value: bool) {
+        self.number_of_values += 1;
+        if let StatisticsKind::Boolean { true_count } = &mut self.kind {
+            if value {
+                *true_count += 1;
+            }
+        }
+    }
+
+    pub fn finish(&mut self) -> proto::ColumnStatistics {
+        let number_of_values = self.number_of_values;
+        let has_null = self.has_null;
+        let kind = std::mem::replace(&mut self.kind, self.kind.empty_like());
+        self.number_of_values = 0;
+        self.has_null = false;
+        proto::ColumnStatistics {
+            number_of_values: Some(number_of_values),
+            has_null: Some(has_null),
+            ..kind.into_proto()
+        }
+    }
+}
+
+/// Lets [`PrimitiveColumnEncoder`](super::column::PrimitiveColumnEncoder) build the right kind
+/// of [`ColumnStatisticsBuilder`] for its native Arrow type and feed encoded values into it,
+/// without needing its own copy of the Integer-vs-Double split.
+pub trait TrackStatistics: Copy {
+    fn empty_builder() -> ColumnStatisticsBuilder;
+    fn track(builder: &mut ColumnStatisticsBuilder, value: Self);
+
+    /// Raw bytes to hash into a [`BloomFilterBuilder`](crate::bloom_filter::BloomFilterBuilder)
+    /// for this value, per the ORC bloom filter spec's "8-byte little-endian for integers"
+    /// convention. `None` for types without a well-defined bloom filter byte representation
+    /// (e.g. floats) -- [`PrimitiveColumnEncoder`](super::column::PrimitiveColumnEncoder) simply
+    /// skips tracking a bloom filter entry for those values.
+    fn bloom_filter_bytes(_value: Self) -> Option<[u8; 8]> {
+        None
+    }
+}
+
+macro_rules! track_as_integer {
+    ($ty:ty) => {
+        impl TrackStatistics for $ty {
+            fn empty_builder() -> ColumnStatisticsBuilder {
+                ColumnStatisticsBuilder::new_integer()
+            }
+
+            fn track(builder: &mut ColumnStatisticsBuilder, value: Self) {
+                builder.add_integer(value as i64);
+            }
+
+            fn bloom_filter_bytes(value: Self) -> Option<[u8; 8]> {
+                Some((value as i64).to_le_bytes())
+            }
+        }
+    };
+}
+
+track_as_integer!(i8);
+track_as_integer!(i16);
+track_as_integer!(i32);
+track_as_integer!(i64);
+
+macro_rules! track_as_double {
+    ($ty:ty) => {
+        impl TrackStatistics for $ty {
+            fn empty_builder() -> ColumnStatisticsBuilder {
+                ColumnStatisticsBuilder::new_double()
+            }
+
+            fn track(builder: &mut ColumnStatisticsBuilder, value: Self) {
+                builder.add_double(value as f64);
+            }
+        }
+    };
+}
+
+track_as_double!(f32);
+track_as_double!(f64);
+
+/// Lets [`GenericBinaryColumnEncoder`](super::column::GenericBinaryColumnEncoder) know whether
+/// to track [`StringStatistics`](proto::StringStatistics) (with min/max) or
+/// [`BinaryStatistics`](proto::BinaryStatistics) (sum of lengths only) for its Arrow byte type.
+pub trait TrackByteStatistics {
+    fn empty_builder() -> ColumnStatisticsBuilder;
+    fn track(builder: &mut ColumnStatisticsBuilder, value: &[u8]);
+
+    /// Whether this byte type's raw value bytes are safe to hash into a
+    /// [`BloomFilterBuilder`](crate::bloom_filter::BloomFilterBuilder). `true` only for UTF-8
+    /// strings, matching the ORC bloom filter spec -- plain binary columns aren't covered.
+    fn supports_bloom_filter() -> bool {
+        false
+    }
+}
+
+impl<O: OffsetSizeTrait> TrackByteStatistics for GenericStringType<O> {
+    fn empty_builder() -> ColumnStatisticsBuilder {
+        ColumnStatisticsBuilder::new_string()
+    }
+
+    fn track(builder: &mut ColumnStatisticsBuilder, value: &[u8]) {
+        // Arrow guarantees UTF-8 validity for values backing a GenericStringType array.
+        let value = std::str::from_utf8(value).unwrap_or_default();
+        builder.add_string(value);
+    }
+
+    fn supports_bloom_filter() -> bool {
+        true
+    }
+}
+
+impl<O: OffsetSizeTrait> TrackByteStatistics for GenericBinaryType<O> {
+    fn empty_builder() -> ColumnStatisticsBuilder {
+        ColumnStatisticsBuilder::new_binary()
+    }
+
+    fn track(builder: &mut ColumnStatisticsBuilder, value: &[u8]) {
+        builder.add_binary(value.len() as i64);
+    }
+}
+
+fn merge_min<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a <= b { a } else { b }),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn merge_max<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a >= b { a } else { b }),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Accumulates `stripe`'s statistics into `total`, used to roll each stripe's
+/// [`proto::ColumnStatistics`] up into the file-level totals written to `Footer.statistics`.
+pub fn merge_into(total: &mut proto::ColumnStatistics, stripe: &proto::ColumnStatistics) {
+    total.number_of_values = Some(total.number_of_values() + stripe.number_of_values());
+    total.has_null = Some(total.has_null() || stripe.has_null());
+
+    if let Some(s) = &stripe.int_statistics {
+        let t = total.int_statistics.get_or_insert_with(Default::default);
+        t.minimum = merge_min(t.minimum, s.minimum);
+        t.maximum = merge_max(t.maximum, s.maximum);
+        t.sum = t.sum.zip(s.sum).and_then(|(a, b)| a.checked_add(b));
+    }
+    if let Some(s) = &stripe.double_statistics {
+        let t = total.double_statistics.get_or_insert_with(Default::default);
+        t.minimum = merge_min(t.minimum, s.minimum);
+        t.maximum = merge_max(t.maximum, s.maximum);
+        t.sum = Some(t.sum.unwrap_or(0.0) + s.sum.unwrap_or(0.0));
+    }
+    if let Some(s) = &stripe.string_statistics {
+        let t = total.string_statistics.get_or_insert_with(Default::default);
+        t.minimum = merge_min(t.minimum.take(), s.minimum.clone());
+        t.maximum = merge_max(t.maximum.take(), s.maximum.clone());
+        t.sum = Some(t.sum.unwrap_or(0) + s.sum.unwrap_or(0));
+    }
+    if let Some(s) = &stripe.binary_statistics {
+        let t = total.binary_statistics.get_or_insert_with(Default::default);
+        t.sum = Some(t.sum.unwrap_or(0) + s.sum.unwrap_or(0));
+    }
+    if let Some(s) = &stripe.bucket_statistics {
+        let t = total.bucket_statistics.get_or_insert_with(Default::default);
+        if t.count.is_empty() {
+            t.count = s.count.clone();
+        } else {
+            for (a, b) in t.count.iter_mut().zip(s.count.iter()) {
+                *a += b;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_statistics_roundtrip_through_proto() {
+        let mut builder = ColumnStatisticsBuilder::new_integer();
+        builder.add_integer(5);
+        builder.add_integer(-2);
+        builder.add_null();
+
+        let stats = builder.finish();
+        assert_eq!(stats.number_of_values(), 2);
+        assert!(stats.has_null());
+        let int_stats = stats.int_statistics.unwrap();
+        assert_eq!(int_stats.minimum(), -2);
+        assert_eq!(int_stats.maximum(), 5);
+        assert_eq!(int_stats.sum(), 3);
+
+        // Builder should have reset back to empty after finish().
+        let stats = builder.finish();
+        assert_eq!(stats.number_of_values(), 0);
+        assert!(!stats.has_null());
+    }
+
+    #[test]
+    fn string_statistics_track_min_max_and_length_sum() {
+        let mut builder = ColumnStatisticsBuilder::new_string();
+        builder.add_string("banana");
+        builder.add_string("apple");
+
+        let stats = builder.finish();
+        let string_stats = stats.string_statistics.unwrap();
+        assert_eq!(string_stats.minimum(), "apple");
+        assert_eq!(string_stats.maximum(), "banana");
+        assert_eq!(string_stats.sum(), "banana".len() as i64 + "apple".len() as i64);
+    }
+
+    #[test]
+    fn merge_into_combines_min_max_and_sum_across_stripes() {
+        let mut total = proto::ColumnStatistics {
+            number_of_values: Some(2),
+            has_null: Some(false),
+            int_statistics: Some(proto::IntegerStatistics {
+                minimum: Some(0),
+                maximum: Some(10),
+                sum: Some(10),
+            }),
+            ..Default::default()
+        };
+        let stripe = proto::ColumnStatistics {
+            number_of_values: Some(3),
+            has_null: Some(true),
+            int_statistics: Some(proto::IntegerStatistics {
+                minimum: Some(-5),
+                maximum: Some(4),
+                sum: Some(6),
+            }),
+            ..Default::default()
+        };
+
+        merge_into(&mut total, &stripe);
+
+        assert_eq!(total.number_of_values(), 5);
+        assert!(total.has_null());
+        let int_stats = total.int_statistics.unwrap();
+        assert_eq!(int_stats.minimum(), -5);
+        assert_eq!(int_stats.maximum(), 10);
+        assert_eq!(int_stats.sum(), 16);
+    }
+}
@@ -22,15 +22,19 @@ use arrow::datatypes::{DataType as ArrowDataType, FieldRef, SchemaRef};
 use prost::Message;
 use snafu::ResultExt;
 
+use crate::bloom_filter::BloomFilterConfig;
 use crate::error::{IoSnafu, Result};
 use crate::memory::EstimateMemory;
 use crate::proto;
+use crate::reader::decompress::Compression;
 
 use super::column::{
     BinaryColumnEncoder, BooleanColumnEncoder, ByteColumnEncoder, ColumnStripeEncoder,
-    DoubleColumnEncoder, FloatColumnEncoder, Int16ColumnEncoder, Int32ColumnEncoder,
-    Int64ColumnEncoder, LargeBinaryColumnEncoder, LargeStringColumnEncoder, StringColumnEncoder,
+    Date32ColumnEncoder, DecimalColumnEncoder, DoubleColumnEncoder, FloatColumnEncoder,
+    Int16ColumnEncoder, Int32ColumnEncoder, Int64ColumnEncoder, LargeBinaryColumnEncoder,
+    LargeStringColumnEncoder, StringColumnEncoder, TimestampColumnEncoder,
 };
+use super::compress::compress_stream;
 use super::{ColumnEncoding, StreamType};
 
 #[derive(Copy, Clone, Eq, Debug, PartialEq)]
@@ -68,6 +72,7 @@ pub struct StripeWriter<W> {
     writer: W,
     /// Flattened columns, in order of their column ID.
     columns: Vec<Box<dyn ColumnStripeEncoder>>,
+    compression: Option<Compression>,
     pub row_count: usize,
 }
 
@@ -80,11 +85,21 @@ impl<W> EstimateMemory for StripeWriter<W> {
 }
 
 impl<W: Write> StripeWriter<W> {
-    pub fn new(writer: W, schema: &SchemaRef) -> Self {
-        let columns = schema.fields().iter().map(create_encoder).collect();
+    pub fn new(
+        writer: W,
+        schema: &SchemaRef,
+        compression: Option<Compression>,
+        bloom_filters: Option<&BloomFilterConfig>,
+    ) -> Self {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| create_encoder(field, bloom_filters))
+            .collect();
         Self {
             writer,
             columns,
+            compression,
             row_count: 0,
         }
     }
@@ -105,10 +120,17 @@ impl<W: Write> StripeWriter<W> {
     /// to write a fresh new stripe.
     ///
     /// `start_offset` is used to manually keep track of position in the writer (instead
-    /// of relying on Seek).
-    pub fn finish_stripe(&mut self, start_offset: u64) -> Result<StripeInformation> {
+    /// of relying on Seek). Alongside the [`StripeInformation`], returns each column's
+    /// [`proto::ColumnStatistics`] for this stripe (root struct column first, then its
+    /// children in the same order as
+    /// [`arrow_schema_to_orc`](crate::schema::arrow_schema_to_orc)),
+    /// for the caller to fold into the file's `Metadata` and `Footer.statistics`.
+    pub fn finish_stripe(
+        &mut self,
+        start_offset: u64,
+    ) -> Result<(StripeInformation, Vec<proto::ColumnStatistics>)> {
         // Order of column_encodings needs to match final type vec order.
-        // (see arrow_writer::serialize_schema())
+        // (see schema::arrow_schema_to_orc())
         // Direct encoding to represent root struct
         let mut column_encodings = vec![ColumnEncoding::Direct];
         let child_column_encodings = self
@@ -121,6 +143,33 @@ impl<W: Write> StripeWriter<W> {
 
         // Root type won't have any streams
         let mut written_streams = vec![];
+
+        // A stripe's index section (bloom filters, and eventually row indexes) is laid out
+        // before its data section, so it's written first even though `finish()` -- which
+        // produces the data streams -- is called per column afterwards.
+        let mut index_length = 0;
+        for (index, c) in self.columns.iter_mut().enumerate() {
+            let column = index + 1;
+            if let Some(bloom_filter) = c.bloom_filter() {
+                let bloom_filter_index = proto::BloomFilterIndex {
+                    bloom_filter: vec![bloom_filter],
+                };
+                let bytes = bloom_filter_index.encode_to_vec();
+                let bytes = match self.compression {
+                    Some(compression) => compress_stream(&bytes, compression)?,
+                    None => bytes.into(),
+                };
+                let length = bytes.len();
+                self.writer.write_all(&bytes).context(IoSnafu)?;
+                index_length += length as u64;
+                written_streams.push(WrittenStream {
+                    kind: StreamType::BloomFilter,
+                    column,
+                    length,
+                });
+            }
+        }
+
         let mut data_length = 0;
         for (index, c) in self.columns.iter_mut().enumerate() {
             // Offset by 1 to account for root of 0
@@ -129,6 +178,10 @@ impl<W: Write> StripeWriter<W> {
             // Flush the streams to the writer
             for s in streams {
                 let (kind, bytes) = s.into_parts();
+                let bytes = match self.compression {
+                    Some(compression) => compress_stream(&bytes, compression)?,
+                    None => bytes,
+                };
                 let length = bytes.len();
                 self.writer.write_all(&bytes).context(IoSnafu)?;
                 data_length += length as u64;
@@ -148,51 +201,107 @@ impl<W: Write> StripeWriter<W> {
         };
 
         let footer_bytes = stripe_footer.encode_to_vec();
+        let footer_bytes = match self.compression {
+            Some(compression) => compress_stream(&footer_bytes, compression)?,
+            None => footer_bytes.into(),
+        };
         let footer_length = footer_bytes.len() as u64;
         let row_count = self.row_count;
         self.writer.write_all(&footer_bytes).context(IoSnafu)?;
 
+        // Root column has no streams of its own, so it only tracks the row count.
+        let mut column_statistics = vec![proto::ColumnStatistics {
+            number_of_values: Some(row_count as u64),
+            has_null: Some(false),
+            ..Default::default()
+        }];
+        column_statistics.extend(self.columns.iter_mut().map(|c| c.statistics()));
+
         // Reset state for next stripe
         self.row_count = 0;
 
-        Ok(StripeInformation {
-            start_offset,
-            index_length: 0,
-            data_length,
-            footer_length,
-            row_count,
-        })
+        Ok((
+            StripeInformation {
+                start_offset,
+                index_length,
+                data_length,
+                footer_length,
+                row_count,
+            },
+            column_statistics,
+        ))
     }
 
     /// When finished writing all stripes, return the inner writer.
     pub fn finish(self) -> W {
         self.writer
     }
+
+    /// Each column's [`ColumnEncoding`], root struct first then its children in the same
+    /// order [`finish_stripe`](Self::finish_stripe) returns their statistics in. Fixed for the
+    /// lifetime of the writer, so callers don't need a stripe in hand to ask for it.
+    pub fn column_encodings(&self) -> Vec<ColumnEncoding> {
+        let mut encodings = vec![ColumnEncoding::Direct];
+        encodings.extend(self.columns.iter().map(|c| c.column_encoding()));
+        encodings
+    }
 }
 
-fn create_encoder(field: &FieldRef) -> Box<dyn ColumnStripeEncoder> {
+pub(crate) fn create_encoder(
+    field: &FieldRef,
+    bloom_filters: Option<&BloomFilterConfig>,
+) -> Box<dyn ColumnStripeEncoder> {
+    // Only integer and UTF-8 string columns have a bloom-filter byte representation defined
+    // (see `TrackStatistics::bloom_filter_bytes`/`TrackByteStatistics::supports_bloom_filter`);
+    // `with_bloom_filter` below is a no-op for any other column type.
+    let bloom_filter = || {
+        bloom_filters
+            .filter(|config| config.is_enabled_for(field.name()))
+            .map(BloomFilterConfig::new_builder)
+    };
     match field.data_type() {
         ArrowDataType::Float32 => Box::new(FloatColumnEncoder::new(ColumnEncoding::Direct)),
         ArrowDataType::Float64 => Box::new(DoubleColumnEncoder::new(ColumnEncoding::Direct)),
-        ArrowDataType::Int8 => Box::new(ByteColumnEncoder::new(ColumnEncoding::Direct)),
-        ArrowDataType::Int16 => Box::new(Int16ColumnEncoder::new(ColumnEncoding::DirectV2)),
-        ArrowDataType::Int32 => Box::new(Int32ColumnEncoder::new(ColumnEncoding::DirectV2)),
-        ArrowDataType::Int64 => Box::new(Int64ColumnEncoder::new(ColumnEncoding::DirectV2)),
-        ArrowDataType::Utf8 => Box::new(StringColumnEncoder::new()),
-        ArrowDataType::LargeUtf8 => Box::new(LargeStringColumnEncoder::new()),
+        ArrowDataType::Int8 => Box::new(
+            ByteColumnEncoder::new(ColumnEncoding::Direct).with_bloom_filter(bloom_filter()),
+        ),
+        ArrowDataType::Int16 => Box::new(
+            Int16ColumnEncoder::new(ColumnEncoding::DirectV2).with_bloom_filter(bloom_filter()),
+        ),
+        ArrowDataType::Int32 => Box::new(
+            Int32ColumnEncoder::new(ColumnEncoding::DirectV2).with_bloom_filter(bloom_filter()),
+        ),
+        ArrowDataType::Int64 => Box::new(
+            Int64ColumnEncoder::new(ColumnEncoding::DirectV2).with_bloom_filter(bloom_filter()),
+        ),
+        ArrowDataType::Utf8 => {
+            Box::new(StringColumnEncoder::new().with_bloom_filter(bloom_filter()))
+        }
+        ArrowDataType::LargeUtf8 => {
+            Box::new(LargeStringColumnEncoder::new().with_bloom_filter(bloom_filter()))
+        }
         ArrowDataType::Binary => Box::new(BinaryColumnEncoder::new()),
         ArrowDataType::LargeBinary => Box::new(LargeBinaryColumnEncoder::new()),
         ArrowDataType::Boolean => Box::new(BooleanColumnEncoder::new()),
-        // TODO: support more datatypes
+        ArrowDataType::Date32 => Box::new(Date32ColumnEncoder::new(ColumnEncoding::DirectV2)),
+        ArrowDataType::Decimal128(_, scale) => Box::new(DecimalColumnEncoder::new(*scale as i32)),
+        ArrowDataType::Timestamp(arrow::datatypes::TimeUnit::Nanosecond, None) => {
+            Box::new(TimestampColumnEncoder::new())
+        }
+        // Struct/List/LargeList/Map need `StripeWriter` itself to recurse (a struct's children
+        // are encoded as sibling columns sharing the parent's row validity, and list/map need a
+        // Length stream over each row's child count plus recursively-encoded child arrays),
+        // which is a bigger change than this column-encoder factory can make alone.
+        // TODO: support nested datatypes (Struct, List, LargeList, Map)
         _ => unimplemented!("unsupported datatype"),
     }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-struct WrittenStream {
-    kind: StreamType,
-    column: usize,
-    length: usize,
+pub(crate) struct WrittenStream {
+    pub kind: StreamType,
+    pub column: usize,
+    pub length: usize,
 }
 
 impl From<&WrittenStream> for proto::Stream {
@@ -19,12 +19,17 @@ use std::fs::File;
 use std::ops::Range;
 use std::sync::Arc;
 
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, StringArray, TimestampNanosecondArray,
+};
+use arrow::compute::concat_batches;
 use arrow::datatypes::{DataType, Decimal128Type, DecimalType, Field, Schema, TimeUnit};
 use arrow::record_batch::{RecordBatch, RecordBatchReader};
 use arrow::util::pretty;
 #[cfg(feature = "async")]
 use futures_util::TryStreamExt;
 use orc_rust::arrow_reader::{ArrowReader, ArrowReaderBuilder};
+use orc_rust::encoding::timestamp::TimestampOverflowMode;
 #[cfg(feature = "async")]
 use orc_rust::async_arrow_reader::ArrowStreamReader;
 use orc_rust::projection::ProjectionMask;
@@ -195,6 +200,71 @@ pub fn basic_test() {
         "+-----+-------+------------+-----+-----+-------+",
     ];
     assert_batches_eq(&batch, &expected);
+
+    let expected = RecordBatch::try_from_iter([
+        (
+            "a",
+            Arc::new(Float64Array::from(vec![
+                Some(1.0),
+                Some(2.0),
+                None,
+                Some(4.0),
+                Some(5.0),
+            ])) as ArrayRef,
+        ),
+        (
+            "b",
+            Arc::new(BooleanArray::from(vec![
+                Some(true),
+                Some(false),
+                None,
+                Some(true),
+                Some(false),
+            ])) as ArrayRef,
+        ),
+        (
+            "str_direct",
+            Arc::new(StringArray::from(vec![
+                Some("a"),
+                Some("cccccc"),
+                None,
+                Some("ddd"),
+                Some("ee"),
+            ])) as ArrayRef,
+        ),
+        (
+            "d",
+            Arc::new(StringArray::from(vec![
+                Some("a"),
+                Some("bb"),
+                None,
+                Some("ccc"),
+                Some("ddd"),
+            ])) as ArrayRef,
+        ),
+        (
+            "e",
+            Arc::new(StringArray::from(vec![
+                Some("ddd"),
+                Some("cc"),
+                None,
+                Some("bb"),
+                Some("a"),
+            ])) as ArrayRef,
+        ),
+        (
+            "f",
+            Arc::new(StringArray::from(vec![
+                Some("aaaaa"),
+                Some("bbbbb"),
+                None,
+                Some("ccccc"),
+                Some("ddddd"),
+            ])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    assert_batches_eq_content(&batch, &[expected]);
 }
 
 #[test]
@@ -541,6 +611,40 @@ pub fn timestamps_test() {
     }
 }
 
+#[test]
+pub fn timestamps_projection_test() {
+    let path = basic_path("pyarrow_timestamps.orc");
+    let reader = new_arrow_reader(&path, &["timestamp_utc"]);
+    let schema = reader.schema();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+    let expected = [
+        "+----------------------+",
+        "| timestamp_utc        |",
+        "+----------------------+",
+        "|                      |",
+        "| 1970-01-01T00:00:00Z |",
+        "| 1970-01-02T23:59:59Z |",
+        "| 1969-12-31T23:59:59Z |",
+        "| 2262-04-11T11:47:16Z |",
+        "| 2001-04-13T02:14:00Z |",
+        "| 2000-01-01T23:10:10Z |",
+        "| 1900-01-01T14:25:14Z |",
+        "+----------------------+",
+    ];
+    assert_batches_eq(&batches, &expected);
+
+    let expected_schema = Arc::new(Schema::new(vec![Field::new(
+        "timestamp_utc",
+        DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+        true,
+    )]));
+    assert_eq!(schema, expected_schema);
+    for batch in &batches {
+        assert_eq!(batch.schema(), expected_schema);
+    }
+}
+
 #[test]
 pub fn overflowing_timestamps_test() {
     let path = basic_path("overflowing_timestamps.orc");
@@ -548,6 +652,72 @@ pub fn overflowing_timestamps_test() {
     assert!(reader.collect::<Result<Vec<_>, _>>().is_err());
 }
 
+#[test]
+pub fn overflowing_timestamps_null_test() {
+    let path = basic_path("overflowing_timestamps.orc");
+    let f = File::open(&path).expect("no file found");
+    let reader = ArrowReaderBuilder::try_new(f)
+        .unwrap()
+        .with_schema(Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+        ])))
+        .with_timestamp_overflow(TimestampOverflowMode::Null)
+        .build();
+    let batch = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+    let expected = RecordBatch::try_from_iter([
+        (
+            "id",
+            Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(3)])) as ArrayRef,
+        ),
+        (
+            "ts",
+            Arc::new(TimestampNanosecondArray::from(vec![
+                Some(12_345_678_000_000_000),
+                None,
+                Some(12_345_678_000_000_000),
+            ])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    assert_batches_eq_content(&batch, &[expected]);
+}
+
+#[test]
+pub fn overflowing_timestamps_saturate_test() {
+    let path = basic_path("overflowing_timestamps.orc");
+    let f = File::open(&path).expect("no file found");
+    let reader = ArrowReaderBuilder::try_new(f)
+        .unwrap()
+        .with_schema(Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("ts", DataType::Timestamp(TimeUnit::Nanosecond, None), true),
+        ])))
+        .with_timestamp_overflow(TimestampOverflowMode::Saturate)
+        .build();
+    let batch = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+    let expected = RecordBatch::try_from_iter([
+        (
+            "id",
+            Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(3)])) as ArrayRef,
+        ),
+        (
+            "ts",
+            // The out-of-range row (year 1 AD) clamps to the earliest instant a
+            // nanosecond-precision i64 can represent.
+            Arc::new(TimestampNanosecondArray::from(vec![
+                Some(12_345_678_000_000_000),
+                Some(i64::MIN),
+                Some(12_345_678_000_000_000),
+            ])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    assert_batches_eq_content(&batch, &[expected]);
+}
+
 #[test]
 pub fn second_timestamps_test() {
     custom_precision_timestamps_test(TimeUnit::Second)
@@ -625,3 +795,19 @@ pub fn assert_batches_eq(batches: &[RecordBatch], expected_lines: &[&str]) {
         expected_lines, actual_lines
     );
 }
+
+/// Compares `batches` against `expected` by schema and column values, via [`RecordBatch`]'s
+/// own `PartialEq`, rather than by rendering both sides to text with
+/// [`pretty::pretty_format_batches`] and comparing line-for-line like [`assert_batches_eq`]
+/// does. Each side is concatenated into a single batch first, so incidental differences in how
+/// the rows happen to be chunked across batches don't cause a spurious mismatch.
+///
+/// Prefer [`assert_batches_eq`] when the expected output is naturally a short text table (it
+/// doubles as a readable snapshot of what a column looks like); prefer this one for columns
+/// `pretty_format_batches` can't render faithfully (e.g. nested structs/maps) or where a
+/// formatting-only change in `arrow`'s pretty-printer shouldn't be able to break the test.
+pub fn assert_batches_eq_content(batches: &[RecordBatch], expected: &[RecordBatch]) {
+    let actual = concat_batches(&batches[0].schema(), batches).unwrap();
+    let expected = concat_batches(&expected[0].schema(), expected).unwrap();
+    assert_eq!(expected, actual);
+}